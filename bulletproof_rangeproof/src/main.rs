@@ -1,4 +1,5 @@
-use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
+use bulletproofs::{BulletproofGens, PedersenGens, ProofError, RangeProof};
+use curve25519_dalek_ng::ristretto::CompressedRistretto;
 use curve25519_dalek_ng::scalar::Scalar;
 use merlin::Transcript;
 use rand::rngs::OsRng;
@@ -13,6 +14,8 @@ use chrono::{DateTime, Local};
 #[derive(Debug)]
 struct MeasurementResult {
     bitsize: usize,
+    /// Số giá trị được chứng minh chung trong một proof (1 = prove_single)
+    aggregation: usize,
     run_number: usize,
     generation_time_ms: f64,
     verification_time_ms: f64,
@@ -24,6 +27,7 @@ struct MeasurementResult {
 impl MeasurementResult {
     fn new(
         bitsize: usize,
+        aggregation: usize,
         run_number: usize,
         gen_time: Duration,
         ver_time: Duration,
@@ -32,6 +36,7 @@ impl MeasurementResult {
     ) -> Self {
         Self {
             bitsize,
+            aggregation,
             run_number,
             generation_time_ms: gen_time.as_micros() as f64 / 1000.0,
             verification_time_ms: ver_time.as_micros() as f64 / 1000.0,
@@ -43,8 +48,9 @@ impl MeasurementResult {
 
     fn to_csv_line(&self) -> String {
         format!(
-            "{},{},{},{:.2},{:.2},{:.2},{},{}\n",
+            "{},{},{},{},{:.2},{:.2},{:.2},{},{}\n",
             self.bitsize,
+            self.aggregation,
             self.run_number,
             if self.success { "SUCCESS" } else { "FAILED" },
             self.generation_time_ms,
@@ -54,81 +60,214 @@ impl MeasurementResult {
             if self.success { "OK" } else { "ERROR" }
         )
     }
+
+    /// Kích thước proof chia đều cho mỗi giá trị được chứng minh
+    fn size_per_value(&self) -> f64 {
+        self.proof_size_bytes as f64 / self.aggregation as f64
+    }
+
+    /// Tổng thời gian chia đều cho mỗi giá trị được chứng minh
+    fn total_time_per_value_ms(&self) -> f64 {
+        self.total_time_ms / self.aggregation as f64
+    }
+}
+
+/// Kiểm tra 0 <= value < 2^bits mà không dịch bit (không tràn khi bits >= 64)
+fn value_fits_bits(value: u64, bits: usize) -> bool {
+    bits >= 64 || value >> bits == 0
+}
+
+/// Bit-range mà `bulletproofs` hỗ trợ cho một proof
+const SUPPORTED_BITSIZES: [usize; 4] = [8, 16, 32, 64];
+
+/// Giá trị test lớn nhất cho một bit-range: 2^bits - 1
+fn max_test_value(bits: usize) -> u64 {
+    if bits >= 64 { u64::MAX } else { (1u64 << bits) - 1 }
+}
+
+/// Cấu hình một lần chạy benchmark
+#[derive(Debug, PartialEq)]
+struct BenchConfig {
+    bitsizes: Vec<usize>,
+    runs: usize,
+    /// Số giá trị gộp vào một proof (prove_multiple); 1 = prove_single
+    aggregation: usize,
+    /// Seed phân tách miền thêm vào mọi transcript, xem `benchmark_transcript`
+    seed: Option<String>,
 }
 
-/// Thực hiện đo cho một bitsize cụ thể
-fn measure_bitsize(bitsize: usize, test_value: u64, runs: usize) -> Vec<MeasurementResult> {
+/// Đọc cấu hình đo từ tham số dòng lệnh: `[bitsize...] [--runs <n>] [--aggregate <m>] [--seed <s>]`
+/// - returns: mặc định là 8 16 32 64 với 10 lần chạy, không gộp; m phải là lũy thừa của 2
+fn parse_args(args: &[String]) -> Result<BenchConfig, String> {
+    let mut bitsizes = Vec::new();
+    let mut runs = 10;
+    let mut aggregation = 1;
+    let mut seed = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg == "--runs" {
+            runs = match rest.next().and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) if n > 0 => n,
+                _ => return Err("--runs cần một số nguyên dương".to_string()),
+            };
+        } else if arg == "--aggregate" {
+            aggregation = match rest.next().and_then(|m| m.parse::<usize>().ok()) {
+                Some(m) if m.is_power_of_two() => m,
+                _ => return Err("--aggregate cần một lũy thừa của 2".to_string()),
+            };
+        } else if arg == "--seed" {
+            seed = match rest.next() {
+                Some(s) => Some(s.clone()),
+                None => return Err("--seed cần một giá trị".to_string()),
+            };
+        } else {
+            match arg.parse::<usize>() {
+                Ok(bits) if SUPPORTED_BITSIZES.contains(&bits) => bitsizes.push(bits),
+                _ => return Err(format!("bitsize không hợp lệ: {} (hỗ trợ {:?})", arg, SUPPORTED_BITSIZES)),
+            }
+        }
+    }
+    if bitsizes.is_empty() {
+        bitsizes = SUPPORTED_BITSIZES.to_vec();
+    }
+    Ok(BenchConfig { bitsizes, runs, aggregation, seed })
+}
+
+/// Kích thước proof đã serialize; nếu serialize lỗi thì trả về kết quả FAILED của lần chạy đó
+/// để vòng đo tiếp tục thay vì panic
+fn proof_size_or_failure<E: std::fmt::Display>(
+    serialized: Result<Vec<u8>, E>,
+    bitsize: usize,
+    aggregation: usize,
+    run: usize,
+    gen_time: Duration,
+) -> Result<usize, MeasurementResult> {
+    serialized.map(|bytes| bytes.len()).map_err(|e| {
+        println!("  Lần chạy {} thất bại: không serialize được proof: {}", run, e);
+        MeasurementResult::new(bitsize, aggregation, run, gen_time, Duration::ZERO, 0, false)
+    })
+}
+
+/// Nhãn transcript cho một bit-range, dùng chung cho prover và verifier
+fn transcript_label(bitsize: usize) -> &'static [u8] {
+    match bitsize {
+        8 => b"RangeProofBenchmark_8",
+        16 => b"RangeProofBenchmark_16",
+        32 => b"RangeProofBenchmark_32",
+        64 => b"RangeProofBenchmark_64",
+        _ => b"RangeProofBenchmark",
+    }
+}
+
+/// Transcript của một lần prove/verify: `transcript_label(bitsize)`, thêm seed phân tách miền
+/// (nếu có) để các lần chạy benchmark khác nhau không dùng chung transcript
+fn benchmark_transcript(bitsize: usize, seed: Option<&str>) -> Transcript {
+    let mut transcript = Transcript::new(transcript_label(bitsize));
+    if let Some(seed) = seed {
+        transcript.append_message(b"benchmark-seed", seed.as_bytes());
+    }
+    transcript
+}
+
+/// Tạo một proof cho tất cả `values`: prove_single cho một giá trị, prove_multiple khi gộp
+/// - returns: proof và commitment của từng giá trị, theo thứ tự của `values`
+fn prove_values(
+    bp_gens: &BulletproofGens,
+    pc_gens: &PedersenGens,
+    transcript: &mut Transcript,
+    values: &[u64],
+    blindings: &[Scalar],
+    bitsize: usize,
+) -> Result<(RangeProof, Vec<CompressedRistretto>), ProofError> {
+    match (values, blindings) {
+        ([value], [blinding]) => RangeProof::prove_single(bp_gens, pc_gens, transcript, *value, blinding, bitsize)
+            .map(|(proof, commitment)| (proof, vec![commitment])),
+        _ => RangeProof::prove_multiple(bp_gens, pc_gens, transcript, values, blindings, bitsize),
+    }
+}
+
+/// Xác minh proof của `prove_values` với đúng các commitment nó trả về
+fn verify_values(
+    proof: &RangeProof,
+    bp_gens: &BulletproofGens,
+    pc_gens: &PedersenGens,
+    transcript: &mut Transcript,
+    commitments: &[CompressedRistretto],
+    bitsize: usize,
+) -> Result<(), ProofError> {
+    match commitments {
+        [commitment] => proof.verify_single(bp_gens, pc_gens, transcript, commitment, bitsize),
+        _ => proof.verify_multiple(bp_gens, pc_gens, transcript, commitments, bitsize),
+    }
+}
+
+/// Thực hiện đo cho một bitsize cụ thể, gộp `aggregation` giá trị vào mỗi proof; transcript
+/// lấy từ `benchmark_transcript(bitsize, seed)`
+fn measure_bitsize(bitsize: usize, test_value: u64, runs: usize, aggregation: usize, seed: Option<&str>) -> Vec<MeasurementResult> {
     let mut results = Vec::new();
     
     println!("=== Đo {}-bit range proof ===", bitsize);
     println!("Giá trị test: {}", test_value);
     println!("Số lần chạy: {}", runs);
+    println!("Số giá trị mỗi proof: {}", aggregation);
     
     // Validate test_value
-    let max_value = if bitsize >= 64 {
-        u64::MAX
-    } else {
-        (1u64 << bitsize) - 1
-    };
-    
-    if test_value > max_value {
-        panic!("Giá trị test {} vượt quá giới hạn cho {}-bit range (max: {})", test_value, bitsize, max_value);
+    if !value_fits_bits(test_value, bitsize) {
+        panic!("Giá trị test {} vượt quá giới hạn cho {}-bit range (max: 2^{}-1)", test_value, bitsize, bitsize);
     }
     
     // Tạo generators
     let pc_gens = PedersenGens::default();
-    let bp_gens = BulletproofGens::new(bitsize, 1);
+    let bp_gens = BulletproofGens::new(bitsize, aggregation);
+    let values = vec![test_value; aggregation];
     
     for run in 1..=runs {
         println!("  Lần chạy {}...", run);
         
-        // Tạo blinding factor
+        // Tạo blinding factor cho từng giá trị
         let mut rng = OsRng;
-        let mut bytes = [0u8; 64];
-        rng.fill_bytes(&mut bytes);
-        let blinding = Scalar::from_bytes_mod_order(bytes[..32].try_into().unwrap());
+        let blindings: Vec<Scalar> = (0..aggregation).map(|_| {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            Scalar::from_bytes_mod_order(bytes[..32].try_into().unwrap())
+        }).collect();
         
         // Đo thời gian tạo proof
-        let mut transcript = Transcript::new(match bitsize {
-            8 => b"RangeProofBenchmark_8",
-            16 => b"RangeProofBenchmark_16",
-            32 => b"RangeProofBenchmark_32",
-            64 => b"RangeProofBenchmark_64",
-            _ => b"RangeProofBenchmark",
-        });
+        let mut transcript = benchmark_transcript(bitsize, seed);
         
         let gen_start = Instant::now();
         
-        let (proof, committed_value) = RangeProof::prove_single(
+        let (proof, committed_values) = prove_values(
             &bp_gens,
             &pc_gens,
             &mut transcript,
-            test_value,
-            &blinding,
+            &values,
+            &blindings,
             bitsize,
         ).expect(&format!("Tạo proof thất bại cho {}-bit lần {}", bitsize, run));
         
         let gen_time = gen_start.elapsed();
         
         // Đo kích thước proof
-        let proof_size = bincode::serialize(&proof).unwrap().len();
+        let proof_size = match proof_size_or_failure(bincode::serialize(&proof), bitsize, aggregation, run, gen_time) {
+            Ok(size) => size,
+            Err(failed) => {
+                results.push(failed);
+                continue;
+            }
+        };
         
         // Đo thời gian xác minh
-        let mut verifier_transcript = Transcript::new(match bitsize {
-            8 => b"RangeProofBenchmark_8",
-            16 => b"RangeProofBenchmark_16",
-            32 => b"RangeProofBenchmark_32",
-            64 => b"RangeProofBenchmark_64",
-            _ => b"RangeProofBenchmark",
-        });
+        let mut verifier_transcript = benchmark_transcript(bitsize, seed);
         
         let ver_start = Instant::now();
         
-        let verification_result = proof.verify_single(
+        let verification_result = verify_values(
+            &proof,
             &bp_gens,
             &pc_gens,
             &mut verifier_transcript,
-            &committed_value,
+            &committed_values,
             bitsize,
         );
         
@@ -143,6 +282,7 @@ fn measure_bitsize(bitsize: usize, test_value: u64, runs: usize) -> Vec<Measurem
         // Tạo kết quả đo
         let result = MeasurementResult::new(
             bitsize,
+            aggregation,
             run,
             gen_time,
             ver_time,
@@ -170,7 +310,7 @@ fn save_measurements_to_csv(all_results: &[Vec<MeasurementResult>]) -> Result<()
     let mut file = File::create(&filename)?;
     
     // Ghi header đơn giản
-    writeln!(file, "Bitsize,RunNumber,Status,GenerationTime_ms,VerificationTime_ms,TotalTime_ms,ProofSize_bytes,Result")?;
+    writeln!(file, "Bitsize,Aggregation,RunNumber,Status,GenerationTime_ms,VerificationTime_ms,TotalTime_ms,ProofSize_bytes,Result")?;
     
     // Ghi dữ liệu cho tất cả bitsizes
     for results in all_results {
@@ -200,12 +340,19 @@ fn save_summary_report(all_results: &[Vec<MeasurementResult>]) -> Result<(), Box
             let bitsize = first_result.bitsize;
             
             writeln!(file, "--- {}-bit Range Proof ---", bitsize)?;
+            writeln!(file, "Số giá trị mỗi proof: {}", first_result.aggregation)?;
             writeln!(file, "Số lần chạy: {}", results.len())?;
             
-            // Tính thống kê
-            let gen_times: Vec<f64> = results.iter().map(|r| r.generation_time_ms).collect();
-            let ver_times: Vec<f64> = results.iter().map(|r| r.verification_time_ms).collect();
-            let total_times: Vec<f64> = results.iter().map(|r| r.total_time_ms).collect();
+            // Tính thống kê trên các lần chạy thành công
+            let succeeded: Vec<&MeasurementResult> = results.iter().filter(|r| r.success).collect();
+            writeln!(file, "Số lần thất bại: {}", results.len() - succeeded.len())?;
+            let Some(first_ok) = succeeded.first() else {
+                writeln!(file)?;
+                continue;
+            };
+            let gen_times: Vec<f64> = succeeded.iter().map(|r| r.generation_time_ms).collect();
+            let ver_times: Vec<f64> = succeeded.iter().map(|r| r.verification_time_ms).collect();
+            let total_times: Vec<f64> = succeeded.iter().map(|r| r.total_time_ms).collect();
             
             let avg_gen = gen_times.iter().sum::<f64>() / gen_times.len() as f64;
             let avg_ver = ver_times.iter().sum::<f64>() / ver_times.len() as f64;
@@ -214,22 +361,28 @@ fn save_summary_report(all_results: &[Vec<MeasurementResult>]) -> Result<(), Box
             let min_gen = gen_times.iter().fold(f64::INFINITY, |a, &b| a.min(b));
             let max_gen = gen_times.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
             
-            let proof_size = results[0].proof_size_bytes;
+            let proof_size = first_ok.proof_size_bytes;
             
             writeln!(file, "Thời gian tạo trung bình: {:.2} ms", avg_gen)?;
             writeln!(file, "Thời gian xác minh trung bình: {:.2} ms", avg_ver)?;
             writeln!(file, "Tổng thời gian trung bình: {:.2} ms", avg_total)?;
             writeln!(file, "Kích thước proof: {} bytes", proof_size)?;
+            if first_ok.aggregation > 1 {
+                let avg_total_per_value = succeeded.iter().map(|r| r.total_time_per_value_ms()).sum::<f64>() / succeeded.len() as f64;
+                writeln!(file, "Kích thước mỗi giá trị: {:.2} bytes", first_ok.size_per_value())?;
+                writeln!(file, "Tổng thời gian mỗi giá trị: {:.2} ms", avg_total_per_value)?;
+            }
             writeln!(file, "Phạm vi thời gian tạo: {:.2} - {:.2} ms", min_gen, max_gen)?;
             
             // Chi tiết từng lần chạy
             writeln!(file, "Chi tiết từng lần chạy:")?;
             for result in results {
-                writeln!(file, "  Lần {}: Gen={:.2}ms, Ver={:.2}ms, Total={:.2}ms", 
+                writeln!(file, "  Lần {}: Gen={:.2}ms, Ver={:.2}ms, Total={:.2}ms{}", 
                          result.run_number, 
                          result.generation_time_ms,
                          result.verification_time_ms,
-                         result.total_time_ms)?;
+                         result.total_time_ms,
+                         if result.success { "" } else { " (FAILED)" })?;
             }
             writeln!(file)?;
         }
@@ -243,21 +396,30 @@ fn main() {
     println!("=== ĐO VÀ GHI DỮ LIỆU BULLETPROOFS RANGE PROOF ===\n");
     
     // Cấu hình đo
-    let bitsizes = [8, 16, 32, 64];
-    let test_values = [255u64, 65535u64, 4294967295u64, 18446744073709551615u64];
-    let runs_per_bitsize = 10;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config = match parse_args(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Usage: bulletproof_rangeproof [bitsize...] [--runs <n>] [--aggregate <m>] [--seed <s>]");
+            return;
+        }
+    };
     
     println!("Cấu hình đo:");
-    println!("- Bit ranges: {:?}", bitsizes);
-    println!("- Số lần chạy mỗi bitsize: {}", runs_per_bitsize);
-    println!("- Tổng số lần đo: {}\n", bitsizes.len() * runs_per_bitsize);
+    println!("- Bit ranges: {:?}", config.bitsizes);
+    println!("- Số lần chạy mỗi bitsize: {}", config.runs);
+    println!("- Số giá trị mỗi proof: {}", config.aggregation);
+    if let Some(seed) = &config.seed {
+        println!("- Seed transcript: {}", seed);
+    }
+    println!("- Tổng số lần đo: {}\n", config.bitsizes.len() * config.runs);
     
     // Thực hiện đo cho từng bitsize
     let mut all_results = Vec::new();
     
-    for (i, &bitsize) in bitsizes.iter().enumerate() {
-        let test_value = test_values[i];
-        let results = measure_bitsize(bitsize, test_value, runs_per_bitsize);
+    for &bitsize in &config.bitsizes {
+        let results = measure_bitsize(bitsize, max_test_value(bitsize), config.runs, config.aggregation, config.seed.as_deref());
         all_results.push(results);
     }
     
@@ -275,22 +437,96 @@ fn main() {
     // In tổng kết
     println!("\n=== TỔNG KẾT ===");
     let total_runs: usize = all_results.iter().map(|r| r.len()).sum();
+    let failed_runs = all_results.iter().flatten().filter(|r| !r.success).count();
     println!(" Tổng số lần đo: {}", total_runs);
-    println!(" Tất cả các lần đo đều thành công");
+    if failed_runs == 0 {
+        println!(" Tất cả các lần đo đều thành công");
+    } else {
+        println!(" Số lần đo thất bại: {} (đánh dấu FAILED trong CSV)", failed_runs);
+    }
     println!(" Dữ liệu đã được lưu vào file CSV và báo cáo");
     
     println!("\n=== THỐNG KÊ NHANH ===");
     for results in &all_results {
-        if let Some(first_result) = results.first() {
+        let succeeded: Vec<&MeasurementResult> = results.iter().filter(|r| r.success).collect();
+        if let Some(first_result) = succeeded.first() {
             let bitsize = first_result.bitsize;
-            let avg_gen: f64 = results.iter().map(|r| r.generation_time_ms).sum::<f64>() / results.len() as f64;
-            let avg_ver: f64 = results.iter().map(|r| r.verification_time_ms).sum::<f64>() / results.len() as f64;
+            let avg_gen: f64 = succeeded.iter().map(|r| r.generation_time_ms).sum::<f64>() / succeeded.len() as f64;
+            let avg_ver: f64 = succeeded.iter().map(|r| r.verification_time_ms).sum::<f64>() / succeeded.len() as f64;
             let proof_size = first_result.proof_size_bytes;
             
             println!("{}-bit: Gen={:.2}ms, Ver={:.2}ms, Size={}B", 
                      bitsize, avg_gen, avg_ver, proof_size);
+            if first_result.aggregation > 1 {
+                let avg_per_value: f64 = succeeded.iter().map(|r| r.total_time_per_value_ms()).sum::<f64>() / succeeded.len() as f64;
+                println!("  mỗi giá trị (gộp {}): Total={:.2}ms, Size={:.2}B",
+                         first_result.aggregation, avg_per_value, first_result.size_per_value());
+            }
         }
     }
     
     println!("\n Hoàn thành đo và ghi dữ liệu!");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Purpose: một lỗi serialize được ghi thành kết quả FAILED thay vì panic
+    // Params: Ok với 672 byte và Err(bincode SizeLimit) cho 32-bit, lần chạy 3
+    // Output: Ok(672); kết quả thất bại có success = false và dòng CSV đánh dấu FAILED/ERROR
+    // Usage: `cargo test -- tests` or `cargo test`
+    #[test]
+    fn serialization_failure_records_failed_result() {
+        let gen_time = Duration::from_millis(5);
+        let ok = proof_size_or_failure(Ok::<_, bincode::Error>(vec![0u8; 672]), 32, 1, 3, gen_time);
+        assert_eq!(ok.unwrap(), 672);
+
+        let err: bincode::Error = Box::new(bincode::ErrorKind::SizeLimit);
+        let failed = proof_size_or_failure(Err(err), 32, 1, 3, gen_time).unwrap_err();
+        assert!(!failed.success);
+        assert_eq!((failed.bitsize, failed.run_number, failed.proof_size_bytes), (32, 3, 0));
+        assert_eq!(failed.to_csv_line(), "32,1,3,FAILED,5.00,0.00,5.00,0,ERROR\n");
+    }
+
+    // Purpose: bốn giá trị gộp trong một proof xác minh được và nhỏ hơn bốn proof riêng
+    // Params: 8-bit, giá trị 0, 1, 200, 255 với blinding ngẫu nhiên; --aggregate 4 và giá trị sai
+    // Output: verify_multiple thành công; proof gộp nhỏ hơn 4 lần proof đơn; parse_args đọc m
+    // Usage: `cargo test -- tests` or `cargo test`
+    #[test]
+    fn aggregated_proof_of_four_values_verifies() {
+        let (pc_gens, bp_gens) = (PedersenGens::default(), BulletproofGens::new(8, 4));
+        let values = [0u64, 1, 200, 255];
+        let blindings: Vec<Scalar> = values.iter().map(|_| Scalar::random(&mut OsRng)).collect();
+
+        let (proof, commitments) = prove_values(&bp_gens, &pc_gens, &mut Transcript::new(b"aggregated"), &values, &blindings, 8).unwrap();
+        assert_eq!(commitments.len(), 4);
+        assert!(verify_values(&proof, &bp_gens, &pc_gens, &mut Transcript::new(b"aggregated"), &commitments, 8).is_ok());
+
+        let (single, _) = prove_values(&bp_gens, &pc_gens, &mut Transcript::new(b"single"), &values[..1], &blindings[..1], 8).unwrap();
+        assert!(bincode::serialize(&proof).unwrap().len() < 4 * bincode::serialize(&single).unwrap().len());
+
+        let args: Vec<String> = ["8", "--aggregate", "4"].map(String::from).to_vec();
+        assert_eq!(parse_args(&args).unwrap(), BenchConfig { bitsizes: vec![8], runs: 10, aggregation: 4, seed: None });
+        assert!(parse_args(&["--aggregate".to_string(), "3".to_string()]).is_err());
+    }
+
+    // Purpose: prover và verifier dùng cùng một transcript cho mọi bit-range; seed tách các lần chạy
+    // Params: SUPPORTED_BITSIZES; proof 8-bit của 200 với seed "run-a", xác minh với "run-a" và "run-b"
+    // Output: nhãn riêng cho từng bit-range; proof chỉ xác minh được với đúng seed đã dùng để tạo
+    // Usage: `cargo test -- tests` or `cargo test`
+    #[test]
+    fn prover_and_verifier_share_transcript_label() {
+        for bits in SUPPORTED_BITSIZES {
+            assert_eq!(transcript_label(bits), format!("RangeProofBenchmark_{}", bits).as_bytes());
+        }
+        assert_eq!(transcript_label(12), b"RangeProofBenchmark");
+
+        let (pc_gens, bp_gens) = (PedersenGens::default(), BulletproofGens::new(8, 1));
+        let blinding = [Scalar::random(&mut OsRng)];
+        let (proof, commitments) = prove_values(&bp_gens, &pc_gens, &mut benchmark_transcript(8, Some("run-a")), &[200], &blinding, 8).unwrap();
+        assert!(verify_values(&proof, &bp_gens, &pc_gens, &mut benchmark_transcript(8, Some("run-a")), &commitments, 8).is_ok());
+        assert!(verify_values(&proof, &bp_gens, &pc_gens, &mut benchmark_transcript(8, Some("run-b")), &commitments, 8).is_err());
+        assert!(verify_values(&proof, &bp_gens, &pc_gens, &mut benchmark_transcript(8, None), &commitments, 8).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bulletproof_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Purpose: bit sizes and the run count come from the command line
+// Params: `8 16 --runs 2` run in an empty temp directory; `12` as an unsupported bit size
+// Output: one CSV with a header and 2 x 2 rows for bit sizes 8 and 16; no CSV for `12`
+// Usage: `cargo test --test cli_args` or `cargo test`
+#[test]
+fn custom_bitsizes_and_runs_set_csv_rows() {
+    let dir = temp_dir("args");
+    let out = Command::new(env!("CARGO_BIN_EXE_bulletproof_rangeproof"))
+        .args(["8", "16", "--runs", "2"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+
+    let csvs: Vec<PathBuf> = std::fs::read_dir(&dir).unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.file_name().unwrap().to_string_lossy().starts_with("bulletproofs_measurements_"))
+        .collect();
+    assert_eq!(csvs.len(), 1);
+    let csv = std::fs::read_to_string(&csvs[0]).unwrap();
+    let rows: Vec<&str> = csv.lines().skip(1).collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows.iter().filter(|r| r.starts_with("8,")).count(), 2);
+    assert_eq!(rows.iter().filter(|r| r.starts_with("16,")).count(), 2);
+    assert!(rows.iter().all(|r| r.contains(",SUCCESS,")));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let dir = temp_dir("bad_args");
+    let out = Command::new(env!("CARGO_BIN_EXE_bulletproof_rangeproof"))
+        .args(["12"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("bitsize không hợp lệ: 12"));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
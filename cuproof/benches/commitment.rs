@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use cuproof::commitment::{mod_exp, mod_exp_windowed, multi_mod_exp};
+use cuproof::setup::fast_test_setup;
+use cuproof::util::{inner_product, random_bigint};
+use num_bigint::BigInt;
+
+/// Naive product of individual modpows vs. simultaneous multi-exponentiation
+/// for the vector sizes the IPP verifier folds (dimension 256+).
+fn bench_multi_mod_exp(c: &mut Criterion) {
+    let (_g, _h, n) = fast_test_setup();
+    let mut group = c.benchmark_group("multi_mod_exp");
+    group.sample_size(10);
+    for len in [64usize, 256, 512] {
+        let bases: Vec<BigInt> = (0..len).map(|_| random_bigint(512) % &n).collect();
+        let exps: Vec<BigInt> = (0..len).map(|_| random_bigint(256)).collect();
+        group.bench_with_input(BenchmarkId::new("naive", len), &len, |b, _| {
+            b.iter(|| bases.iter().zip(&exps)
+                .fold(BigInt::from(1), |acc, (base, e)| acc * mod_exp(base, e, &n) % &n))
+        });
+        group.bench_with_input(BenchmarkId::new("straus", len), &len, |b, _| {
+            b.iter(|| multi_mod_exp(&bases, &exps, &n))
+        });
+    }
+    group.finish();
+}
+
+/// `mod_exp` vs. `mod_exp_windowed` for the 256-bit exponents used as blindings, per window size
+/// (plain-reduction windows against num-bigint's Montgomery `modpow`)
+fn bench_mod_exp_windowed(c: &mut Criterion) {
+    let (g, _h, n) = fast_test_setup();
+    let exps: Vec<BigInt> = (0..16).map(|_| random_bigint(256)).collect();
+    let mut group = c.benchmark_group("mod_exp_windowed");
+    group.sample_size(20);
+    group.bench_function("modpow", |b| {
+        b.iter(|| exps.iter().map(|e| mod_exp(&g, e, &n)).collect::<Vec<_>>())
+    });
+    for window_bits in [1u32, 2, 4, 6, 8] {
+        group.bench_with_input(BenchmarkId::new("windowed", window_bits), &window_bits, |b, &w| {
+            b.iter(|| exps.iter().map(|e| mod_exp_windowed(&g, e, &n, w)).collect::<Vec<_>>())
+        });
+    }
+    group.finish();
+}
+
+/// `inner_product` vs. summing one fresh product per element, over length-1024 vectors of
+/// signed 512-bit entries (the size of the prover's l and r at dimension 1024)
+fn bench_inner_product(c: &mut Criterion) {
+    let signed = |i: usize| if i.is_multiple_of(2) { random_bigint(512) } else { -random_bigint(512) };
+    let l: Vec<BigInt> = (0..1024).map(signed).collect();
+    let r: Vec<BigInt> = (0..1024).map(signed).collect();
+    let mut group = c.benchmark_group("inner_product");
+    group.bench_function("map_sum", |b| {
+        b.iter(|| l.iter().zip(&r).map(|(x, y)| x * y).sum::<BigInt>())
+    });
+    group.bench_function("scratch", |b| b.iter(|| inner_product(&l, &r)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_multi_mod_exp, bench_mod_exp_windowed, bench_inner_product);
+criterion_main!(benches);
@@ -0,0 +1,103 @@
+use cuproof::backend::RsaBackend;
+use cuproof::baseline;
+use cuproof::fiat_shamir::Sha256Hasher;
+use cuproof::range_proof::{interactive_verify_step1, interactive_verify_step2, interactive_verify_step3, ipp_commitment, prove_inner_product, IppGenerators};
+use cuproof::setup::fast_test_setup;
+use cuproof::util::{inner_product, random_bigint};
+use cuproof::verify::verify_inner_product;
+use num_bigint::BigInt;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// System allocator that counts allocations and allocated bytes
+struct CountingAlloc;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// (allocations, bytes) made while running `f`
+fn count<T>(f: impl FnOnce() -> T) -> (usize, usize, T) {
+    let (a0, b0) = (ALLOCS.load(Ordering::Relaxed), BYTES.load(Ordering::Relaxed));
+    let out = f();
+    (ALLOCS.load(Ordering::Relaxed) - a0, BYTES.load(Ordering::Relaxed) - b0, out)
+}
+
+/// Allocation counts of the clone-based (`cuproof::baseline`) and borrowing verification
+/// paths, and of the two `inner_product` implementations.
+/// Run with `cargo bench --bench verify_alloc --features alloc-baseline`.
+fn main() {
+    let (g, h, n) = fast_test_setup();
+    let backend = RsaBackend::<Sha256Hasher>::new(&g, &h, &n);
+
+    println!("{:<28} {:>12} {:>12}", "path", "allocations", "bytes");
+    for dim in [8usize, 64] {
+        let gens = IppGenerators::new(&backend, dim);
+        let l: Vec<BigInt> = (1..=dim as u64).map(BigInt::from).collect();
+        let r: Vec<BigInt> = (1..=dim as u64).rev().map(BigInt::from).collect();
+        let P = ipp_commitment(&backend, &gens, &l, &r);
+        let proof = prove_inner_product(&backend, &gens, &l, &r);
+
+        let (a_old, b_old, ok_old) = count(|| baseline::verify_inner_product(&backend, &gens, &P, &proof));
+        let (a_new, b_new, ok_new) = count(|| verify_inner_product(&backend, &gens, &P, &proof));
+        assert!(ok_old && ok_new, "both paths must accept the proof");
+        println!("{:<28} {:>12} {:>12}", format!("ipp dim={} (clone)", dim), a_old, b_old);
+        println!("{:<28} {:>12} {:>12}", format!("ipp dim={} (borrow)", dim), a_new, b_new);
+    }
+
+    // Steps 1-3 of the interactive verifier, receiving A, S, T1, T2 of the prover
+    let (a, s, t1, t2) = (random_bigint(2048), random_bigint(2048), random_bigint(2048), random_bigint(2048));
+    let (a_old, b_old, _) = count(|| {
+        let (mut state, _, _) = baseline::interactive_verify_step1(&g, &h, &n);
+        baseline::interactive_verify_step2(&mut state, &a, &s);
+        baseline::interactive_verify_step3(&mut state, &t1, &t2);
+        state
+    });
+    let (a_new, b_new, _) = count(|| {
+        let (mut state, _, _) = interactive_verify_step1(&g, &h, &n);
+        interactive_verify_step2(&mut state, &a, &s);
+        interactive_verify_step3(&mut state, &t1, &t2);
+        state
+    });
+    println!("{:<28} {:>12} {:>12}", "verifier state (owned)", a_old, b_old);
+    println!("{:<28} {:>12} {:>12}", "verifier state (borrowed)", a_new, b_new);
+
+    // 256-bit entries at dimension 1024, the size of the prover's l and r vectors
+    let l: Vec<BigInt> = (0..1024).map(|_| random_bigint(256)).collect();
+    let r: Vec<BigInt> = (0..1024).map(|_| random_bigint(256)).collect();
+    let (a_old, b_old, old) = count(|| baseline::inner_product(&l, &r));
+    let (a_new, b_new, new) = count(|| inner_product(&l, &r));
+    assert_eq!(old, new, "both inner products must agree");
+    println!("{:<28} {:>12} {:>12}", "inner_product 1024 (map)", a_old, b_old);
+    println!("{:<28} {:>12} {:>12}", "inner_product 1024 (scratch)", a_new, b_new);
+
+    const ROUNDS: u32 = 200;
+    let start = Instant::now();
+    for _ in 0..ROUNDS { std::hint::black_box(baseline::inner_product(&l, &r)); }
+    let t_old = start.elapsed() / ROUNDS;
+    let start = Instant::now();
+    for _ in 0..ROUNDS { std::hint::black_box(inner_product(&l, &r)); }
+    let t_new = start.elapsed() / ROUNDS;
+    println!("inner_product 1024: {:?} (map) vs {:?} (scratch) per call", t_old, t_new);
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.19;
+
+/**
+ * @title CuproofAggregatedVerifier256
+ * @dev Verify aggregated Cuproof proofs (several values in one range) with a 256-bit modulus
+ * @notice Layout matches `serialize_aggregated_for_evm` / `export_aggregated_json` in src/evm.rs:
+ *         12 shared scalars, then C, C_v1, C_v2 with one entry per value, then the IPP
+ */
+contract CuproofAggregatedVerifier256 {
+    // Events
+    event AggregatedProofVerified(
+        address indexed subject,
+        bytes32 indexed proofHash,
+        uint256 valueCount,
+        uint256 rangeMin,
+        uint256 rangeMax,
+        bool isValid,
+        uint256 timestamp
+    );
+
+    // Aggregated proof structure
+    struct AggregatedProof {
+        uint256 A;
+        uint256 S;
+        uint256 T1;
+        uint256 T2;
+        uint256 tau_x;
+        uint256 mu;
+        uint256 t_hat;
+        uint256 t0;
+        uint256 t1;
+        uint256 t2;
+        uint256 tau1;
+        uint256 tau2;
+        uint256[] C;
+        uint256[] C_v1;
+        uint256[] C_v2;
+        uint256[] ipp_L;
+        uint256[] ipp_R;
+        uint256 ipp_a;
+        uint256 ipp_b;
+    }
+
+    // State
+    mapping(bytes32 => bool) public verifiedProofs;
+    mapping(address => bytes32) public latestProofHash;
+
+    /**
+     * @dev Modular exponentiation: base^exp mod modulus
+     */
+    function modExp(uint256 base, uint256 exp, uint256 modulus) internal pure returns (uint256) {
+        if (modulus == 0) return 0;
+        if (exp == 0) return 1;
+        if (base == 0) return 0;
+
+        uint256 result = 1;
+        base = base % modulus;
+
+        while (exp > 0) {
+            if (exp % 2 == 1) {
+                result = mulmod(result, base, modulus);
+            }
+            exp = exp >> 1;
+            base = mulmod(base, base, modulus);
+        }
+        return result;
+    }
+
+    /**
+     * @dev Pedersen commitment: g^m * h^r mod n
+     */
+    function pedersenCommit(uint256 m, uint256 r, uint256 g, uint256 h, uint256 n) internal pure returns (uint256) {
+        return mulmod(modExp(g, m, n), modExp(h, r, n), n);
+    }
+
+    /**
+     * @dev Fiat-Shamir hash
+     */
+    function fiatShamir(uint256[] memory inputs) internal pure returns (uint256) {
+        bytes memory data;
+        for (uint256 i = 0; i < inputs.length; i++) {
+            data = abi.encodePacked(data, inputs[i]);
+        }
+        return uint256(keccak256(data));
+    }
+
+    /**
+     * @dev Compute Fiat-Shamir challenges
+     * @notice y hashes A, S, then all C_i, all C_v1_i, all C_v2_i (`aggregated_transcript`)
+     */
+    function computeChallenges(AggregatedProof memory proof, uint256 n) internal pure returns (uint256 y, uint256 z, uint256 x) {
+        uint256 m = proof.C.length;
+        uint256[] memory fsInputs = new uint256[](2 + 3 * m);
+        fsInputs[0] = proof.A;
+        fsInputs[1] = proof.S;
+        for (uint256 i = 0; i < m; i++) {
+            fsInputs[2 + i] = proof.C[i];
+            fsInputs[2 + m + i] = proof.C_v1[i];
+            fsInputs[2 + 2 * m + i] = proof.C_v2[i];
+        }
+        y = fiatShamir(fsInputs) % n;
+        require(y != 0, "Invalid challenge y");
+
+        uint256[] memory zInputs = new uint256[](1);
+        zInputs[0] = y;
+        z = fiatShamir(zInputs) % n;
+        require(z != 0, "Invalid challenge z");
+
+        uint256[] memory xInputs = new uint256[](2);
+        xInputs[0] = proof.T1;
+        xInputs[1] = proof.T2;
+        x = fiatShamir(xInputs) % n;
+        require(x != 0, "Invalid challenge x");
+    }
+
+    /**
+     * @dev Verify commitments and polynomial
+     */
+    function verifyCommitmentsAndPolynomial(
+        AggregatedProof memory proof,
+        uint256 x,
+        uint256 g,
+        uint256 h,
+        uint256 n
+    ) internal pure {
+        require(pedersenCommit(proof.t1, proof.tau1, g, h, n) == proof.T1, "T1 commitment mismatch");
+        require(pedersenCommit(proof.t2, proof.tau2, g, h, n) == proof.T2, "T2 commitment mismatch");
+
+        uint256 x2 = mulmod(x, x, n);
+        uint256 rhs_t = addmod(addmod(proof.t0, mulmod(proof.t1, x, n), n), mulmod(proof.t2, x2, n), n);
+        require(proof.t_hat == rhs_t, "t_hat mismatch");
+
+        uint256 lhs = pedersenCommit(proof.t_hat, proof.tau_x, g, h, n);
+        uint256 rhs = pedersenCommit(rhs_t, proof.tau_x, g, h, n);
+        require(lhs == rhs, "t_hat commitment mismatch");
+    }
+
+    /**
+     * @dev Number of IPP levels for m values: log2 of m * 64 rounded up to a power of two
+     */
+    function expectedIppLevels(uint256 m) internal pure returns (uint256 levels) {
+        uint256 size = 1;
+        while (size < m * 64) {
+            size <<= 1;
+            levels++;
+        }
+    }
+
+    /**
+     * @dev Verify basic sanity checks
+     */
+    function verifySanityChecks(AggregatedProof memory proof, uint256 n) internal pure {
+        uint256 m = proof.C.length;
+        require(m > 0, "No values");
+        require(proof.C_v1.length == m && proof.C_v2.length == m, "Commitment count mismatch");
+
+        require(proof.A % n != 0, "A is zero mod n");
+        require(proof.S % n != 0, "S is zero mod n");
+        require(proof.T1 % n != 0, "T1 is zero mod n");
+        require(proof.T2 % n != 0, "T2 is zero mod n");
+        for (uint256 i = 0; i < m; i++) {
+            require(proof.C[i] % n != 0, "C is zero mod n");
+            require(proof.C_v1[i] % n != 0, "C_v1 is zero mod n");
+            require(proof.C_v2[i] % n != 0, "C_v2 is zero mod n");
+
+            require(proof.C[i] != proof.C_v1[i], "C == C_v1");
+            require(proof.C[i] != proof.C_v2[i], "C == C_v2");
+            require(proof.C_v1[i] != proof.C_v2[i], "C_v1 == C_v2");
+        }
+
+        require(proof.ipp_L.length == proof.ipp_R.length, "IPP length mismatch");
+        require(proof.ipp_L.length == expectedIppLevels(m), "IPP levels mismatch");
+    }
+
+    /**
+     * @dev Verify an aggregated proof with AggregatedProof struct and parameters
+     * @param proof AggregatedProof struct as written by export_aggregated_json
+     * @param g Generator g
+     * @param h Generator h
+     * @param n Modulus n
+     * @param rangeMin Minimum value of the shared range
+     * @param rangeMax Maximum value of the shared range
+     * @param subject Address of the prover
+     * @return bool True if proof is valid
+     */
+    function verifyAggregatedProof(
+        AggregatedProof memory proof,
+        uint256 g,
+        uint256 h,
+        uint256 n,
+        uint256 rangeMin,
+        uint256 rangeMax,
+        address subject
+    ) external returns (bool) {
+        require(g != 0 && h != 0 && n != 0, "Invalid parameters");
+        require(g < n && h < n, "Parameters must be less than modulus");
+        require(rangeMin <= rangeMax, "Invalid range");
+        require(subject != address(0), "Invalid subject");
+
+        verifySanityChecks(proof, n);
+
+        bytes32 proofHash = keccak256(abi.encode(proof, g, h, n));
+        require(!verifiedProofs[proofHash], "Proof already verified");
+
+        (, , uint256 x) = computeChallenges(proof, n);
+
+        verifyCommitmentsAndPolynomial(proof, x, g, h, n);
+
+        verifiedProofs[proofHash] = true;
+        latestProofHash[subject] = proofHash;
+
+        emit AggregatedProofVerified(subject, proofHash, proof.C.length, rangeMin, rangeMax, true, block.timestamp);
+
+        return true;
+    }
+
+    /**
+     * @dev Check if a proof has been verified
+     */
+    function isProofVerified(bytes32 proofHash) external view returns (bool) {
+        return verifiedProofs[proofHash];
+    }
+
+    /**
+     * @dev Get latest proof hash for a subject
+     */
+    function getLatestProofHash(address subject) external view returns (bytes32) {
+        return latestProofHash[subject];
+    }
+}
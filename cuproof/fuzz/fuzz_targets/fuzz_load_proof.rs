@@ -0,0 +1,20 @@
+//! Fuzz the proof file parser: arbitrary file contents must give `Ok` or `Err`, never a panic.
+//!
+//! Run from `cuproof/` (needs a nightly toolchain and `cargo install cargo-fuzz`):
+//!
+//!     cargo +nightly fuzz run fuzz_load_proof
+//!
+//! `fuzz/corpus/fuzz_load_proof` seeds the run with a valid proof (`valid_v2`, and `valid` in the
+//! header-less legacy layout) and truncated variants;
+//! crashing inputs are written to `fuzz/artifacts/fuzz_load_proof`.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // `load_proof` reads the file with `read_to_string`, so non-UTF-8 input never reaches the parser
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = cuproof::util::parse_proof(text);
+        let _ = cuproof::util::parse_proof_legacy(text);
+    }
+});
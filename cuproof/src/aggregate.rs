@@ -0,0 +1,303 @@
+use crate::{util::*, commitment::*, fiat_shamir::*};
+use crate::backend::{GroupBackend, RsaBackend};
+use crate::range_proof::{IPPProof, CommitmentBlindings, IppGenerators, RangeWeights, DEFAULT_DIMENSION, expected_auxiliary, padded_range_witness, ipp_statement, prove_range_relation, range_binding_holds, range_blocks, range_relation_holds};
+use crate::verify::{verify_inner_product, expected_ipp_levels};
+use num_bigint::BigInt;
+use num_traits::Zero;
+use rand::rngs::OsRng;
+
+/// Several values proven with shared A, S, T1, T2 and one IPP over the concatenated witness
+/// vectors (`DEFAULT_DIMENSION` entries per value, zero-padded to a power of two)
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregatedCuproof {
+    pub A: BigInt,
+    pub S: BigInt,
+    pub T1: BigInt,
+    pub T2: BigInt,
+    pub tau_x: BigInt,
+    pub mu: BigInt,
+    pub t_hat: BigInt,
+    pub C: Vec<BigInt>,
+    pub C_v1: Vec<BigInt>,
+    pub C_v2: Vec<BigInt>,
+    pub t0: BigInt,
+    pub t1: BigInt,
+    pub t2: BigInt,
+    pub tau1: BigInt,
+    pub tau2: BigInt,
+    pub ipp_proof: IPPProof,
+}
+
+impl AggregatedCuproof {
+    /// Number of aggregated values (one commitment C_i each)
+    pub fn len(&self) -> usize {
+        self.C.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.C.is_empty()
+    }
+
+    /// Commitments C_i in proving order: index i belongs to the i-th value passed to the prover
+    pub fn commitments(&self) -> &[BigInt] {
+        &self.C
+    }
+
+    /// Commitment C_i of the value at `index`, or None past the last value
+    pub fn commitment(&self, index: usize) -> Option<&BigInt> {
+        self.C.get(index)
+    }
+}
+
+/// IPP length of an aggregated proof over m values: m * `DEFAULT_DIMENSION` rounded up to a power of two
+pub fn aggregated_dimension(m: usize) -> usize {
+    (m * DEFAULT_DIMENSION).next_power_of_two()
+}
+
+/// Fiat–Shamir inputs for y of an aggregated proof: A, S, then all C_i, C_v1_i, C_v2_i
+#[allow(non_snake_case)]
+pub fn aggregated_transcript<'a>(A: &'a BigInt, S: &'a BigInt, C: &'a [BigInt], C_v1: &'a [BigInt], C_v2: &'a [BigInt]) -> Vec<&'a BigInt> {
+    let mut inputs = vec![A, S];
+    inputs.extend(C.iter());
+    inputs.extend(C_v1.iter());
+    inputs.extend(C_v2.iter());
+    inputs
+}
+
+/// Aggregated proof of several values sharing the range [a, b]
+/// - params: values and blindings of the same, non-zero length (C_i = g^v_i h^r_i), a/b range, g/h/n
+/// - returns: AggregatedCuproof; C_v1_i, C_v2_i use fresh random blindings, so they are not bound
+///   to C_i (use `cuproof_prove_aggregated_multi` for per-value binding)
+/// - usage: panics if a value lies outside [a, b]; verify with `cuproof_verify_aggregated`
+#[allow(non_snake_case)]
+pub fn cuproof_prove_aggregated(values: &[BigInt], blindings: &[BigInt], a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> AggregatedCuproof {
+    assert!(!values.is_empty(), "aggregation needs at least one value");
+    assert_eq!(values.len(), blindings.len(), "one blinding per value");
+
+    let mut d = Vec::with_capacity(aggregated_dimension(values.len()));
+    let mut C = Vec::with_capacity(values.len());
+    let mut C_v1 = Vec::with_capacity(values.len());
+    let mut C_v2 = Vec::with_capacity(values.len());
+    let mut block_blindings = Vec::with_capacity(2 * values.len());
+    for (v, r) in values.iter().zip(blindings) {
+        assert!(value_in_range(v, a, b), "value outside the shared range");
+        let (v1, v2) = expected_auxiliary(v, a, b);
+        d.extend(padded_range_witness(&v1, &v2, DEFAULT_DIMENSION).expect("v must lie in [a, b]"));
+        let (r_v1, r_v2) = (random_bigint_mod(n), random_bigint_mod(n));
+        C.push(pedersen_commit(g, h, v, r, n));
+        C_v1.push(pedersen_commit(g, h, &v1, &r_v1, n));
+        C_v2.push(pedersen_commit(g, h, &v2, &r_v2, n));
+        block_blindings.extend([r_v1, r_v2]);
+    }
+    prove_aggregated_witness(d, C, C_v1, C_v2, &block_blindings, &RsaBackend::<DefaultChallengeHasher>::new(g, h, n))
+}
+
+/// Aggregated proof where each value carries its own range [a_i, b_i]
+/// - params: items (v_i, r_i, a_i, b_i), non-empty, each with a_i <= v_i <= b_i; C_i = g^v_i h^r_i
+/// - returns: AggregatedCuproof with C_v1_i, C_v2_i derived from C_i (`CommitmentBlindings`), so
+///   `cuproof_verify_aggregated_multi` can bind them to each item's own range
+/// - usage: panics if a value lies outside its own range
+#[allow(non_snake_case)]
+pub fn cuproof_prove_aggregated_multi(items: &[(BigInt, BigInt, BigInt, BigInt)], g: &BigInt, h: &BigInt, n: &BigInt) -> AggregatedCuproof {
+    assert!(!items.is_empty(), "aggregation needs at least one value");
+
+    let mut d = Vec::with_capacity(aggregated_dimension(items.len()));
+    let mut C = Vec::with_capacity(items.len());
+    let mut C_v1 = Vec::with_capacity(items.len());
+    let mut C_v2 = Vec::with_capacity(items.len());
+    let mut block_blindings = Vec::with_capacity(2 * items.len());
+    for (v, r, a, b) in items {
+        assert!(value_in_range(v, a, b), "value outside its own range");
+        let (v1, v2) = expected_auxiliary(v, a, b);
+        d.extend(padded_range_witness(&v1, &v2, DEFAULT_DIMENSION).expect("v must lie in [a, b]"));
+        let blindings = CommitmentBlindings::new(r);
+        let (c, c_v1, c_v2) = blindings.commit(v, &v1, &v2, g, h, n);
+        C.push(c);
+        C_v1.push(c_v1);
+        C_v2.push(c_v2);
+        block_blindings.extend([blindings.r_v1, blindings.r_v2]);
+    }
+    prove_aggregated_witness(d, C, C_v1, C_v2, &block_blindings, &RsaBackend::<DefaultChallengeHasher>::new(g, h, n))
+}
+
+/// Shared part of the aggregated provers: `prove_range_relation` over the concatenated witness
+/// d, zero-padded to `aggregated_dimension`, with y hashed over every commitment and blocks
+/// 2i, 2i + 1 opening C_v1_i, C_v2_i (`block_blindings` in the same order)
+#[allow(non_snake_case)]
+fn prove_aggregated_witness<B: GroupBackend<Element = BigInt>>(mut d: Vec<BigInt>, C: Vec<BigInt>, C_v1: Vec<BigInt>, C_v2: Vec<BigInt>, block_blindings: &[BigInt], backend: &B) -> AggregatedCuproof {
+    let total = aggregated_dimension(C.len());
+    d.resize(total, BigInt::zero());
+    let blocks = range_blocks(C.len(), DEFAULT_DIMENSION, total);
+    let p = prove_range_relation(backend, &d, &blocks, block_blindings,
+        |A, S| backend.challenge(&aggregated_transcript(A, S, &C, &C_v1, &C_v2)), &mut OsRng);
+
+    AggregatedCuproof {
+        A: p.A, S: p.S, T1: p.T1, T2: p.T2, tau_x: p.tau_x, mu: p.mu, t_hat: p.t_hat, C, C_v1, C_v2,
+        t0: p.t0, t1: p.t1, t2: p.t2, tau1: p.tau1, tau2: p.tau2, ipp_proof: p.ipp_proof,
+    }
+}
+
+/// Verify an aggregated proof with the checks of `cuproof_verify`, applied to the shared
+/// scalars, every per-value commitment triple and the IPP over `aggregated_dimension(len)`
+/// - returns: true if the proof is well-formed for (g, h, n); says nothing about the range
+/// - usage: pair with `cuproof_prove_aggregated`
+#[allow(non_snake_case)]
+pub fn cuproof_verify_aggregated(proof: &AggregatedCuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    let m = proof.len();
+    if proof.is_empty() || proof.C_v1.len() != m || proof.C_v2.len() != m { return false; }
+    let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+
+    // 1. Fiat–Shamir
+    let y = backend.challenge(&aggregated_transcript(&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2));
+    let z = backend.challenge(&[&y]);
+    let x = backend.challenge(&[&proof.T1, &proof.T2]);
+    if y.is_zero() || z.is_zero() || x.is_zero() { return false; }
+
+    // 2. Check T1, T2 commitments
+    if !ct_eq(&pedersen_commit(g, h, &proof.t1, &proof.tau1, n), &proof.T1, n) { return false; }
+    if !ct_eq(&pedersen_commit(g, h, &proof.t2, &proof.tau2, n), &proof.T2, n) { return false; }
+
+    // 3. Verify t_hat consistency: t_hat ?= t0 + t1 x + t2 x^2
+    let rhs_t = &proof.t0 + &(&proof.t1 * &x) + &(&proof.t2 * &x * &x);
+    if proof.t_hat != rhs_t { return false; }
+
+    // 4. IPP structure over m * DEFAULT_DIMENSION elements padded to a power of two
+    let dimension = aggregated_dimension(m);
+    if proof.ipp_proof.L.len() != proof.ipp_proof.R.len() { return false; }
+    if expected_ipp_levels(dimension) != Ok(proof.ipp_proof.L.len()) { return false; }
+
+    // 5. Basic sanity: commitments must be non-zero modulo n
+    for c in [&proof.A, &proof.S, &proof.T1, &proof.T2].into_iter()
+        .chain(proof.C.iter()).chain(proof.C_v1.iter()).chain(proof.C_v2.iter()) {
+        if (c % n).is_zero() { return false; }
+    }
+
+    // 6. Per value, C, C_v1, C_v2 must be pairwise distinct
+    for i in 0..m {
+        if proof.C[i] == proof.C_v1[i] || proof.C[i] == proof.C_v2[i] || proof.C_v1[i] == proof.C_v2[i] { return false; }
+    }
+
+    // 7. The IPP must open the statement rebuilt from A and S, tying t_hat to the witness
+    let weights = RangeWeights::new(&backend, &y, &z, &range_blocks(m, DEFAULT_DIMENSION, dimension), 2 * m);
+    let gens = IppGenerators::new(&backend, dimension);
+    let P = ipp_statement(&backend, &gens, &proof.A, &proof.S, &proof.t_hat, &proof.mu, &x, &weights);
+    if !verify_inner_product(&backend, &weights.generators(&backend, &gens), &P, &proof.ipp_proof) { return false; }
+
+    // 8. tau_x must open C_v1_i, C_v2_i with weights z^(2m-1+2i), z^(2m+2i) through T1, T2
+    let commitments: Vec<&BigInt> = proof.C_v1.iter().zip(&proof.C_v2).flat_map(|(c1, c2)| [c1, c2]).collect();
+    range_relation_holds(&backend, &commitments, &proof.T1, &proof.T2, &proof.t_hat, &proof.tau_x, &x, &weights)
+}
+
+/// Verify a `cuproof_prove_aggregated_multi` proof, checking each value against its own range
+/// - params: ranges (a_i, b_i) in proving order, one per commitment C_i
+/// - returns: the `cuproof_verify_aggregated` checks plus, for every i, a_i < b_i and
+///   C_v1_i, C_v2_i bound to C_i and [a_i, b_i] (`range_binding_holds`)
+pub fn cuproof_verify_aggregated_multi(proof: &AggregatedCuproof, ranges: &[(BigInt, BigInt)], g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    if ranges.len() != proof.len() || ranges.iter().any(|(a, b)| a >= b) { return false; }
+    if !cuproof_verify_aggregated(proof, g, h, n) { return false; }
+    ranges.iter().enumerate().all(|(i, (a, b))| range_binding_holds(&proof.C[i], &proof.C_v1[i], &proof.C_v2[i], a, b, g, n))
+}
+
+fn bigint_size_bytes(x: &BigInt) -> usize {
+    let (_sign, bytes) = x.to_bytes_be();
+    bytes.len()
+}
+
+/// Serialized size of an aggregated proof: shared scalars, per-value commitments and the IPP
+pub fn aggregated_proof_size_bytes(proof: &AggregatedCuproof) -> usize {
+    let shared = [
+        &proof.A, &proof.S, &proof.T1, &proof.T2, &proof.tau_x, &proof.mu, &proof.t_hat,
+        &proof.t0, &proof.t1, &proof.t2, &proof.tau1, &proof.tau2,
+        &proof.ipp_proof.a, &proof.ipp_proof.b,
+    ];
+    shared.into_iter()
+        .chain(proof.C.iter()).chain(proof.C_v1.iter()).chain(proof.C_v2.iter())
+        .chain(proof.ipp_proof.L.iter()).chain(proof.ipp_proof.R.iter())
+        .map(bigint_size_bytes)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup::setup_256;
+
+    // Purpose: C_i of an aggregated proof are exposed in proving order and open under the caller's blindings
+    // Params: setup_256 params, range [1, 100], three values with random 128-bit blindings
+    // Output: len / commitments / commitment(i) assertions
+    // Usage: `cargo test -- aggregate::` or `cargo test`
+    #[test]
+    fn aggregated_commitment_accessors() {
+        let (g, h, n) = setup_256();
+        let a = BigInt::from(1);
+        let b = BigInt::from(100);
+        let values = vec![BigInt::from(5), BigInt::from(42), BigInt::from(99)];
+        let blindings: Vec<BigInt> = (0..3).map(|_| random_bigint(128)).collect();
+        let proof = cuproof_prove_aggregated(&values, &blindings, &a, &b, &g, &h, &n);
+
+        assert_eq!(proof.len(), 3);
+        assert!(!proof.is_empty());
+        assert_eq!(proof.commitments().len(), 3);
+        for (i, (v, r)) in values.iter().zip(&blindings).enumerate() {
+            let c = proof.commitment(i).unwrap();
+            assert_ne!(c, &BigInt::from(0));
+            assert_eq!(c, &pedersen_commit(&g, &h, v, r, &n));
+        }
+        assert_eq!(proof.commitment(3), None);
+        assert_eq!(proof.ipp_proof.L.len(), 8, "3 values pad to 256 IPP entries");
+    }
+
+    // Purpose: aggregated proofs verify, and tampering with t_hat's IPP statement is caught
+    // Params: setup_256 params, range [1, 100], two values
+    // Output: cuproof_verify_aggregated accepts the proof and rejects a changed mu or a dropped commitment
+    // Usage: `cargo test -- aggregate::` or `cargo test`
+    #[test]
+    fn aggregated_verify_pass_and_tamper_fail() {
+        let (g, h, n) = setup_256();
+        let values = [BigInt::from(42), BigInt::from(50)];
+        let blindings = [random_bigint(128), random_bigint(128)];
+        let proof = cuproof_prove_aggregated(&values, &blindings, &BigInt::from(1), &BigInt::from(100), &g, &h, &n);
+        assert!(cuproof_verify_aggregated(&proof, &g, &h, &n));
+
+        // mu only enters the IPP statement, so this is caught by the IPP alone
+        let mut bad = proof.clone();
+        bad.mu += 1;
+        assert!(!cuproof_verify_aggregated(&bad, &g, &h, &n));
+
+        let mut short = proof.clone();
+        short.C.pop();
+        assert!(!cuproof_verify_aggregated(&short, &g, &h, &n));
+        assert!(aggregated_proof_size_bytes(&proof) > 0);
+    }
+
+    // Purpose: per-value ranges are enforced by cuproof_verify_aggregated_multi
+    // Params: setup_256 params, items 42 in [1, 100] and 500 in [0, 1000]
+    // Output: accepted for the proving ranges; rejected for a range excluding 500, a missing
+    //         range, or a shared-range proof whose C_v1, C_v2 carry no binding
+    // Usage: `cargo test -- aggregate::` or `cargo test`
+    #[test]
+    fn aggregated_multi_checks_each_range() {
+        let (g, h, n) = setup_256();
+        let items = [
+            (BigInt::from(42), random_bigint(128), BigInt::from(1), BigInt::from(100)),
+            (BigInt::from(500), random_bigint(128), BigInt::from(0), BigInt::from(1000)),
+        ];
+        let proof = cuproof_prove_aggregated_multi(&items, &g, &h, &n);
+        let ranges: Vec<_> = items.iter().map(|(_, _, a, b)| (a.clone(), b.clone())).collect();
+        assert!(cuproof_verify_aggregated_multi(&proof, &ranges, &g, &h, &n));
+
+        // 500 is outside [0, 400], so the second item no longer binds
+        let out_of_range = [ranges[0].clone(), (BigInt::from(0), BigInt::from(400))];
+        assert!(!cuproof_verify_aggregated_multi(&proof, &out_of_range, &g, &h, &n));
+        assert!(!cuproof_verify_aggregated_multi(&proof, &ranges[..1], &g, &h, &n));
+
+        // Shared-range proofs use independent C_v1, C_v2 and carry no per-value binding
+        let values = [BigInt::from(42), BigInt::from(50)];
+        let blindings = [random_bigint(128), random_bigint(128)];
+        let shared = cuproof_prove_aggregated(&values, &blindings, &BigInt::from(1), &BigInt::from(100), &g, &h, &n);
+        let shared_ranges = vec![(BigInt::from(1), BigInt::from(100)); 2];
+        assert!(cuproof_verify_aggregated(&shared, &g, &h, &n));
+        assert!(!cuproof_verify_aggregated_multi(&shared, &shared_ranges, &g, &h, &n));
+    }
+}
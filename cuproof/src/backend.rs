@@ -0,0 +1,155 @@
+use crate::{commitment::*, fiat_shamir::*};
+use crate::setup::derive_h_with;
+use num_bigint::BigInt;
+use std::marker::PhantomData;
+
+/// Group operations the cuproof protocol needs, so the same proof logic can run over the
+/// RSA group or a prime-order curve
+///
+/// - `commit(m, r)`: Pedersen commitment g^m h^r
+/// - `combine(a, b)`: group operation, so commit(m1, r1) · commit(m2, r2) = commit(m1 + m2, r1 + r2)
+/// - `challenge(inputs)`: Fiat–Shamir challenge reduced into the challenge space
+///   (`challenge_modulus(n)` for RSA groups)
+/// - `scalar_modulus()`: range blindings are sampled from, [0, scalar_modulus)
+/// - `exp(base, e)`: base^e, used to fold the IPP generator vectors
+/// - `generator(index)`: independent generator derived by hashing the parameters, so nobody
+///   knows its discrete log relative to g, h or any other index
+/// - `multi_exp(bases, exps)`: prod bases[i]^exps[i]; override when a faster method exists
+pub trait GroupBackend {
+    type Element: Clone + PartialEq + std::fmt::Debug;
+
+    fn commit(&self, m: &BigInt, r: &BigInt) -> Self::Element;
+    fn combine(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn challenge(&self, inputs: &[&Self::Element]) -> BigInt;
+    fn scalar_modulus(&self) -> &BigInt;
+    fn exp(&self, base: &Self::Element, e: &BigInt) -> Self::Element;
+    fn generator(&self, index: usize) -> Self::Element;
+
+    /// Panics if `bases` is empty or the lengths differ
+    fn multi_exp(&self, bases: &[Self::Element], exps: &[BigInt]) -> Self::Element {
+        assert_eq!(bases.len(), exps.len(), "multi_exp: bases/exps length mismatch");
+        bases.iter().zip(exps)
+            .map(|(b, e)| self.exp(b, e))
+            .reduce(|acc, t| self.combine(&acc, &t))
+            .expect("multi_exp: no bases")
+    }
+}
+
+/// i-th hashed generator of Z_n^* for the parameters (g, h, n), via `derive_h_with`
+///
+/// Hashed with the backend's `H` rather than the feature default, so an `RsaBackend<Sha256Hasher>`
+/// derives the same generators whether or not the `keccak` feature is enabled
+fn rsa_generator<H: ChallengeHasher>(g: &BigInt, h: &BigInt, n: &BigInt, index: usize) -> BigInt {
+    derive_h_with::<H>(&fiat_shamir_with::<H>(&[g, h, &BigInt::from(index)]), n)
+}
+
+/// RSA-group backend: commitments g^m h^r mod n, challenges hashed with `H`
+#[derive(Clone, Debug)]
+pub struct RsaBackend<H: ChallengeHasher = DefaultChallengeHasher> {
+    pub g: BigInt,
+    pub h: BigInt,
+    pub n: BigInt,
+    challenge_modulus: BigInt,
+    hasher: PhantomData<H>,
+}
+
+impl<H: ChallengeHasher> RsaBackend<H> {
+    pub fn new(g: &BigInt, h: &BigInt, n: &BigInt) -> Self {
+        RsaBackend { g: g.clone(), h: h.clone(), n: n.clone(), challenge_modulus: challenge_modulus(n), hasher: PhantomData }
+    }
+}
+
+impl<H: ChallengeHasher> GroupBackend for RsaBackend<H> {
+    type Element = BigInt;
+
+    fn commit(&self, m: &BigInt, r: &BigInt) -> BigInt {
+        pedersen_commit(&self.g, &self.h, m, r, &self.n)
+    }
+
+    fn combine(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        a * b % &self.n
+    }
+
+    fn challenge(&self, inputs: &[&BigInt]) -> BigInt {
+        fiat_shamir_with::<H>(inputs) % &self.challenge_modulus
+    }
+
+    // The order of Z_n^* is unknown to everyone but the setup, so n stands in for it
+    fn scalar_modulus(&self) -> &BigInt {
+        &self.n
+    }
+
+    fn exp(&self, base: &BigInt, e: &BigInt) -> BigInt {
+        mod_exp(base, e, &self.n)
+    }
+
+    fn generator(&self, index: usize) -> BigInt {
+        rsa_generator::<H>(&self.g, &self.h, &self.n, index)
+    }
+
+    fn multi_exp(&self, bases: &[BigInt], exps: &[BigInt]) -> BigInt {
+        multi_mod_exp(bases, exps, &self.n)
+    }
+}
+
+/// A precomputed context is an RSA backend with SHA-256 challenges and table-based commitments
+impl GroupBackend for GeneratorContext {
+    type Element = BigInt;
+
+    fn commit(&self, m: &BigInt, r: &BigInt) -> BigInt {
+        GeneratorContext::commit(self, m, r)
+    }
+
+    fn combine(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        a * b % &self.n
+    }
+
+    fn challenge(&self, inputs: &[&BigInt]) -> BigInt {
+        fiat_shamir(inputs) % &self.challenge_modulus
+    }
+
+    fn scalar_modulus(&self) -> &BigInt {
+        &self.n
+    }
+
+    fn exp(&self, base: &BigInt, e: &BigInt) -> BigInt {
+        mod_exp(base, e, &self.n)
+    }
+
+    fn generator(&self, index: usize) -> BigInt {
+        rsa_generator::<DefaultChallengeHasher>(&self.g, &self.h, &self.n, index)
+    }
+
+    fn multi_exp(&self, bases: &[BigInt], exps: &[BigInt]) -> BigInt {
+        multi_mod_exp(bases, exps, &self.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup::fast_test_setup;
+    use crate::range_proof::cuproof_prove_with_backend;
+    use crate::util::random_bigint;
+    use crate::verify::cuproof_verify_with_range;
+
+    // Purpose: the RSA backend is homomorphic and the existing proof runs through the trait unchanged
+    // Params: fast_test_setup params, range [1, 100], v = 42
+    // Output: combine matches commit of sums; proof from the backend passes the plain verifier
+    // Usage: `cargo test -- src::backend` or `cargo test`
+    #[test]
+    fn rsa_backend_proves_existing_protocol() {
+        let (g, h, n) = fast_test_setup();
+        let backend = RsaBackend::<DefaultChallengeHasher>::new(&g, &h, &n);
+        let (m1, r1, m2, r2) = (random_bigint(64), random_bigint(256), random_bigint(64), random_bigint(256));
+        assert_eq!(
+            backend.combine(&backend.commit(&m1, &r1), &backend.commit(&m2, &r2)),
+            backend.commit(&(&m1 + &m2), &(&r1 + &r2)),
+        );
+        assert_eq!(backend.challenge(&[&m1, &m2]), fiat_shamir(&[&m1, &m2]) % challenge_modulus(&n));
+
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove_with_backend(&v, &r1, &a, &b, &backend);
+        assert!(cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b));
+    }
+}
@@ -0,0 +1,85 @@
+//! Previous clone-based verifier paths, kept behind the `alloc-baseline` feature
+//!
+//! These are the implementations the borrowing ones replaced, unchanged apart from the
+//! current `ct_eq` signature, so `benches/verify_alloc.rs` measures against the real
+//! previous code rather than a retyped copy. Not for production use.
+
+use crate::backend::GroupBackend;
+use crate::range_proof::{IPPProof, IppGenerators, fold_ipp_commitment, fold_ipp_generators};
+use crate::util::{ct_eq, random_bigint};
+use crate::verify::expected_ipp_levels;
+use num_bigint::BigInt;
+
+/// `verify_inner_product` before it borrowed P and the generators
+#[allow(non_snake_case)]
+pub fn verify_inner_product<B: GroupBackend<Element = BigInt>>(backend: &B, gens: &IppGenerators<BigInt>, P: &BigInt, proof: &IPPProof) -> bool {
+	if proof.L.len() != proof.R.len() || gens.h_vec.len() != gens.g_vec.len() { return false; }
+	if expected_ipp_levels(gens.g_vec.len()) != Ok(proof.L.len()) { return false; }
+
+	let mut P = P.clone();
+	let mut g_vec = gens.g_vec.clone();
+	let mut h_vec = gens.h_vec.clone();
+	for (L, R) in proof.L.iter().zip(&proof.R) {
+		let x = backend.challenge(&[&P, L, R]);
+		if x == BigInt::from(0) { return false; }
+		P = fold_ipp_commitment(backend, &P, L, R, &x);
+		(g_vec, h_vec) = fold_ipp_generators(backend, &g_vec, &h_vec, &x);
+	}
+
+	let expected = backend.multi_exp(
+		&[g_vec[0].clone(), h_vec[0].clone(), gens.u.clone()],
+		&[proof.a.clone(), proof.b.clone(), &proof.a * &proof.b],
+	);
+	ct_eq(&P, &expected, backend.scalar_modulus())
+}
+
+/// `range_proof::VerifierState` before it borrowed the parameters and the prover's messages
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct VerifierState {
+	pub g: BigInt,
+	pub h: BigInt,
+	pub n: BigInt,
+	pub A: BigInt,
+	pub S: BigInt,
+	pub T1: BigInt,
+	pub T2: BigInt,
+	pub y: BigInt,
+	pub z: BigInt,
+	pub x: BigInt,
+}
+
+pub fn interactive_verify_step1(g: &BigInt, h: &BigInt, n: &BigInt) -> (VerifierState, BigInt, BigInt) {
+	// Step 6: Verifier chooses natural values y', z' and computes y = g^(y'), z = g^(z')
+	let y_prime = random_bigint(256);
+	let z_prime = random_bigint(256);
+	let y = g.modpow(&y_prime, n);
+	let z = g.modpow(&z_prime, n);
+
+	let verifier_state = VerifierState {
+		g: g.clone(), h: h.clone(), n: n.clone(),
+		A: BigInt::from(0), S: BigInt::from(0), T1: BigInt::from(0), T2: BigInt::from(0),
+		y: y.clone(), z: z.clone(), x: BigInt::from(0),
+	};
+
+	(verifier_state, y, z)
+}
+
+#[allow(non_snake_case)]
+pub fn interactive_verify_step2(verifier_state: &mut VerifierState, A: &BigInt, S: &BigInt) {
+	// Step 5: Verifier receives commitments A and S from Prover
+	verifier_state.A = A.clone();
+	verifier_state.S = S.clone();
+}
+
+#[allow(non_snake_case)]
+pub fn interactive_verify_step3(verifier_state: &mut VerifierState, T1: &BigInt, T2: &BigInt) {
+	// Step 9: Verifier receives T1 and T2 from Prover
+	verifier_state.T1 = T1.clone();
+	verifier_state.T2 = T2.clone();
+}
+
+/// `util::inner_product` before it accumulated through a reused scratch value
+pub fn inner_product(a: &[BigInt], b: &[BigInt]) -> BigInt {
+	a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
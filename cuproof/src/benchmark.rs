@@ -1,9 +1,12 @@
 use std::time::{Instant, Duration};
 use num_bigint::BigInt;
-use crate::setup::{trusted_setup, fast_test_setup};
+use crate::setup::{default_setup, fast_test_setup};
 use crate::range_proof::{cuproof_prove, proof_size_bytes};
-use crate::verify::cuproof_verify;
-use crate::util::random_bigint;
+use crate::verify::{cuproof_verify, cuproof_verify_with_context, cuproof_verify_with_range};
+use crate::commitment::{GeneratorContext, pedersen_commit};
+use crate::util::{random_bigint, load_params, load_proof, bigint_to_hex, hex_to_bigint};
+use crate::lagrange::{find_3_squares, SquaresError};
+use crate::aggregate::{cuproof_prove_aggregated, cuproof_verify_aggregated, aggregated_proof_size_bytes};
 
 /// Kết quả đo benchmark cho một độ dài khoảng cụ thể
 #[derive(Debug, Clone)]
@@ -14,6 +17,87 @@ pub struct BenchmarkResult {
     pub verify_time_ms: u128,
     pub proof_size_bytes: usize,
     pub success: bool,
+    /// Thời gian phân tích v1, v2 thành tổng ba bình phương (bước Lagrange của prover)
+    pub decomposition_time_ms: u128,
+    /// Số giá trị trong (v1, v2) không có biểu diễn ba bình phương (cần bốn bình phương)
+    pub four_square_count: usize,
+    /// Commitment C và blinding r đã dùng (chỉ có khi bật `capture_commitment`)
+    pub captured: Option<CapturedCommitment>,
+    /// Lý do bỏ qua khi tham số đầu vào không hợp lệ (khi đó `success = false`)
+    pub note: Option<String>,
+    /// Đỉnh bộ nhớ cấp phát thêm khi tạo một proof (chỉ có khi bật `--track-memory`)
+    pub prove_peak_bytes: Option<usize>,
+    /// Đỉnh bộ nhớ cấp phát thêm khi verify một proof (chỉ có khi bật `--track-memory`)
+    pub verify_peak_bytes: Option<usize>,
+}
+
+/// Độ dài khoảng tối đa mặc định (bit); lớn hơn sẽ bị từ chối thay vì cấp phát BigInt khổng lồ
+pub const DEFAULT_MAX_RANGE_LENGTH: usize = 4096;
+
+impl BenchmarkResult {
+    /// Kết quả rỗng cho một độ dài khoảng bị từ chối
+    fn rejected(range_length: usize, note: String) -> Self {
+        println!("  ✗ Bỏ qua {} bit: {}", range_length, note);
+        BenchmarkResult {
+            range_length,
+            setup_time_ms: 0,
+            prove_time_ms: 0,
+            verify_time_ms: 0,
+            proof_size_bytes: 0,
+            success: false,
+            decomposition_time_ms: 0,
+            four_square_count: 0,
+            captured: None,
+            note: Some(note),
+            prove_peak_bytes: None,
+            verify_peak_bytes: None,
+        }
+    }
+
+    /// Tiêu đề CSV tương ứng với `to_csv_row`
+    pub const CSV_HEADER: &'static str = "range_length,setup_time_ms,prove_time_ms,verify_time_ms,proof_size_bytes,success,decomposition_time_ms,four_square_count,prove_peak_bytes,verify_peak_bytes";
+
+    /// Một dòng CSV (không có ký tự xuống dòng) cho kết quả này; cột bộ nhớ để trống khi không đo
+    pub fn to_csv_row(&self) -> String {
+        let peak = |bytes: Option<usize>| bytes.map(|b| b.to_string()).unwrap_or_default();
+        format!("{},{},{},{},{},{},{},{},{},{}", self.range_length, self.setup_time_ms, self.prove_time_ms,
+                self.verify_time_ms, self.proof_size_bytes, self.success, self.decomposition_time_ms, self.four_square_count,
+                peak(self.prove_peak_bytes), peak(self.verify_peak_bytes))
+    }
+}
+
+/// Dữ liệu (hex) để tái tạo và mở lại commitment C = g^v h^r mod n của một dòng benchmark
+#[derive(Debug, Clone)]
+pub struct CapturedCommitment {
+    pub g_hex: String,
+    pub h_hex: String,
+    pub n_hex: String,
+    pub value_hex: String,
+    pub commitment_hex: String,
+    pub blinding_hex: String,
+}
+
+impl CapturedCommitment {
+    /// Kiểm tra C có mở được bằng (v, r) đã lưu hay không
+    pub fn reopens(&self) -> bool {
+        let (g, h, n) = (hex_to_bigint(&self.g_hex), hex_to_bigint(&self.h_hex), hex_to_bigint(&self.n_hex));
+        let c = pedersen_commit(&g, &h, &hex_to_bigint(&self.value_hex), &hex_to_bigint(&self.blinding_hex), &n);
+        bigint_to_hex(&c) == self.commitment_hex
+    }
+}
+
+/// Chạy `f` và đo đỉnh bộ nhớ nếu đã bật `memory::enable` (feature `track-memory`)
+#[cfg(feature = "track-memory")]
+fn with_peak_memory<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    if !crate::memory::is_enabled() { return (f(), None); }
+    let (out, peak) = crate::memory::peak_during(f);
+    (out, Some(peak))
+}
+
+/// Không có feature `track-memory`: chỉ chạy `f`
+#[cfg(not(feature = "track-memory"))]
+fn with_peak_memory<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    (f(), None)
 }
 
 /// Thực hiện đo thời gian với độ chính xác cao hơn
@@ -36,22 +120,72 @@ where F: FnMut(),
 }
 
 pub fn benchmark_range_length(range_length: usize, use_fast_setup: bool) -> BenchmarkResult {
+    benchmark_range_length_with_context(range_length, use_fast_setup, false)
+}
+
+/// Đo riêng bước phân tích ba bình phương cho v1 = 4v-4a+1 và v2 = 4b-4v+1
+/// Trả về (thời gian trung bình, số giá trị cần bốn bình phương)
+fn measure_decomposition(v: &BigInt, a: &BigInt, b: &BigInt) -> (Duration, usize) {
+    let v1 = 4 * v - 4 * a + 1;
+    let v2 = 4 * b - 4 * v + 1;
+    let time = measure_time_accurate(|| {
+        let _ = find_3_squares(&v1);
+        let _ = find_3_squares(&v2);
+    }, 10);
+    let four_square_count = [&v1, &v2].iter()
+        .filter(|x| find_3_squares(x) == Err(SquaresError::FourSquaresRequired))
+        .count();
+    (time, four_square_count)
+}
+
+/// Benchmark một độ dài khoảng, tuỳ chọn dùng `GeneratorContext` (bảng lũy thừa g, h tính trước)
+/// cho vòng lặp verify để chi phí tiền xử lý được chia đều cho mọi lần verify
+pub fn benchmark_range_length_with_context(range_length: usize, use_fast_setup: bool, use_context: bool) -> BenchmarkResult {
+    benchmark_range_length_impl(range_length, use_fast_setup, use_context, false, DEFAULT_MAX_RANGE_LENGTH)
+}
+
+/// Benchmark một độ dài khoảng với giới hạn `max_range_length` tự chọn thay cho
+/// `DEFAULT_MAX_RANGE_LENGTH`; range_length = 0 hoặc vượt giới hạn trả về `success = false` kèm `note`
+pub fn benchmark_range_length_with_max(range_length: usize, use_fast_setup: bool, max_range_length: usize) -> BenchmarkResult {
+    benchmark_range_length_impl(range_length, use_fast_setup, false, false, max_range_length)
+}
+
+/// Benchmark một độ dài khoảng; nếu `capture_commitment` bật thì kết quả mang theo
+/// C, r và tham số (hex) để tái tạo đúng proof khi debug một dòng bị lỗi
+pub fn benchmark_range_length_with_capture(range_length: usize, use_fast_setup: bool, capture_commitment: bool) -> BenchmarkResult {
+    benchmark_range_length_impl(range_length, use_fast_setup, false, capture_commitment, DEFAULT_MAX_RANGE_LENGTH)
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Số lần `timed_setup` được gọi trên thread hiện tại (chỉ dùng trong test)
+    static SETUP_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Chạy setup đúng một lần và trả về tham số cùng thời gian đã tốn.
+/// Setup là chi phí một lần nên không warm-up hay lặp như prove/verify.
+fn timed_setup(use_fast_setup: bool) -> ((BigInt, BigInt, BigInt), Duration) {
+    #[cfg(test)]
+    SETUP_CALLS.with(|c| c.set(c.get() + 1));
+    let start = Instant::now();
+    let params = if use_fast_setup { fast_test_setup() } else { default_setup() };
+    (params, start.elapsed())
+}
+
+fn benchmark_range_length_impl(range_length: usize, use_fast_setup: bool, use_context: bool, capture_commitment: bool, max_range_length: usize) -> BenchmarkResult {
+    // Kiểm tra trước khi setup: 2^(range_length-1) tràn khi range_length = 0,
+    // và range_length quá lớn sẽ cấp phát BigInt khổng lồ
+    if range_length == 0 {
+        return BenchmarkResult::rejected(range_length, "độ dài khoảng phải lớn hơn 0".to_string());
+    }
+    if range_length > max_range_length {
+        return BenchmarkResult::rejected(range_length, format!("độ dài khoảng vượt quá giới hạn {} bit", max_range_length));
+    }
+
     println!("Đang benchmark với {} bit (khoảng [0, 2^{}-1]):", range_length, range_length);
     
-    // Đo thời gian setup với độ chính xác cao
-    let setup_time = measure_time_accurate(|| {
-        let _ = if use_fast_setup {
-            fast_test_setup()
-        } else {
-            trusted_setup(2048)
-        };
-    }, 5);
-    
-    let (g, h, n) = if use_fast_setup {
-        fast_test_setup()
-    } else {
-        trusted_setup(2048)
-    };
+    // Setup chỉ chạy một lần: tham số vừa đo được dùng lại cho prove và verify
+    let ((g, h, n), setup_time) = timed_setup(use_fast_setup);
     
     // Tạo dữ liệu test dựa trên số bit
     let a = BigInt::from(0);
@@ -64,17 +198,23 @@ pub fn benchmark_range_length(range_length: usize, use_fast_setup: bool) -> Benc
         let _proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
     }, 3);
     
-    let proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
+    let (proof, prove_peak_bytes) = with_peak_memory(|| cuproof_prove(&v, &r, &a, &b, &g, &h, &n));
+
+    // Đo riêng bước Lagrange (đã nằm trong thời gian prove ở trên)
+    let (decomposition_time, four_square_count) = measure_decomposition(&v, &a, &b);
     
     // Đo kích thước proof
     let proof_size = proof_size_bytes(&proof);
     
+    // Bảng tính trước chỉ tạo một lần, ngoài vòng đo
+    let ctx = if use_context { Some(GeneratorContext::new(&g, &h, &n)) } else { None };
+
     // Đo thời gian verify với độ chính xác cao
     let verify_time = measure_time_accurate(|| {
-        let _result = cuproof_verify(&proof, &g, &h, &n);
+        let _result = cuproof_verify_with_context(&proof, &g, &h, &n, ctx.as_ref());
     }, 10);
     
-    let verify_result = cuproof_verify(&proof, &g, &h, &n);
+    let (verify_result, verify_peak_bytes) = with_peak_memory(|| cuproof_verify_with_context(&proof, &g, &h, &n, ctx.as_ref()));
     
     BenchmarkResult {
         range_length,
@@ -83,6 +223,19 @@ pub fn benchmark_range_length(range_length: usize, use_fast_setup: bool) -> Benc
         verify_time_ms: verify_time.as_millis(),
         proof_size_bytes: proof_size,
         success: verify_result,
+        decomposition_time_ms: decomposition_time.as_millis(),
+        four_square_count,
+        captured: capture_commitment.then(|| CapturedCommitment {
+            g_hex: bigint_to_hex(&g),
+            h_hex: bigint_to_hex(&h),
+            n_hex: bigint_to_hex(&n),
+            value_hex: bigint_to_hex(&v),
+            commitment_hex: bigint_to_hex(&proof.C),
+            blinding_hex: bigint_to_hex(&r),
+        }),
+        note: None,
+        prove_peak_bytes,
+        verify_peak_bytes,
     }
 }
 
@@ -101,28 +254,130 @@ pub fn benchmark_multiple_ranges(range_lengths: Vec<usize>, use_fast_setup: bool
     println!("Sử dụng {} setup", if use_fast_setup { "fast" } else { "trusted" });
     println!("{}", "=".repeat(80));
     
-    for &range_length in &range_lengths {
-        let result = benchmark_range_length(range_length, use_fast_setup);
-        results.push(result.clone());
-        
+    benchmark_multiple_ranges_streaming(&range_lengths, use_fast_setup, |result| {
         // In kết quả ngay lập tức
-        print_benchmark_result(&result);
+        print_benchmark_result(result);
         println!("{}", "=".repeat(80));
-    }
+        results.push(result.clone());
+    });
     
     results
 }
 
+/// Như `benchmark_multiple_ranges`, nhưng chạy các độ dài khoảng độc lập song song trên thread pool của rayon.
+/// Mỗi độ dài vẫn tự setup, warm-up và đo thời gian riêng; kết quả trả về theo đúng thứ tự `range_lengths`.
+/// Lưu ý: các độ dài chạy đồng thời tranh chấp CPU nên thời gian tuyệt đối có thể cao hơn khi chạy tuần tự.
+#[cfg(feature = "rayon")]
+pub fn benchmark_multiple_ranges_parallel(range_lengths: Vec<usize>, use_fast_setup: bool) -> Vec<BenchmarkResult> {
+    use rayon::prelude::*;
+
+    println!("Bắt đầu benchmark song song cho {} độ dài khoảng khác nhau", range_lengths.len());
+    println!("Sử dụng {} setup", if use_fast_setup { "fast" } else { "trusted" });
+    println!("{}", "=".repeat(80));
+
+    // collect() trên par_iter giữ nguyên thứ tự đầu vào
+    let results: Vec<BenchmarkResult> = range_lengths.par_iter()
+        .map(|&range_length| benchmark_range_length(range_length, use_fast_setup))
+        .collect();
+
+    for result in &results {
+        print_benchmark_result(result);
+        println!("{}", "=".repeat(80));
+    }
+    results
+}
+
+/// Như `benchmark_multiple_ranges`, nhưng gọi `sink` ngay khi mỗi độ dài khoảng đo xong (theo thứ tự)
+/// thay vì trả về Vec ở cuối, nên các dòng đã đo không bị mất nếu một độ dài sau bị panic.
+/// Ví dụ: ghi mỗi kết quả bằng `BenchmarkResult::to_csv_row` rồi flush file trong `sink`.
+pub fn benchmark_multiple_ranges_streaming<F: FnMut(&BenchmarkResult)>(range_lengths: &[usize], use_fast_setup: bool, mut sink: F) {
+    for &range_length in range_lengths {
+        let result = benchmark_range_length(range_length, use_fast_setup);
+        sink(&result);
+    }
+}
+
+/// Kết quả benchmark proof gộp cho một số lượng giá trị cụ thể
+#[derive(Debug, Clone)]
+pub struct AggregationBenchmarkResult {
+    pub range_length: usize,
+    /// Số giá trị được gộp vào một proof
+    pub aggregation_count: usize,
+    pub setup_time_ms: u128,
+    pub prove_time_ms: u128,
+    pub verify_time_ms: u128,
+    pub proof_size_bytes: usize,
+    pub success: bool,
+}
+
+/// Benchmark proof gộp (`cuproof_prove_aggregated`) cho từng số lượng giá trị trong `counts`
+///
+/// # Arguments
+/// * `counts` - Các số lượng giá trị cần gộp
+/// * `range_bits` - Độ dài khoảng chung [0, 2^range_bits - 1]
+/// * `use_fast_setup` - Sử dụng fast setup thay vì `default_setup`
+///
+/// # Returns
+/// Vector chứa kết quả cho từng số lượng, setup chỉ chạy một lần cho tất cả
+pub fn benchmark_aggregation(counts: &[usize], range_bits: usize, use_fast_setup: bool) -> Vec<AggregationBenchmarkResult> {
+    println!("Bắt đầu benchmark proof gộp cho {} số lượng giá trị, {} bit", counts.len(), range_bits);
+
+    let ((g, h, n), setup_time) = timed_setup(use_fast_setup);
+    let a = BigInt::from(0);
+    let b = BigInt::from(2).pow(range_bits as u32) - 1;
+
+    let mut results = Vec::new();
+    for &count in counts {
+        let values: Vec<BigInt> = (0..count).map(|_| random_bigint(range_bits) % (&b + 1)).collect();
+        let blindings: Vec<BigInt> = (0..count).map(|_| random_bigint(256)).collect();
+
+        let prove_time = measure_time_accurate(|| {
+            let _proof = cuproof_prove_aggregated(&values, &blindings, &a, &b, &g, &h, &n);
+        }, 3);
+        let proof = cuproof_prove_aggregated(&values, &blindings, &a, &b, &g, &h, &n);
+
+        let verify_time = measure_time_accurate(|| {
+            let _result = cuproof_verify_aggregated(&proof, &g, &h, &n);
+        }, 10);
+
+        let result = AggregationBenchmarkResult {
+            range_length: range_bits,
+            aggregation_count: count,
+            setup_time_ms: setup_time.as_millis(),
+            prove_time_ms: prove_time.as_millis(),
+            verify_time_ms: verify_time.as_millis(),
+            proof_size_bytes: aggregated_proof_size_bytes(&proof),
+            success: cuproof_verify_aggregated(&proof, &g, &h, &n),
+        };
+        println!("  {} giá trị: verify {} ms, {} bytes ({:.1} bytes/giá trị)",
+                 count, result.verify_time_ms, result.proof_size_bytes,
+                 result.proof_size_bytes as f64 / count as f64);
+        results.push(result);
+    }
+
+    results
+}
+
 /// In kết quả benchmark một cách đẹp mắt
 pub fn print_benchmark_result(result: &BenchmarkResult) {
     println!("Kết quả benchmark cho {} bit (khoảng [0, 2^{}-1]):", result.range_length, result.range_length);
-    println!("  ✓ Thời gian setup: {:.2} ms", result.setup_time_ms as f64);
+    println!("  ✓ Thời gian setup (một lần): {:.2} ms", result.setup_time_ms as f64);
     println!("  ✓ Thời gian tạo proof: {:.2} ms", result.prove_time_ms as f64);
     println!("  ✓ Thời gian verify: {:.2} ms", result.verify_time_ms as f64);
+    println!("  ✓ Thời gian phân tích bình phương: {:.2} ms", result.decomposition_time_ms as f64);
+    if result.four_square_count > 0 {
+        println!("  ! Số giá trị cần bốn bình phương: {}", result.four_square_count);
+    }
     println!("  ✓ Kích thước proof: {} bytes ({:.2} KB)", 
              result.proof_size_bytes, 
              (result.proof_size_bytes as f64 / 1024.0 * 100.0).round() / 100.0);
+    if let (Some(prove_peak), Some(verify_peak)) = (result.prove_peak_bytes, result.verify_peak_bytes) {
+        println!("  ✓ Bộ nhớ đỉnh: prove {:.2} KB, verify {:.2} KB", prove_peak as f64 / 1024.0, verify_peak as f64 / 1024.0);
+    }
     println!("  ✓ Trạng thái: {}", if result.success { "THÀNH CÔNG" } else { "THẤT BẠI" });
+    if let Some(note) = &result.note {
+        println!("  ! Ghi chú: {}", note);
+    }
 }
 
 /// In tổng kết tất cả kết quả benchmark
@@ -209,6 +464,7 @@ pub fn benchmark_different_values_in_range(range_length: usize, use_fast_setup:
         let prove_time = prove_start.elapsed();
         
         let proof_size = proof_size_bytes(&proof);
+        let (decomposition_time, four_square_count) = measure_decomposition(&v, &a, &b);
         
         let verify_start = Instant::now();
         let verify_result = cuproof_verify(&proof, &g, &h, &n);
@@ -221,6 +477,12 @@ pub fn benchmark_different_values_in_range(range_length: usize, use_fast_setup:
             verify_time_ms: verify_time.as_millis(),
             proof_size_bytes: proof_size,
             success: verify_result,
+            decomposition_time_ms: decomposition_time.as_millis(),
+            four_square_count,
+            captured: None,
+            note: None,
+            prove_peak_bytes: None,
+            verify_peak_bytes: None,
         };
         
         results.push(result);
@@ -231,6 +493,38 @@ pub fn benchmark_different_values_in_range(range_length: usize, use_fast_setup:
     results
 }
 
+/// Chỉ đo thời gian verify cho các proof đã lưu trên đĩa (ví dụ proof tạo từ công cụ khác)
+/// 
+/// # Arguments
+/// * `paths` - Các file proof (định dạng của `save_proof`)
+/// * `params_path` - File tham số (g, h, n)
+/// * `a`, `b` - Khoảng công khai dùng cho `cuproof_verify_with_range`
+/// 
+/// # Returns
+/// (đường dẫn, thời gian verify trung bình tính bằng ms) cho mỗi file đọc được;
+/// file không đọc được sẽ bị bỏ qua và in lỗi
+pub fn benchmark_verify_files(paths: &[&str], params_path: &str, a: &BigInt, b: &BigInt) -> Vec<(String, u128)> {
+    let (g, h, n) = match load_params(params_path) {
+        Ok(t) => t,
+        Err(e) => { eprintln!("Không đọc được tham số {}: {}", params_path, e); return Vec::new(); }
+    };
+
+    let mut results = Vec::new();
+    for &path in paths {
+        let proof = match load_proof(path) {
+            Ok(p) => p,
+            Err(e) => { eprintln!("Không đọc được proof {}: {}", path, e); continue; }
+        };
+        let verify_time = measure_time_accurate(|| {
+            let _result = cuproof_verify_with_range(&proof, &g, &h, &n, a, b);
+        }, 10);
+        let valid = cuproof_verify_with_range(&proof, &g, &h, &n, a, b);
+        println!("  {}: Verify={}ms, Valid={}", path, verify_time.as_millis(), valid);
+        results.push((path.to_string(), verify_time.as_millis()));
+    }
+    results
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +538,137 @@ mod tests {
         assert!(result.proof_size_bytes > 0);
     }
 
+    #[test]
+    fn test_benchmark_setup_runs_once() {
+        let before = SETUP_CALLS.with(|c| c.get());
+        let result = benchmark_range_length(8, true);
+        assert!(result.success);
+        // Một lần setup cho cả prove lẫn verify, không warm-up, không sinh lại tham số
+        assert_eq!(SETUP_CALLS.with(|c| c.get()) - before, 1);
+
+        // Độ dài bị từ chối trả về trước khi setup
+        let before = SETUP_CALLS.with(|c| c.get());
+        let _ = benchmark_range_length(0, true);
+        assert_eq!(SETUP_CALLS.with(|c| c.get()), before);
+    }
+
+    #[test]
+    fn test_benchmark_streaming_sink_order() {
+        let mut seen = Vec::new();
+        let mut rows = Vec::new();
+        benchmark_multiple_ranges_streaming(&[4, 8, 0], true, |r| {
+            seen.push(r.range_length);
+            rows.push(r.to_csv_row());
+        });
+        // Mỗi độ dài gọi sink đúng một lần, theo thứ tự, kể cả độ dài bị từ chối
+        assert_eq!(seen, vec![4, 8, 0]);
+        let columns = BenchmarkResult::CSV_HEADER.split(',').count();
+        assert!(rows.iter().all(|row| row.split(',').count() == columns));
+        assert!(rows[2].contains("false"));
+    }
+
+    #[test]
+    fn test_benchmark_rejects_bad_range_length() {
+        let zero = benchmark_range_length(0, true);
+        assert!(!zero.success);
+        assert!(zero.note.is_some());
+
+        let huge = benchmark_range_length(usize::MAX, true);
+        assert!(!huge.success);
+        assert!(huge.note.is_some());
+
+        let capped = benchmark_range_length_with_max(64, true, 32);
+        assert!(!capped.success);
+        assert!(capped.note.unwrap().contains("32"));
+    }
+
+    #[test]
+    fn test_benchmark_decomposition_time() {
+        let result = benchmark_range_length(16, true);
+        assert!(result.success);
+        // Bước Lagrange là một phần của prove nên không thể lâu hơn cả prove
+        assert!(result.decomposition_time_ms <= result.prove_time_ms);
+        // v1, v2 luôn có dạng 4x+1 nên không bao giờ cần bốn bình phương
+        assert_eq!(result.four_square_count, 0);
+    }
+
+    #[test]
+    fn test_benchmark_commitments_with_context() {
+        let (g, h, n) = fast_test_setup();
+        let inputs: Vec<(BigInt, BigInt)> = (0..100).map(|_| (random_bigint(256), random_bigint(256))).collect();
+
+        let start = Instant::now();
+        let plain: Vec<BigInt> = inputs.iter().map(|(m, r)| pedersen_commit(&g, &h, m, r, &n)).collect();
+        let plain_time = start.elapsed();
+
+        let start = Instant::now();
+        let ctx = GeneratorContext::new(&g, &h, &n);
+        let setup_time = start.elapsed();
+        let start = Instant::now();
+        let cached: Vec<BigInt> = inputs.iter().map(|(m, r)| ctx.commit(m, r)).collect();
+        let cached_time = start.elapsed();
+
+        println!("100 commitments: pedersen_commit={:?}, context={:?} (+{:?} tiền xử lý)", plain_time, cached_time, setup_time);
+        assert_eq!(plain, cached);
+
+        let result = benchmark_range_length_with_context(8, true, true);
+        assert!(result.success);
+    }
+
+    #[test]
+    fn test_benchmark_verify_files() {
+        use crate::util::{save_params, save_proof};
+        let (g, h, n) = fast_test_setup();
+        let (a, b) = (BigInt::from(1), BigInt::from(100));
+        let dir = std::env::temp_dir();
+        let tag = std::process::id();
+        let params_path = dir.join(format!("cuproof_bench_params_{}.txt", tag)).to_string_lossy().into_owned();
+        save_params(&params_path, &g, &h, &n).unwrap();
+
+        let mut proof_paths = Vec::new();
+        for (i, v) in [10, 90].into_iter().enumerate() {
+            let proof = cuproof_prove(&BigInt::from(v), &random_bigint(256), &a, &b, &g, &h, &n);
+            let path = dir.join(format!("cuproof_bench_proof_{}_{}.txt", tag, i)).to_string_lossy().into_owned();
+            save_proof(&path, &proof).unwrap();
+            proof_paths.push(path);
+        }
+
+        let paths: Vec<&str> = proof_paths.iter().map(|p| p.as_str()).collect();
+        let results = benchmark_verify_files(&paths, &params_path, &a, &b);
+        assert_eq!(results.len(), 2);
+        for (path, ms) in &results {
+            assert!(*ms > 0, "{} reported zero verify time", path);
+        }
+
+        for p in proof_paths.iter().chain(std::iter::once(&params_path)) {
+            let _ = std::fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_benchmark_capture_commitment() {
+        let plain = benchmark_range_length(8, true);
+        assert!(plain.captured.is_none());
+
+        let result = benchmark_range_length_with_capture(8, true, true);
+        assert!(result.success);
+        let captured = result.captured.expect("capture_commitment must fill captured");
+        assert!(!captured.commitment_hex.is_empty());
+        assert!(captured.reopens());
+    }
+
+    #[cfg(feature = "track-memory")]
+    #[test]
+    fn test_benchmark_track_memory_peaks() {
+        // benchmark chứng minh với DEFAULT_DIMENSION = 64
+        crate::memory::enable();
+        let result = benchmark_range_length(8, true);
+        assert!(result.success);
+        assert!(result.prove_peak_bytes.unwrap() > 0);
+        assert!(result.verify_peak_bytes.unwrap() > 0);
+        assert!(result.to_csv_row().ends_with(&format!(",{},{}", result.prove_peak_bytes.unwrap(), result.verify_peak_bytes.unwrap())));
+    }
+
     #[test]
     fn test_benchmark_multiple_ranges() {
         let range_lengths = vec![8, 16, 32];
@@ -253,4 +678,29 @@ mod tests {
             assert!(result.success);
         }
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_benchmark_multiple_ranges_parallel_matches_serial() {
+        let range_lengths = vec![8, 16, 32];
+        let serial = benchmark_multiple_ranges(range_lengths.clone(), true);
+        let parallel = benchmark_multiple_ranges_parallel(range_lengths.clone(), true);
+        // Cùng thứ tự độ dài khoảng và cùng cờ success như khi chạy tuần tự
+        assert_eq!(parallel.iter().map(|r| r.range_length).collect::<Vec<_>>(), range_lengths);
+        assert_eq!(parallel.iter().map(|r| r.success).collect::<Vec<_>>(), serial.iter().map(|r| r.success).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_benchmark_aggregation_per_value_size_decreases() {
+        let counts = [1, 2, 4];
+        let results = benchmark_aggregation(&counts, 8, true);
+        assert_eq!(results.len(), counts.len());
+        for result in &results {
+            assert!(result.success);
+        }
+        let per_value: Vec<f64> = results.iter()
+            .map(|r| r.proof_size_bytes as f64 / r.aggregation_count as f64)
+            .collect();
+        assert!(per_value[0] > per_value[1] && per_value[1] > per_value[2], "{:?}", per_value);
+    }
 }
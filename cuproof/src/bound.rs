@@ -0,0 +1,186 @@
+use crate::{util::*, commitment::*, fiat_shamir::*};
+use crate::backend::{GroupBackend, RsaBackend};
+use crate::lagrange::{find_3_squares_balanced, SquaresError};
+use crate::range_proof::{CommitmentBlindings, IPPProof, IppGenerators, ProveError, RangeWeights, ipp_statement, prove_range_relation, range_relation_holds, zero_padded};
+use crate::verify::{expected_ipp_levels, verify_inner_product};
+use num_bigint::BigInt;
+use rand::rngs::OsRng;
+
+/// IPP dimension of a one-sided proof: its single three-square witness needs half the
+/// coordinates of a two-sided `Cuproof`
+pub const BOUND_DIMENSION: usize = 32;
+
+/// One-sided range proof for v <= b (`prove_upper_bound`) or v >= a (`prove_lower_bound`)
+///
+/// A `Cuproof` commits to both v1 = 4v-4a+1 and v2 = 4b-4v+1. Here only the needed side is
+/// committed as C_w (w = v2 for an upper bound, w = v1 for a lower bound) and its three-square
+/// witness is the whole IPP vector. C_w is bound to C and the public bound:
+/// - upper: C_w = Commit(4b-4v+1, -4r), so C_w C^4 = g^(4b+1)
+/// - lower: C_w = Commit(4v-4a+1, 4r), so C_w g^(4a) = C^4 g
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundProof {
+    pub A: BigInt,
+    pub S: BigInt,
+    pub T1: BigInt,
+    pub T2: BigInt,
+    pub tau_x: BigInt,
+    pub mu: BigInt,
+    pub t_hat: BigInt,
+    pub C: BigInt,    // Commitment to value v
+    pub C_w: BigInt,  // Commitment to w = 4b - 4v + 1 (upper) or 4v - 4a + 1 (lower)
+    pub t0: BigInt,
+    pub t1: BigInt,
+    pub t2: BigInt,
+    pub tau1: BigInt,
+    pub tau2: BigInt,
+    pub ipp_proof: IPPProof,
+}
+
+/// Prove that v <= b without the lower-bound half of a `Cuproof`
+/// - params: v, r blinding of C, b public upper bound, g/h/n public parameters
+/// - returns: BoundProof; V2Decomposition if v > b
+/// - usage: pair with `verify_upper_bound(proof, b, ..)`
+pub fn prove_upper_bound(v: &BigInt, r: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<BoundProof, ProveError> {
+    let w = 4 * b - 4 * v + 1;
+    prove_bound(v, r, &w, &CommitmentBlindings::new(r).r_v2, g, h, n).map_err(ProveError::V2Decomposition)
+}
+
+/// Prove that v >= a without the upper-bound half of a `Cuproof`
+/// - params: v, r blinding of C, a public lower bound, g/h/n public parameters
+/// - returns: BoundProof; V1Decomposition if v < a
+/// - usage: pair with `verify_lower_bound(proof, a, ..)`
+pub fn prove_lower_bound(v: &BigInt, r: &BigInt, a: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<BoundProof, ProveError> {
+    let w = 4 * v - 4 * a + 1;
+    prove_bound(v, r, &w, &CommitmentBlindings::new(r).r_v1, g, h, n).map_err(ProveError::V1Decomposition)
+}
+
+/// Same steps as the two-sided prover, over the three squares of w alone: the whole witness is
+/// one block, opening C_w
+#[allow(non_snake_case)]
+fn prove_bound(v: &BigInt, r: &BigInt, w: &BigInt, r_w: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<BoundProof, SquaresError> {
+    let d = zero_padded(find_3_squares_balanced(w)?, BOUND_DIMENSION).expect("three squares fit in BOUND_DIMENSION");
+
+    let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+    let C = backend.commit(v, r);
+    let C_w = backend.commit(w, r_w);
+    let p = prove_range_relation(&backend, &d, &[0; BOUND_DIMENSION], std::slice::from_ref(r_w),
+        |A, S| backend.challenge(&[A, S, &C, &C_w]), &mut OsRng);
+
+    Ok(BoundProof {
+        A: p.A, S: p.S, T1: p.T1, T2: p.T2, tau_x: p.tau_x, mu: p.mu, t_hat: p.t_hat, C, C_w,
+        t0: p.t0, t1: p.t1, t2: p.t2, tau1: p.tau1, tau2: p.tau2, ipp_proof: p.ipp_proof,
+    })
+}
+
+/// Verify a `prove_upper_bound` proof that the value behind `proof.C` is at most b
+/// - params: proof, b public upper bound, g/h/n public parameters
+/// - returns: true if the proof is well-formed and C_w C^4 = g^(4b+1)
+/// - usage: a proof for b does not verify for any other bound, nor as a lower-bound proof
+pub fn verify_upper_bound(proof: &BoundProof, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    let lhs = &proof.C_w * mod_exp(&proof.C, &BigInt::from(4), n) % n;
+    let rhs = mod_exp(g, &(4 * b + 1), n);
+    bound_proof_well_formed(proof, g, h, n) & ct_eq(&lhs, &rhs, n)
+}
+
+/// Verify a `prove_lower_bound` proof that the value behind `proof.C` is at least a
+/// - params: proof, a public lower bound, g/h/n public parameters
+/// - returns: true if the proof is well-formed and C_w g^(4a) = C^4 g
+/// - usage: a proof for a does not verify for any other bound, nor as an upper-bound proof
+pub fn verify_lower_bound(proof: &BoundProof, a: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    let lhs = &proof.C_w * mod_exp(g, &(4 * a), n) % n;
+    let rhs = mod_exp(&proof.C, &BigInt::from(4), n) * g % n;
+    bound_proof_well_formed(proof, g, h, n) & ct_eq(&lhs, &rhs, n)
+}
+
+/// Checks shared by both bounds, mirroring `cuproof_verify`: the openings, the IPP opening of
+/// t_hat and its binding to C_w (the squares of the witness sum to w)
+#[allow(non_snake_case)]
+fn bound_proof_well_formed(proof: &BoundProof, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+    let zero = BigInt::from(0);
+    let y = backend.challenge(&[&proof.A, &proof.S, &proof.C, &proof.C_w]);
+    let z = backend.challenge(&[&y]);
+    let x = backend.challenge(&[&proof.T1, &proof.T2]);
+    if y == zero || z == zero || x == zero { return false; }
+
+    if !ct_eq(&pedersen_commit(g, h, &proof.t1, &proof.tau1, n), &proof.T1, n) { return false; }
+    if !ct_eq(&pedersen_commit(g, h, &proof.t2, &proof.tau2, n), &proof.T2, n) { return false; }
+    if proof.t_hat != &proof.t0 + &(&proof.t1 * &x) + &(&proof.t2 * &x * &x) { return false; }
+
+    if proof.ipp_proof.L.len() != proof.ipp_proof.R.len() { return false; }
+    if expected_ipp_levels(BOUND_DIMENSION) != Ok(proof.ipp_proof.L.len()) { return false; }
+
+    for c in [&proof.A, &proof.S, &proof.T1, &proof.T2, &proof.C, &proof.C_w] {
+        if c % n == zero { return false; }
+    }
+    if proof.C == proof.C_w { return false; }
+
+    let weights = RangeWeights::new(&backend, &y, &z, &[0; BOUND_DIMENSION], 1);
+    let gens = IppGenerators::new(&backend, BOUND_DIMENSION);
+    let P = ipp_statement(&backend, &gens, &proof.A, &proof.S, &proof.t_hat, &proof.mu, &x, &weights);
+    verify_inner_product(&backend, &weights.generators(&backend, &gens), &P, &proof.ipp_proof)
+        && range_relation_holds(&backend, &[&proof.C_w], &proof.T1, &proof.T2, &proof.t_hat, &proof.tau_x, &x, &weights)
+}
+
+/// Same count as `proof_size_bytes`, over the fields of a `BoundProof`
+pub fn bound_proof_size_bytes(proof: &BoundProof) -> usize {
+    [&proof.A, &proof.S, &proof.T1, &proof.T2, &proof.tau_x, &proof.mu, &proof.t_hat, &proof.C, &proof.C_w,
+        &proof.t0, &proof.t1, &proof.t2, &proof.tau1, &proof.tau2, &proof.ipp_proof.a, &proof.ipp_proof.b]
+        .into_iter()
+        .chain(&proof.ipp_proof.L)
+        .chain(&proof.ipp_proof.R)
+        .map(|x| x.to_bytes_be().1.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::range_proof::{cuproof_prove, proof_size_bytes};
+    use crate::setup::fast_test_setup;
+
+    // Purpose: each one-sided proof accepts its boundary value and rejects one step outside
+    // Params: fast_test_setup params, upper bound b = 100, lower bound a = 10
+    // Output: v = b / v = a prove and verify; v = b + 1 / v = a - 1 cannot be proven; a proof
+    //         checked against a bound one step tighter, or as the other side, is rejected, and so
+    //         is one with t_hat, an IPP entry or tau_x tampered
+    // Usage: `cargo test -- src::bound` or `cargo test`
+    #[test]
+    fn upper_and_lower_bounds_at_the_boundary() {
+        let (g, h, n) = fast_test_setup();
+        let r = random_bigint(128);
+        let (a, b) = (BigInt::from(10), BigInt::from(100));
+
+        let upper = prove_upper_bound(&b, &r, &b, &g, &h, &n).unwrap();
+        assert!(verify_upper_bound(&upper, &b, &g, &h, &n));
+        assert!(!verify_upper_bound(&upper, &(&b - 1), &g, &h, &n));
+        assert!(!verify_lower_bound(&upper, &b, &g, &h, &n));
+        assert_eq!(prove_upper_bound(&(&b + 1), &r, &b, &g, &h, &n), Err(ProveError::V2Decomposition(SquaresError::Negative)));
+
+        let lower = prove_lower_bound(&a, &r, &a, &g, &h, &n).unwrap();
+        assert!(verify_lower_bound(&lower, &a, &g, &h, &n));
+        assert!(!verify_lower_bound(&lower, &(&a + 1), &g, &h, &n));
+        assert!(!verify_upper_bound(&lower, &a, &g, &h, &n));
+        assert_eq!(prove_lower_bound(&(&a - 1), &r, &a, &g, &h, &n), Err(ProveError::V1Decomposition(SquaresError::Negative)));
+
+        // Tampering with the polynomial, the IPP or tau_x breaks the checks
+        let mut tampered = upper.clone();
+        tampered.t_hat += 1;
+        assert!(!verify_upper_bound(&tampered, &b, &g, &h, &n));
+        let mut tampered = upper.clone();
+        tampered.ipp_proof.L[0] = &tampered.ipp_proof.L[0] * &g % &n;
+        assert!(!verify_upper_bound(&tampered, &b, &g, &h, &n));
+        let mut tampered = upper.clone();
+        tampered.ipp_proof.a += 1;
+        assert!(!verify_upper_bound(&tampered, &b, &g, &h, &n));
+        let mut tampered = lower.clone();
+        tampered.tau_x += 1;
+        assert!(!verify_lower_bound(&tampered, &a, &g, &h, &n));
+
+        // One commitment and one IPP level fewer than the two-sided proof
+        let full = cuproof_prove(&b, &r, &a, &b, &g, &h, &n);
+        assert_eq!(upper.ipp_proof.L.len() + 1, full.ipp_proof.L.len());
+        assert!(bound_proof_size_bytes(&upper) < proof_size_bytes(&full));
+    }
+}
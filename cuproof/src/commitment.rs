@@ -1,11 +1,61 @@
 use num_bigint::BigInt;
-use num_traits::Zero;
+use num_traits::{One, Signed, Zero};
+use crate::fiat_shamir::fiat_shamir_length_prefixed;
+use crate::util::{ct_eq, random_bigint};
 
 /// Modular exponentiation: base^exp mod modulus
+///
+/// A negative exponent is taken as a power of the inverse, base^(-e) = (base^-1)^e, so
+/// commitments to negative values stay homomorphic. Panics if exp is negative and base is not
+/// invertible mod n; use `checked_mod_exp` when the base comes from outside.
 pub fn mod_exp(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> BigInt {
+    checked_mod_exp(base, exp, modulus).expect("mod_exp: negative power of a non-invertible base")
+}
+
+/// Same as `mod_exp`, but None instead of a panic when exp is negative and base has no inverse
+pub fn checked_mod_exp(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> Option<BigInt> {
     let base_pos = if base < &BigInt::zero() { -base } else { base.clone() };
     let exp_pos = if exp < &BigInt::zero() { -exp } else { exp.clone() };
-    base_pos.modpow(&exp_pos, modulus)
+    let r = base_pos.modpow(&exp_pos, modulus);
+    if exp.is_negative() { mod_inverse(&r, modulus) } else { Some(r) }
+}
+
+/// Same result as `mod_exp`, computed with a fixed window of `window_bits` bits
+///
+/// Builds the table base^d for d < 2^window_bits, then scans the exponent from the most
+/// significant window down: window_bits squarings and at most one table multiplication per
+/// window. Larger windows mean fewer multiplications but a table of 2^window_bits elements.
+/// Reductions are plain `%`, so on odd moduli `mod_exp` (Montgomery inside `modpow`) is still
+/// faster; `benches/commitment.rs` measures both. Panics if window_bits is 0 or above 16, or
+/// where `mod_exp` would.
+pub fn mod_exp_windowed(base: &BigInt, exp: &BigInt, modulus: &BigInt, window_bits: u32) -> BigInt {
+    assert!((1..=16).contains(&window_bits), "mod_exp_windowed: window_bits must be in 1..=16");
+    let base_pos = base.abs() % modulus;
+    let mut table = Vec::with_capacity(1 << window_bits);
+    table.push(BigInt::one() % modulus);
+    for d in 1..1usize << window_bits {
+        let next = &table[d - 1] * &base_pos % modulus;
+        table.push(next);
+    }
+
+    let exp_pos = exp.magnitude();
+    let w = window_bits as u64;
+    let mut acc = BigInt::one() % modulus;
+    for i in (0..exp_pos.bits().div_ceil(w)).rev() {
+        for _ in 0..w {
+            acc = &acc * &acc % modulus;
+        }
+        let digit = (0..w).filter(|&k| exp_pos.bit(i * w + k)).fold(0usize, |d, k| d | 1 << k);
+        if digit != 0 {
+            acc = acc * &table[digit] % modulus;
+        }
+    }
+    if exp.is_negative() { mod_inverse(&acc, modulus).expect("mod_exp_windowed: negative power of a non-invertible base") } else { acc }
+}
+
+/// Inverse of x modulo n; None if gcd(x, n) != 1 (never the case for elements of Z_n^*)
+pub fn mod_inverse(x: &BigInt, n: &BigInt) -> Option<BigInt> {
+    x.modinv(n)
 }
 
 /// Pedersen Commitment over RSA group
@@ -28,6 +78,333 @@ pub fn pedersen_commit(g: &BigInt, h: &BigInt, m: &BigInt, r: &BigInt, n: &BigIn
     mod_exp(g, m, n) * mod_exp(h, r, n) % n
 }
 
+/// Pedersen commitment to several messages at once: C = g_1^m_1 * ... * g_k^m_k * h^r mod n
+///
+/// Slot i is bound to `gens[i]`, so the same messages in another order give another commitment.
+/// Homomorphic slot by slot: Commit(m, r) * Commit(m', r') = Commit(m + m', r + r').
+/// With a single generator this is `pedersen_commit`.
+///
+/// Panics if `gens` and `msgs` have different lengths.
+pub fn pedersen_commit_multi(gens: &[BigInt], msgs: &[BigInt], h: &BigInt, r: &BigInt, n: &BigInt) -> BigInt {
+    assert_eq!(gens.len(), msgs.len(), "pedersen_commit_multi: gens/msgs length mismatch");
+    let bases: Vec<BigInt> = gens.iter().chain([h]).cloned().collect();
+    let exps: Vec<BigInt> = msgs.iter().chain([r]).cloned().collect();
+    multi_mod_exp(&bases, &exps, n)
+}
+
+/// Check a full opening of a `pedersen_commit` commitment
+/// - params: c commitment, v revealed value, r revealed blinding, g/h/n public parameters
+/// - returns: true if c = g^v h^r mod n
+/// - usage: once a range-proven value is disclosed, confirm it is the value the proof's C commits to
+pub fn verify_commitment_opening(c: &BigInt, v: &BigInt, r: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    ct_eq(&pedersen_commit(g, h, v, r, n), &(c % n), n)
+}
+
+/// Statistical hiding margin (bits) of an integer Schnorr nonce over |secret| * c
+pub(crate) const SCHNORR_SLACK_BITS: u64 = 128;
+
+/// Integer Schnorr responses s_i = k_i + c x_i, computed over the integers since the group
+/// order is unknown
+/// - params: secrets x_i; `commit` maps the nonces k_i to the first message, `challenge` maps
+///   that message to c
+/// - returns: the first message and the responses s_i
+/// - usage: the proofs in `sum`, `equality`, `inequality` and `open_partial`. c is a 256-bit
+///   hash, so each k_i gets |x_i| + 256 + `SCHNORR_SLACK_BITS` bits to keep s_i positive and
+///   hiding; a negative s_i (negligible probability) is resampled rather than leaking its sign
+pub(crate) fn schnorr_responses<M>(secrets: &[BigInt], commit: impl Fn(&[BigInt]) -> M, challenge: impl Fn(&M) -> BigInt) -> (M, Vec<BigInt>) {
+    loop {
+        let k: Vec<BigInt> = secrets.iter()
+            .map(|x| random_bigint((x.bits() + 256 + SCHNORR_SLACK_BITS) as usize))
+            .collect();
+        let message = commit(&k);
+        let c = challenge(&message);
+        let s: Vec<BigInt> = k.iter().zip(secrets).map(|(k, x)| k + &c * x).collect();
+        if s.iter().all(|x| !x.is_negative()) { return (message, s); }
+    }
+}
+
+/// Partial opening of a `pedersen_commit_multi` commitment C
+///
+/// `opened` reveals some slots; `rest` = prod(g_j^m_j for the other slots) * h^r carries the
+/// hidden ones. `rest` alone is unconstrained (anyone can set rest = C / g_i^m' for any claimed
+/// m'), so it comes with a Schnorr proof of knowledge of an opening over the unopened generators
+/// and h, with integer responses since the group order is unknown:
+/// - T = prod g_j^k_j * h^k_r for random nonces
+/// - c = H(C, rest, T, gens, opened)
+/// - s_j = k_j + c m_j for the unopened slots in increasing index order, then s_r = k_r + c r
+///
+/// The verifier checks prod g_j^s_j * h^s_r = T rest^c.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialOpening {
+    pub opened: Vec<(usize, BigInt)>,
+    pub rest: BigInt,
+    pub T: BigInt,
+    pub s: Vec<BigInt>,
+}
+
+#[allow(non_snake_case)]
+fn partial_opening_challenge(c: &BigInt, gens: &[BigInt], opened: &[(usize, BigInt)], rest: &BigInt, T: &BigInt) -> BigInt {
+    let indices: Vec<BigInt> = opened.iter().map(|(i, _)| BigInt::from(*i)).collect();
+    let mut inputs = vec![c, rest, T];
+    inputs.extend(gens);
+    for ((_, m), i) in opened.iter().zip(&indices) { inputs.extend([i, m]); }
+    fiat_shamir_length_prefixed(&inputs)
+}
+
+/// Slots of `gens` not in `opened`, ascending; None for an index out of range or opened twice
+fn unopened_slots(len: usize, opened: &[(usize, BigInt)]) -> Option<Vec<usize>> {
+    let mut seen = vec![false; len];
+    for (i, _) in opened {
+        if *i >= len || seen[*i] { return None; }
+        seen[*i] = true;
+    }
+    Some((0..len).filter(|i| !seen[*i]).collect())
+}
+
+/// Open the slots `reveal` of C = `pedersen_commit_multi(gens, msgs, h, r, n)`
+/// - params: gens, msgs, h, r, n as for `pedersen_commit_multi`; reveal slot indices to disclose
+/// - returns: PartialOpening with the revealed messages, rest and the proof of knowledge of rest
+/// - usage: reveal e.g. the amount of an (amount, timestamp) record, keeping the timestamp hidden in rest
+///
+/// Panics if `gens` and `msgs` differ in length or `reveal` has an index out of range or twice.
+#[allow(non_snake_case)]
+pub fn open_partial(gens: &[BigInt], msgs: &[BigInt], h: &BigInt, r: &BigInt, n: &BigInt, reveal: &[usize]) -> PartialOpening {
+    assert_eq!(gens.len(), msgs.len(), "open_partial: gens/msgs length mismatch");
+    let c = pedersen_commit_multi(gens, msgs, h, r, n);
+    let opened: Vec<(usize, BigInt)> = reveal.iter().map(|&i| (i, msgs[i].clone())).collect();
+    let hidden = unopened_slots(gens.len(), &opened).expect("open_partial: slot out of range or revealed twice");
+    let bases: Vec<BigInt> = hidden.iter().map(|&j| gens[j].clone()).chain([h.clone()]).collect();
+    let secrets: Vec<BigInt> = hidden.iter().map(|&j| msgs[j].clone()).chain([r.clone()]).collect();
+    let rest = multi_mod_exp(&bases, &secrets, n);
+    let (T, s) = schnorr_responses(&secrets, |k| multi_mod_exp(&bases, k, n),
+        |T| partial_opening_challenge(&c, gens, &opened, &rest, T));
+    PartialOpening { opened, rest, T, s }
+}
+
+/// Check a partial opening of a `pedersen_commit_multi` commitment
+/// - params: c commitment, gens its generators, opening from `open_partial`, h/n public parameters
+/// - returns: true if c = prod(gens[i]^m_i for opened slots) * rest and the proof shows knowledge
+///   of an opening of rest over the unopened generators and h; false for an index out of range,
+///   a slot opened twice or a forged rest
+/// - usage: pair with `open_partial`
+pub fn verify_partial_opening(c: &BigInt, gens: &[BigInt], opening: &PartialOpening, h: &BigInt, n: &BigInt) -> bool {
+    let Some(hidden) = unopened_slots(gens.len(), &opening.opened) else { return false };
+    if opening.s.len() != hidden.len() + 1 || opening.s.iter().any(|x| x.is_negative()) { return false; }
+    if (&opening.T % n).is_zero() || (&opening.rest % n).is_zero() { return false; }
+
+    let bases: Vec<BigInt> = opening.opened.iter().map(|(i, _)| gens[*i].clone()).collect();
+    let exps: Vec<BigInt> = opening.opened.iter().map(|(_, m)| m.clone()).collect();
+    let revealed = if bases.is_empty() { BigInt::one() } else { multi_mod_exp(&bases, &exps, n) };
+    let splits = ct_eq(&(revealed * &opening.rest % n), &(c % n), n);
+
+    let ch = partial_opening_challenge(c, gens, &opening.opened, &opening.rest, &opening.T);
+    let bases: Vec<BigInt> = hidden.iter().map(|&j| gens[j].clone()).chain([h.clone()]).collect();
+    let lhs = multi_mod_exp(&bases, &opening.s, n);
+    let rhs = &opening.T * mod_exp(&opening.rest, &ch, n) % n;
+    splits & ct_eq(&lhs, &rhs, n)
+}
+
+/// Re-randomize a commitment: C' = C * h^delta_blinding mod n
+///
+/// C' commits to the same value as C but is unlinkable to it without `delta_blinding`.
+/// Whoever opens C' must use the blinding r + delta_blinding, since
+/// Commit(m, r) * h^delta = Commit(m, r + delta).
+pub fn rerandomize(c: &BigInt, delta_blinding: &BigInt, h: &BigInt, n: &BigInt) -> BigInt {
+    c * mod_exp(h, delta_blinding, n) % n
+}
+
+/// Window width (in bits) of the fixed-base tables in `GeneratorContext`
+const FIXED_BASE_WINDOW: u64 = 4;
+
+/// Default exponent size covered by `GeneratorContext` tables; larger exponents fall back to `mod_exp`
+pub const DEFAULT_CONTEXT_EXP_BITS: u64 = 2048;
+
+/// Fixed-window table for one base: rows[j][d] = base^(d * 2^(w*j)) mod n
+#[derive(Clone)]
+struct FixedBaseTable {
+    base: BigInt,
+    rows: Vec<Vec<BigInt>>,
+}
+
+impl FixedBaseTable {
+    fn new(base: &BigInt, n: &BigInt, max_exp_bits: u64) -> Self {
+        let base_pos = base.abs() % n;
+        let windows = max_exp_bits.div_ceil(FIXED_BASE_WINDOW);
+        let table_len = 1usize << FIXED_BASE_WINDOW;
+        let mut rows = Vec::with_capacity(windows as usize);
+        let mut window_base = base_pos.clone();
+        for _ in 0..windows {
+            let mut row = Vec::with_capacity(table_len);
+            row.push(BigInt::one() % n);
+            for d in 1..table_len {
+                let next = &row[d - 1] * &window_base % n;
+                row.push(next);
+            }
+            window_base = &row[table_len - 1] * &window_base % n;
+            rows.push(row);
+        }
+        FixedBaseTable { base: base.clone(), rows }
+    }
+
+    /// base^exp mod n with the same sign handling as `mod_exp`
+    fn pow(&self, exp: &BigInt, n: &BigInt) -> BigInt {
+        let r = self.pow_magnitude(exp, n);
+        if exp.is_negative() { mod_inverse(&r, n).expect("FixedBaseTable: base is not invertible modulo n") } else { r }
+    }
+
+    /// base^|exp| mod n
+    fn pow_magnitude(&self, exp: &BigInt, n: &BigInt) -> BigInt {
+        let exp_pos = exp.magnitude();
+        let windows = exp_pos.bits().div_ceil(FIXED_BASE_WINDOW);
+        if windows > self.rows.len() as u64 {
+            return mod_exp(&self.base, &exp.abs(), n);
+        }
+        let mut acc = BigInt::one() % n;
+        for (j, row) in self.rows.iter().enumerate().take(windows as usize) {
+            let mut digit = 0usize;
+            for k in 0..FIXED_BASE_WINDOW {
+                if exp_pos.bit(j as u64 * FIXED_BASE_WINDOW + k) { digit |= 1 << k; }
+            }
+            if digit != 0 {
+                acc = acc * &row[digit] % n;
+            }
+        }
+        acc
+    }
+}
+
+/// Precomputed state for the fixed public parameters (g, h, n)
+///
+/// Since g, h and n are constant for a whole session, the fixed-window tables for g and h are
+/// built once here and every `commit` only performs table lookups and multiplications
+/// (no squarings). Exponents wider than the tables fall back to `mod_exp`.
+#[derive(Clone)]
+pub struct GeneratorContext {
+    pub g: BigInt,
+    pub h: BigInt,
+    pub n: BigInt,
+    pub(crate) challenge_modulus: BigInt,
+    g_table: FixedBaseTable,
+    h_table: FixedBaseTable,
+}
+
+impl GeneratorContext {
+    /// Build tables covering exponents up to `DEFAULT_CONTEXT_EXP_BITS` bits
+    pub fn new(g: &BigInt, h: &BigInt, n: &BigInt) -> Self {
+        Self::with_max_exp_bits(g, h, n, DEFAULT_CONTEXT_EXP_BITS)
+    }
+
+    /// Build tables covering exponents up to `max_exp_bits` bits
+    pub fn with_max_exp_bits(g: &BigInt, h: &BigInt, n: &BigInt, max_exp_bits: u64) -> Self {
+        GeneratorContext {
+            g: g.clone(),
+            h: h.clone(),
+            n: n.clone(),
+            challenge_modulus: crate::fiat_shamir::challenge_modulus(n),
+            g_table: FixedBaseTable::new(g, n, max_exp_bits),
+            h_table: FixedBaseTable::new(h, n, max_exp_bits),
+        }
+    }
+
+    /// Whether this context was built for the given public parameters
+    pub fn matches(&self, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+        &self.g == g && &self.h == h && &self.n == n
+    }
+
+    /// Same result as `pedersen_commit(g, h, m, r, n)`, reusing the precomputed tables
+    pub fn commit(&self, m: &BigInt, r: &BigInt) -> BigInt {
+        self.g_table.pow(m, &self.n) * self.h_table.pow(r, &self.n) % &self.n
+    }
+}
+
+/// Running Pedersen commitment to a sum of values
+///
+/// Each `add(m_i, r_i)` folds g^m_i h^r_i into a product mod n, so committing to thousands of
+/// values needs O(1) memory. By the homomorphism, `finalize()` equals
+/// `pedersen_commit(g, h, sum m_i, sum r_i, n)`.
+#[derive(Clone, Debug)]
+pub struct CommitmentAccumulator {
+    g: BigInt,
+    h: BigInt,
+    n: BigInt,
+    acc: BigInt,
+}
+
+impl CommitmentAccumulator {
+    pub fn new(g: &BigInt, h: &BigInt, n: &BigInt) -> Self {
+        CommitmentAccumulator { g: g.clone(), h: h.clone(), n: n.clone(), acc: BigInt::one() % n }
+    }
+
+    /// Fold one (value, blinding) contribution into the running product
+    pub fn add(&mut self, value: &BigInt, blinding: &BigInt) {
+        let c = pedersen_commit(&self.g, &self.h, value, blinding, &self.n);
+        self.acc = &self.acc * c % &self.n;
+    }
+
+    /// Commitment to the sum of all added values and blindings
+    pub fn finalize(self) -> BigInt {
+        self.acc
+    }
+}
+
+/// Window width (in bits) used by `multi_mod_exp`
+const MULTI_EXP_WINDOW: u64 = 4;
+
+/// Simultaneous multi-exponentiation: prod(bases[i]^exps[i]) mod n
+///
+/// Uses Straus' interleaved fixed-window method (the generalisation of Shamir's trick):
+/// every exponent is scanned from the most significant window downwards, so the squarings
+/// are shared by all bases instead of being repeated once per `mod_exp`.
+///
+/// Signs are normalised exactly like `mod_exp`, so the result is identical to the naive
+/// product of individual `mod_exp` calls.
+///
+/// Panics if `bases` and `exps` have different lengths, or where `mod_exp` would.
+pub fn multi_mod_exp(bases: &[BigInt], exps: &[BigInt], n: &BigInt) -> BigInt {
+    assert_eq!(bases.len(), exps.len(), "multi_mod_exp: bases/exps length mismatch");
+
+    let table_len = 1usize << MULTI_EXP_WINDOW;
+    // tables[i][d] = bases[i]^d mod n
+    // Negative exponents use the inverted base, matching `mod_exp`
+    let tables: Vec<Vec<BigInt>> = bases.iter().zip(exps).map(|(base, exp)| {
+        let base_pos = base.abs() % n;
+        let base_pos = if exp.is_negative() {
+            mod_inverse(&base_pos, n).expect("multi_mod_exp: negative power of a non-invertible base")
+        } else {
+            base_pos
+        };
+        let mut row = Vec::with_capacity(table_len);
+        row.push(BigInt::one() % n);
+        for d in 1..table_len {
+            let next = &row[d - 1] * &base_pos % n;
+            row.push(next);
+        }
+        row
+    }).collect();
+    let exps_pos: Vec<_> = exps.iter().map(|e| e.magnitude().clone()).collect();
+
+    let max_bits = exps_pos.iter().map(|e| e.bits()).max().unwrap_or(0);
+    let windows = max_bits.div_ceil(MULTI_EXP_WINDOW);
+
+    let mut acc = BigInt::one() % n;
+    for w in (0..windows).rev() {
+        for _ in 0..MULTI_EXP_WINDOW {
+            acc = &acc * &acc % n;
+        }
+        for (table, exp) in tables.iter().zip(&exps_pos) {
+            let mut digit = 0usize;
+            for k in 0..MULTI_EXP_WINDOW {
+                if exp.bit(w * MULTI_EXP_WINDOW + k) { digit |= 1 << k; }
+            }
+            if digit != 0 {
+                acc = acc * &table[digit] % n;
+            }
+        }
+    }
+    acc
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,5 +436,209 @@ mod tests {
         let lhs = c1 * c2 % &n;
         let rhs = pedersen_commit(&g, &h, &(m1.clone()+m2.clone()), &(r1.clone()+r2.clone()), &n);
         assert_eq!(lhs, rhs);
+
+        // negative exponents invert: C(m, r) * C(-m, -r) = 1
+        let c_neg = pedersen_commit(&g, &h, &-&m2, &-&r2, &n);
+        assert_eq!(pedersen_commit(&g, &h, &m2, &r2, &n) * c_neg % &n, BigInt::from(1));
+    }
+
+    // Purpose: the windowed exponentiation agrees with `mod_exp` for every window size
+    // Params: fast_test_setup modulus, random 256-bit exponents, edge exponents 0, 1, -e
+    // Output: equality with mod_exp for window sizes 1..=8
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn windowed_mod_exp_matches_mod_exp() {
+        use crate::util::random_bigint;
+        let (g, h, n) = fast_test_setup();
+        let exps = [BigInt::zero(), BigInt::one(), random_bigint(256), random_bigint(256), -random_bigint(256), &n - 1];
+        for window_bits in 1..=8 {
+            for base in [&g, &h] {
+                for e in &exps {
+                    assert_eq!(mod_exp_windowed(base, e, &n, window_bits), mod_exp(base, e, &n), "window {}", window_bits);
+                }
+            }
+        }
+    }
+
+    // Purpose: negative powers of a non-invertible base fail instead of panicking
+    // Params: modulus 15, base 2 (inverse 8) and base 6 (gcd 3), exponents -1 and 2
+    // Output: Some for the invertible base or a positive exponent, None otherwise
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn inverse_of_non_unit_is_none() {
+        let n = BigInt::from(15);
+        assert_eq!(mod_inverse(&BigInt::from(2), &n), Some(BigInt::from(8)));
+        assert_eq!(mod_inverse(&BigInt::from(6), &n), None);
+        assert_eq!(checked_mod_exp(&BigInt::from(2), &BigInt::from(-1), &n), Some(BigInt::from(8)));
+        assert_eq!(checked_mod_exp(&BigInt::from(6), &BigInt::from(-1), &n), None);
+        assert_eq!(checked_mod_exp(&BigInt::from(6), &BigInt::from(2), &n), Some(BigInt::from(6)));
+    }
+
+    // Purpose: a re-randomized commitment opens to the original value with the adjusted blinding
+    // Params: fast_test_setup params, random 256-bit blinding and delta
+    // Output: equality/inequality assertions
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn rerandomized_commitment_opens_with_adjusted_blinding() {
+        use crate::util::random_bigint;
+        let (g, h, n) = fast_test_setup();
+        let (m, r, delta) = (BigInt::from(42), random_bigint(256), random_bigint(256));
+        let c = pedersen_commit(&g, &h, &m, &r, &n);
+        let c_new = rerandomize(&c, &delta, &h, &n);
+        assert_ne!(c_new, c);
+        assert_eq!(c_new, pedersen_commit(&g, &h, &m, &(&r + &delta), &n));
+        // A negative delta undoes the re-randomization
+        assert_eq!(rerandomize(&c_new, &-&delta, &h, &n), c);
+    }
+
+    // Purpose: the streaming accumulator equals one commitment to the summed values/blindings
+    // Params: fast_test_setup params, 1,000 random (value, blinding) pairs
+    // Output: equality assertion
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn accumulator_matches_one_shot_commitment() {
+        use crate::util::random_bigint;
+        let (g, h, n) = fast_test_setup();
+        let mut acc = CommitmentAccumulator::new(&g, &h, &n);
+        let mut sum_m = BigInt::zero();
+        let mut sum_r = BigInt::zero();
+        for _ in 0..1_000 {
+            let (m, r) = (random_bigint(64), random_bigint(128));
+            acc.add(&m, &r);
+            sum_m += &m;
+            sum_r += &r;
+        }
+        assert_eq!(acc.finalize(), pedersen_commit(&g, &h, &sum_m, &sum_r, &n));
+    }
+
+    // Purpose: a commitment opens to its own (v, r) and to no other value or blinding
+    // Params: fast_test_setup params, v = 42, random 128-bit r
+    // Output: true for (v, r); false for v + 1 and for r + 1
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn commitment_opening_checks_value_and_blinding() {
+        let (g, h, n) = fast_test_setup();
+        let (v, r) = (BigInt::from(42), crate::util::random_bigint(128));
+        let c = pedersen_commit(&g, &h, &v, &r, &n);
+        assert!(verify_commitment_opening(&c, &v, &r, &g, &h, &n));
+        assert!(!verify_commitment_opening(&c, &(&v + 1), &r, &g, &h, &n));
+        assert!(!verify_commitment_opening(&c, &v, &(&r + 1), &g, &h, &n));
+    }
+
+    // Purpose: multi-slot commitments are homomorphic per slot, order-sensitive and partially openable
+    // Params: fast_test_setup params, generators 1..=3 of the RSA backend, small record messages
+    // Output: equality/inequality assertions; an honest opening verifies, a wrong or duplicated one is rejected
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn multi_message_commitment_homomorphism_and_opening() {
+        use crate::backend::{GroupBackend, RsaBackend};
+        use crate::util::random_bigint;
+        let (g, h, n) = fast_test_setup();
+        let backend: RsaBackend = RsaBackend::new(&g, &h, &n);
+        let gens: Vec<BigInt> = (1..=3).map(|i| backend.generator(i)).collect();
+        let m = [BigInt::from(500), BigInt::from(1_700_000_000), BigInt::from(7)];
+        let m2 = [BigInt::from(25), BigInt::from(60), BigInt::from(-3)];
+        let (r, r2) = (random_bigint(256), random_bigint(256));
+
+        let c = pedersen_commit_multi(&gens, &m, &h, &r, &n);
+        let c2 = pedersen_commit_multi(&gens, &m2, &h, &r2, &n);
+        let sum: Vec<BigInt> = m.iter().zip(&m2).map(|(a, b)| a + b).collect();
+        assert_eq!(c * &c2 % &n, pedersen_commit_multi(&gens, &sum, &h, &(&r + &r2), &n));
+
+        // Swapping two messages moves them to other generators
+        let swapped = [m[1].clone(), m[0].clone(), m[2].clone()];
+        assert_ne!(pedersen_commit_multi(&gens, &swapped, &h, &r, &n), pedersen_commit_multi(&gens, &m, &h, &r, &n));
+        // One slot is the single-message commitment
+        assert_eq!(pedersen_commit_multi(&gens[..1], &m[..1], &h, &r, &n), pedersen_commit(&gens[0], &h, &m[0], &r, &n));
+
+        // Open slot 0, keep slots 1 and 2 behind `rest`
+        let c = pedersen_commit_multi(&gens, &m, &h, &r, &n);
+        let opening = open_partial(&gens, &m, &h, &r, &n, &[0]);
+        assert_eq!(opening.rest, pedersen_commit_multi(&gens[1..], &m[1..], &h, &r, &n));
+        assert!(verify_partial_opening(&c, &gens, &opening, &h, &n));
+        let with = |opened: Vec<(usize, BigInt)>| PartialOpening { opened, ..opening.clone() };
+        assert!(!verify_partial_opening(&c, &gens, &with(vec![(0, &m[0] + 1)]), &h, &n));
+        assert!(!verify_partial_opening(&c, &gens, &with(vec![(1, m[0].clone())]), &h, &n));
+        assert!(!verify_partial_opening(&c, &gens, &with(vec![(0, m[0].clone()), (0, BigInt::zero())]), &h, &n));
+        assert!(!verify_partial_opening(&c, &gens, &with(vec![(3, m[0].clone())]), &h, &n));
+        // Opening everything leaves only h^r
+        let all = open_partial(&gens, &m, &h, &r, &n, &[0, 1, 2]);
+        assert_eq!(all.rest, mod_exp(&h, &r, &n));
+        assert!(verify_partial_opening(&c, &gens, &all, &h, &n));
+    }
+
+    // Purpose: a partial opening cannot claim another value by choosing rest = C / g_0^m'
+    // Params: fast_test_setup params, 2-slot commitment to (500, 7), claimed slot 0 value 999
+    // Output: the forged rest splits C correctly but fails the proof of knowledge, whether it
+    //         reuses the honest proof or a proof for the honest rest
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn partial_opening_rejects_forged_rest() {
+        use crate::backend::{GroupBackend, RsaBackend};
+        use crate::util::random_bigint;
+        let (g, h, n) = fast_test_setup();
+        let backend: RsaBackend = RsaBackend::new(&g, &h, &n);
+        let gens: Vec<BigInt> = (1..=2).map(|i| backend.generator(i)).collect();
+        let m = [BigInt::from(500), BigInt::from(7)];
+        let r = random_bigint(256);
+        let c = pedersen_commit_multi(&gens, &m, &h, &r, &n);
+        let honest = open_partial(&gens, &m, &h, &r, &n, &[0]);
+        assert!(verify_partial_opening(&c, &gens, &honest, &h, &n));
+
+        let claimed = BigInt::from(999);
+        let forged_rest = &c * mod_exp(&gens[0], &-&claimed, &n) % &n;
+        assert_eq!(mod_exp(&gens[0], &claimed, &n) * &forged_rest % &n, c);
+        let forged = PartialOpening { opened: vec![(0, claimed)], rest: forged_rest, ..honest.clone() };
+        assert!(!verify_partial_opening(&c, &gens, &forged, &h, &n));
+
+        // Without the proof the forgery was accepted; an empty or extra response is rejected too
+        assert!(!verify_partial_opening(&c, &gens, &PartialOpening { s: vec![], ..forged.clone() }, &h, &n));
+        let mut extra = forged.clone();
+        extra.s.push(BigInt::from(1));
+        assert!(!verify_partial_opening(&c, &gens, &extra, &h, &n));
+    }
+
+    // Purpose: multi_mod_exp must agree with the naive product of individual mod_exp calls
+    // Params: fast_test_setup modulus, random bases/exponents (including zero and negative values)
+    // Output: equality assertions
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn multi_mod_exp_matches_naive_product() {
+        use crate::util::random_bigint;
+        let (g, h, n) = fast_test_setup();
+        for len in [0usize, 1, 2, 7, 32] {
+            let bases: Vec<BigInt> = (0..len).map(|i| if i % 2 == 0 { g.clone() + i } else { random_bigint(512) }).collect();
+            let mut exps: Vec<BigInt> = (0..len).map(|_| random_bigint(256)).collect();
+            if len > 1 {
+                exps[0] = BigInt::from(0);
+                exps[1] = -&exps[1];
+            }
+            let naive = bases.iter().zip(&exps)
+                .fold(BigInt::from(1) % &n, |acc, (b, e)| acc * mod_exp(b, e, &n) % &n);
+            assert_eq!(multi_mod_exp(&bases, &exps, &n), naive);
+        }
+
+        // two-base case is exactly a Pedersen commitment
+        let m = random_bigint(256);
+        let r = random_bigint(256);
+        assert_eq!(multi_mod_exp(&[g.clone(), h.clone()], &[m.clone(), r.clone()], &n), pedersen_commit(&g, &h, &m, &r, &n));
+    }
+
+    // Purpose: GeneratorContext::commit must equal pedersen_commit, including the mod_exp fallback
+    // Params: fast_test_setup params, small tables to force the fallback path
+    // Output: equality assertions
+    // Usage: `cargo test -- src::commitment` or `cargo test`
+    #[test]
+    fn generator_context_matches_pedersen() {
+        use crate::util::random_bigint;
+        let (g, h, n) = fast_test_setup();
+        let ctx = GeneratorContext::with_max_exp_bits(&g, &h, &n, 300);
+        assert!(ctx.matches(&g, &h, &n));
+        for bits in [1usize, 64, 256, 300, 1024] {
+            let m = random_bigint(bits);
+            let r = random_bigint(256);
+            assert_eq!(ctx.commit(&m, &r), pedersen_commit(&g, &h, &m, &r, &n));
+        }
+        assert_eq!(ctx.commit(&BigInt::from(0), &BigInt::from(0)), pedersen_commit(&g, &h, &BigInt::from(0), &BigInt::from(0), &n));
     }
 }
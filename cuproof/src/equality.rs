@@ -0,0 +1,79 @@
+use crate::{commitment::*, fiat_shamir::*};
+use num_bigint::BigInt;
+use num_traits::Signed;
+
+/// Proof that C1 = Commit(v, r1) and C2 = Commit(v, r2) open to the same v
+///
+/// Schnorr proof of knowledge of the discrete log of C1/C2 = h^(r1 - r2) base h:
+/// - T = h^k for a random nonce k
+/// - c = H(C1, C2, T)
+/// - s = k + c (r1 - r2), computed over the integers since the group order is unknown
+///
+/// The verifier checks h^s = T (C1/C2)^c in the inverse-free form h^s C2^c = T C1^c.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EqualityProof {
+    pub T: BigInt,
+    pub s: BigInt,
+}
+
+#[allow(non_snake_case)]
+fn equality_challenge(C1: &BigInt, C2: &BigInt, T: &BigInt) -> BigInt {
+    fiat_shamir(&[C1, C2, T])
+}
+
+/// Prove that Commit(v, r1) and Commit(v, r2) commit to the same value
+/// - params: v shared value, r1/r2 blindings, g/h/n public parameters
+/// - returns: EqualityProof (the commitments are recomputed by the verifier's caller)
+/// - usage: link two commitments to one hidden value, e.g. across two range proofs
+#[allow(non_snake_case)]
+pub fn prove_equality(v: &BigInt, r1: &BigInt, r2: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> EqualityProof {
+    let C1 = pedersen_commit(g, h, v, r1, n);
+    let C2 = pedersen_commit(g, h, v, r2, n);
+    let (T, mut s) = schnorr_responses(&[r1 - r2], |k| mod_exp(h, &k[0], n), |T| equality_challenge(&C1, &C2, T));
+    EqualityProof { T, s: s.remove(0) }
+}
+
+/// Verify an EqualityProof for commitments c1 and c2
+/// - params: c1, c2 commitments, proof, g/h/n public parameters
+/// - returns: true if c1 and c2 open to the same value
+/// - usage: pair with `prove_equality`; g is unused (only C1/C2 = h^(r1 - r2) is proven)
+pub fn verify_equality(c1: &BigInt, c2: &BigInt, proof: &EqualityProof, _g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    if proof.s.is_negative() { return false; }
+    if &proof.T % n == BigInt::from(0) { return false; }
+    let c = equality_challenge(c1, c2, &proof.T);
+    let lhs = mod_exp(h, &proof.s, n) * mod_exp(c2, &c, n) % n;
+    let rhs = &proof.T * mod_exp(c1, &c, n) % n;
+    lhs == rhs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup::fast_test_setup;
+    use crate::util::random_bigint;
+
+    // Purpose: equal committed values verify, unequal values or tampered proofs do not
+    // Params: fast_test_setup params, random 256-bit blindings
+    // Output: boolean assertions on verify_equality
+    // Usage: `cargo test -- src::equality` or `cargo test`
+    #[test]
+    fn equality_pass_and_fail() {
+        let (g, h, n) = fast_test_setup();
+        let v = BigInt::from(42);
+        let r1 = random_bigint(256);
+        let r2 = random_bigint(256);
+        let c1 = pedersen_commit(&g, &h, &v, &r1, &n);
+        let c2 = pedersen_commit(&g, &h, &v, &r2, &n);
+        let proof = prove_equality(&v, &r1, &r2, &g, &h, &n);
+        assert!(verify_equality(&c1, &c2, &proof, &g, &h, &n));
+
+        // Same blindings, different value: the proof must not transfer
+        let c2_other = pedersen_commit(&g, &h, &BigInt::from(43), &r2, &n);
+        assert!(!verify_equality(&c1, &c2_other, &proof, &g, &h, &n));
+
+        let mut bad = proof.clone();
+        bad.s += 1;
+        assert!(!verify_equality(&c1, &c2, &bad, &g, &h, &n));
+    }
+}
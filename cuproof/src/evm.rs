@@ -0,0 +1,681 @@
+use crate::range_proof::{Cuproof, IPPProof};
+use crate::aggregate::{AggregatedCuproof, cuproof_verify_aggregated};
+use crate::commitment::pedersen_commit;
+use crate::util::ct_eq;
+use crate::verify::VerifyError;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::Zero;
+use sha3::{Digest, Keccak256};
+use std::io::{self, Write};
+
+/// Keccak-256 over each input as a big-endian uint256 word, matching abi.encodePacked in
+/// CuproofVerifier256.sol. Inputs wider than 32 bytes (a modulus above 256 bits) are encoded as a
+/// 32-byte length word followed by the value left-padded to a multiple of 32 bytes, so the
+/// encoding stays deterministic and never panics; such transcripts have no on-chain counterpart
+///
+/// Unlike `fiat_shamir` (decimal strings, hasher chosen by feature), this is the transcript of
+/// the contracts, so the exporters below recompute x with it whichever hasher made the proof
+pub fn fiat_shamir_uint256(inputs: &[&BigInt]) -> BigInt {
+    let mut hasher = Keccak256::new();
+    for i in inputs {
+        let (sign, bytes) = i.to_bytes_be();
+        let width = bytes.len().div_ceil(32).max(1) * 32;
+        if width > 32 {
+            let mut len_word = [0u8; 32];
+            len_word[24..].copy_from_slice(&(bytes.len() as u64).to_be_bytes());
+            hasher.update(len_word);
+        }
+        let mut padded = vec![0u8; width];
+        padded[width - bytes.len()..].copy_from_slice(&bytes);
+        // Handle negative by inverting (though in practice all values should be positive)
+        if sign == num_bigint::Sign::Minus {
+            for b in &mut padded {
+                *b = !*b;
+            }
+        }
+        hasher.update(&padded);
+    }
+    BigInt::from_bytes_be(num_bigint::Sign::Plus, &hasher.finalize())
+}
+
+/// Verify the EVM-reduced form of a proof (every scalar taken mod n, as exported to JSON) with
+/// the checks CuproofVerifier256.sol performs
+/// - params: proof from `import_proof_json` (or any `evm_reduced` proof), g/h/n public parameters
+/// - returns: the first failing check: challenges from `fiat_shamir_uint256`, the T1/T2
+///   openings, t_hat == t0 + t1 x + t2 x^2 mod n, the t_hat commitment, IPP lengths and the
+///   commitment sanity checks
+/// - usage: the reduced form drops the prover's transcript, so `cuproof_verify` rejects it
+pub fn cuproof_verify_reduced(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<(), VerifyError> {
+    // 1. Fiat–Shamir over uint256 words
+    let y = fiat_shamir_uint256(&[&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2]) % n;
+    let z = fiat_shamir_uint256(&[&y]) % n;
+    let x = fiat_shamir_uint256(&[&proof.T1, &proof.T2]) % n;
+    if y.is_zero() || z.is_zero() || x.is_zero() { return Err(VerifyError::ZeroChallenge); }
+
+    // 2. Check T1, T2 commitments
+    if !ct_eq(&pedersen_commit(g, h, &proof.t1, &proof.tau1, n), &proof.T1, n) { return Err(VerifyError::T1Mismatch); }
+    if !ct_eq(&pedersen_commit(g, h, &proof.t2, &proof.tau2, n), &proof.T2, n) { return Err(VerifyError::T2Mismatch); }
+
+    // 3. t_hat ?= t0 + t1 x + t2 x^2 mod n
+    let rhs_t = (&proof.t0 + &(&proof.t1 * &x) + &(&proof.t2 * &x * &x)) % n;
+    if proof.t_hat != rhs_t { return Err(VerifyError::THatInconsistent); }
+
+    // 4. Commitment consistency for t_hat
+    let lhs = pedersen_commit(g, h, &proof.t_hat, &proof.tau_x, n);
+    let rhs = pedersen_commit(g, h, &rhs_t, &proof.tau_x, n);
+    if !ct_eq(&lhs, &rhs, n) { return Err(VerifyError::THatCommitmentMismatch); }
+
+    // 5. IPP lengths only: the contract does not fold the IPP, and expects dimension 64
+    if proof.ipp_proof.L.len() != proof.ipp_proof.R.len() { return Err(VerifyError::IppLengthMismatch); }
+    let expected_levels = crate::range_proof::DEFAULT_DIMENSION.trailing_zeros() as usize;
+    if proof.ipp_proof.L.len() != expected_levels {
+        return Err(VerifyError::IppLevelMismatch { expected: expected_levels, found: proof.ipp_proof.L.len() });
+    }
+
+    // 6. Commitments must be non-zero modulo n and pairwise distinct
+    for c in [&proof.A, &proof.S, &proof.T1, &proof.T2, &proof.C, &proof.C_v1, &proof.C_v2] {
+        if (c % n).is_zero() { return Err(VerifyError::ZeroCommitment); }
+    }
+    if proof.C == proof.C_v1 || proof.C == proof.C_v2 || proof.C_v1 == proof.C_v2 {
+        return Err(VerifyError::DuplicateCommitment);
+    }
+
+    Ok(())
+}
+
+/// Convert BigInt to uint256 (ensure it fits in 256 bits)
+/// Applies modulo n first to ensure values are in the correct range
+/// Returns the lower 256 bits as a hex string
+fn bigint_to_uint256(x: &BigInt, n: &BigInt) -> String {
+    let x_mod = x % n;
+    let (_sign, bytes) = x_mod.to_bytes_be();
+    if bytes.len() > 32 {
+        // Take only the last 32 bytes (lower 256 bits)
+        let start = bytes.len() - 32;
+        hex::encode(&bytes[start..])
+    } else {
+        // Pad with zeros if needed
+        let mut padded = vec![0u8; 32];
+        let offset = 32 - bytes.len();
+        padded[offset..].copy_from_slice(&bytes);
+        hex::encode(&padded)
+    }
+}
+
+/// Proof in the EVM-reduced form shared by the single-proof exporters: `Cuproof::reduce_mod`,
+/// then t0, t1, t2, tau1, tau2 taken into [0, n) with T1, T2, t_hat, tau_x recalculated from
+/// them so the exported values stay consistent with the contract
+#[allow(non_snake_case)]
+fn evm_reduced(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> Cuproof {
+    let mut reduced = proof.clone();
+    reduced.reduce_mod(n);
+    // t0 includes delta(y, z) < 0, and uint256 words carry no sign
+    for x in [&mut reduced.t0, &mut reduced.t1, &mut reduced.t2, &mut reduced.tau1, &mut reduced.tau2] {
+        *x = x.mod_floor(n);
+    }
+
+    // Recalculate T1, T2 and x from the modulo'd openings
+    reduced.T1 = pedersen_commit(g, h, &reduced.t1, &reduced.tau1, n);
+    reduced.T2 = pedersen_commit(g, h, &reduced.t2, &reduced.tau2, n);
+    let x = fiat_shamir_uint256(&[&reduced.T1, &reduced.T2]) % n;
+
+    // Recalculate t_hat and tau_x from the modulo'd coefficients and recalculated x
+    // (mu = alpha z + rho x needs the prover's alpha, rho, so it is only reduced)
+    reduced.t_hat = (&reduced.t0 + &(&reduced.t1 * &x) + &(&reduced.t2 * &x * &x)) % n;
+    reduced.tau_x = (&reduced.tau2 * &x * &x + &reduced.tau1 * &x) % n;
+    reduced
+}
+
+/// Serialize proof to EVM-compatible format
+/// Returns a JSON-like structure that can be used in Solidity
+/// T1 and T2 are recalculated from modulo'd t1, tau1, t2, tau2 to ensure consistency
+pub fn serialize_proof_for_evm(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> String {
+    let p = evm_reduced(proof, g, h, n);
+
+    let mut output = String::new();
+    
+    output.push_str("// Cuproof Proof for EVM (256-bit modulus)\n");
+    output.push_str("// Use this data with CuproofVerifier256.sol\n\n");
+    
+    output.push_str("// Scalars (15 values):\n");
+    output.push_str("// [A, S, T1, T2, tau_x, mu, t_hat, C, C_v1, C_v2, t0, t1, t2, tau1, tau2]\n");
+    output.push_str("uint256[15] memory scalars = [\n");
+    
+    // Export with recalculated T1, T2, tau_x, t_hat and modulo'd t0, t1, t2, tau1, tau2
+    let scalars = vec![
+        &p.A, &p.S, &p.T1, &p.T2, &p.tau_x,
+        &p.mu, &p.t_hat, &p.C, &p.C_v1, &p.C_v2,
+        &p.t0, &p.t1, &p.t2, &p.tau1, &p.tau2,
+    ];
+    
+    for (i, scalar) in scalars.iter().enumerate() {
+        let hex_val = bigint_to_uint256(scalar, n);
+        output.push_str(&format!("    uint256(0x{}),", hex_val));
+        if i < scalars.len() - 1 {
+            output.push_str(" // ");
+            output.push_str(match i {
+                0 => "A",
+                1 => "S",
+                2 => "T1",
+                3 => "T2",
+                4 => "tau_x",
+                5 => "mu",
+                6 => "t_hat",
+                7 => "C",
+                8 => "C_v1",
+                9 => "C_v2",
+                10 => "t0",
+                11 => "t1",
+                12 => "t2",
+                13 => "tau1",
+                14 => "tau2",
+                _ => "",
+            });
+        }
+        output.push('\n');
+    }
+    output.push_str("];\n\n");
+    
+    output.push_str("// IPP Proof L vector:\n");
+    output.push_str(&format!("uint256[] memory ipp_L = new uint256[]({});\n", p.ipp_proof.L.len()));
+    for (i, l_val) in p.ipp_proof.L.iter().enumerate() {
+        let hex_val = bigint_to_uint256(l_val, n);
+        output.push_str(&format!("ipp_L[{}] = uint256(0x{});\n", i, hex_val));
+    }
+    output.push('\n');
+    
+    output.push_str("// IPP Proof R vector:\n");
+    output.push_str(&format!("uint256[] memory ipp_R = new uint256[]({});\n", p.ipp_proof.R.len()));
+    for (i, r_val) in p.ipp_proof.R.iter().enumerate() {
+        let hex_val = bigint_to_uint256(r_val, n);
+        output.push_str(&format!("ipp_R[{}] = uint256(0x{});\n", i, hex_val));
+    }
+    output.push('\n');
+    
+    output.push_str("// IPP Proof scalars:\n");
+    let a_hex = bigint_to_uint256(&p.ipp_proof.a, n);
+    let b_hex = bigint_to_uint256(&p.ipp_proof.b, n);
+    output.push_str(&format!("uint256 ipp_a = uint256(0x{});\n", a_hex));
+    output.push_str(&format!("uint256 ipp_b = uint256(0x{});\n", b_hex));
+    
+    output
+}
+
+/// Export proof to JSON format for JavaScript/TypeScript integration
+/// T1 and T2 are recalculated from modulo'd t1, tau1, t2, tau2 to ensure consistency
+pub fn export_proof_json(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> String {
+    let p = evm_reduced(proof, g, h, n);
+
+    let mut json = String::new();
+    json.push_str("{\n");
+    
+    json.push_str("  \"scalars\": [\n");
+    let scalars = vec![
+        &p.A, &p.S, &p.T1, &p.T2, &p.tau_x,
+        &p.mu, &p.t_hat, &p.C, &p.C_v1, &p.C_v2,
+        &p.t0, &p.t1, &p.t2, &p.tau1, &p.tau2,
+    ];
+    for (i, scalar) in scalars.iter().enumerate() {
+        let hex_val = bigint_to_uint256(scalar, n);
+        json.push_str(&format!("    \"0x{}\"", hex_val));
+        if i < scalars.len() - 1 {
+            json.push(',');
+        }
+        json.push('\n');
+    }
+    json.push_str("  ],\n");
+    
+    json.push_str("  \"ipp_L\": [\n");
+    for (i, l_val) in p.ipp_proof.L.iter().enumerate() {
+        let hex_val = bigint_to_uint256(l_val, n);
+        json.push_str(&format!("    \"0x{}\"", hex_val));
+        if i < p.ipp_proof.L.len() - 1 {
+            json.push(',');
+        }
+        json.push('\n');
+    }
+    json.push_str("  ],\n");
+    
+    json.push_str("  \"ipp_R\": [\n");
+    for (i, r_val) in p.ipp_proof.R.iter().enumerate() {
+        let hex_val = bigint_to_uint256(r_val, n);
+        json.push_str(&format!("    \"0x{}\"", hex_val));
+        if i < p.ipp_proof.R.len() - 1 {
+            json.push(',');
+        }
+        json.push('\n');
+    }
+    json.push_str("  ],\n");
+    
+    let a_hex = bigint_to_uint256(&p.ipp_proof.a, n);
+    let b_hex = bigint_to_uint256(&p.ipp_proof.b, n);
+    json.push_str(&format!("  \"ipp_a\": \"0x{}\",\n", a_hex));
+    json.push_str(&format!("  \"ipp_b\": \"0x{}\"\n", b_hex));
+    
+    json.push_str("}\n");
+    json
+}
+
+/// Parse one `"0x..."` uint256 literal as written by `bigint_to_uint256`
+fn parse_uint256(s: &str) -> io::Result<BigInt> {
+    let t = s.trim().trim_matches('"');
+    let digits = t.strip_prefix("0x").ok_or_else(|| invalid_json(&format!("expected 0x-prefixed hex, got {}", t)))?;
+    let bytes = hex::decode(digits).map_err(|_| invalid_json(&format!("invalid hex: {}", t)))?;
+    if bytes.len() > 32 { return Err(invalid_json(&format!("value wider than uint256: {}", t))); }
+    Ok(BigInt::from_bytes_be(num_bigint::Sign::Plus, &bytes))
+}
+
+fn invalid_json(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Text after `"key":`, with surrounding whitespace removed
+fn json_field<'a>(json: &'a str, key: &str) -> io::Result<&'a str> {
+    let tag = format!("\"{}\":", key);
+    let start = json.find(&tag).ok_or_else(|| invalid_json(&format!("missing key {}", key)))?;
+    Ok(json[start + tag.len()..].trim_start())
+}
+
+fn json_hex_array(json: &str, key: &str) -> io::Result<Vec<BigInt>> {
+    let rest = json_field(json, key)?;
+    let body = rest.strip_prefix('[').ok_or_else(|| invalid_json(&format!("{} is not an array", key)))?;
+    let end = body.find(']').ok_or_else(|| invalid_json(&format!("unterminated array {}", key)))?;
+    body[..end].split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_uint256)
+        .collect()
+}
+
+fn json_hex_value(json: &str, key: &str) -> io::Result<BigInt> {
+    let rest = json_field(json, key)?;
+    let end = rest.find([',', '\n', '}']).unwrap_or(rest.len());
+    parse_uint256(&rest[..end])
+}
+
+/// Import a proof written by `export_proof_json`
+/// - params: json text (keys scalars, ipp_L, ipp_R, ipp_a, ipp_b), n the modulus it was exported with
+/// - returns: Cuproof in EVM-reduced form, or InvalidData on a malformed document
+/// - usage: the JSON only stores values mod n with T1, T2, t_hat, tau_x recalculated, so the result
+///   is not the original prover output; check it with `cuproof_verify_reduced`, not `cuproof_verify`
+#[allow(non_snake_case)]
+pub fn import_proof_json(json: &str, n: &BigInt) -> io::Result<Cuproof> {
+    let scalars = json_hex_array(json, "scalars")?;
+    if scalars.len() != 15 {
+        return Err(invalid_json(&format!("expected 15 scalars, found {}", scalars.len())));
+    }
+    let L = json_hex_array(json, "ipp_L")?;
+    let R = json_hex_array(json, "ipp_R")?;
+    let a = json_hex_value(json, "ipp_a")?;
+    let b = json_hex_value(json, "ipp_b")?;
+    for x in scalars.iter().chain(&L).chain(&R).chain([&a, &b]) {
+        if x >= n { return Err(invalid_json("value not reduced mod n")); }
+    }
+
+    let mut s = scalars.into_iter();
+    let mut next = || s.next().unwrap();
+    Ok(Cuproof {
+        A: next(), S: next(), T1: next(), T2: next(), tau_x: next(),
+        mu: next(), t_hat: next(), C: next(), C_v1: next(), C_v2: next(),
+        t0: next(), t1: next(), t2: next(), tau1: next(), tau2: next(),
+        ipp_proof: IPPProof { L, R, a, b },
+    })
+}
+
+/// Operand size (bytes) of every value the 256-bit verifier exponentiates
+const EVM_WORD_BYTES: u64 = 32;
+/// Gas of the STATICCALL into the modexp precompile (warm address, EIP-2929)
+const MODEXP_CALL_GAS: u64 = 100;
+/// Calldata gas per non-zero byte (EIP-2028); uint256 scalars are assumed fully non-zero
+const CALLDATA_BYTE_GAS: u64 = 16;
+/// Fixed cost of a transaction
+const TX_BASE_GAS: u64 = 21_000;
+
+/// Gas of one modexp precompile call under EIP-2565
+/// - params: base/modulus length in bytes, exponent bit length (exponent no wider than 32 bytes)
+/// - returns: max(200, ceil(len / 8)^2 * max(exp_bits - 1, 1) / 3)
+fn modexp_gas(len_bytes: u64, exp_bits: u64) -> u64 {
+    let words = len_bytes.div_ceil(8);
+    let iterations = exp_bits.saturating_sub(1).max(1);
+    (words * words * iterations / 3).max(200)
+}
+
+/// Rough gas estimate for verifying `proof` on-chain with 256-bit operands
+///
+/// This is an estimate, not a measurement: it only counts the dominant costs, assuming each
+/// exponentiation is one modexp precompile call with a full 256-bit exponent:
+/// - 8 exponentiations for the T1, T2 and t_hat Pedersen checks
+/// - 2 per IPP level to fold P, 2 (dimension - 1) to fold the generator vectors, and 3 for
+///   the final g^a h^b u^(ab) check, where dimension = 2^levels
+/// - calldata for every uint256 scalar (15 fixed, 2 per IPP level, ipp_a and ipp_b)
+///
+/// Storage writes, events, hashing and the transaction's own overhead beyond the base fee are
+/// ignored, so compare estimates across dimensions rather than against a gas limit.
+///
+/// None if the IPP has too many levels for the estimate to fit a u64, e.g. a proof from
+/// `import_proof_json` with 64 or more L/R entries.
+pub fn estimate_evm_gas(proof: &Cuproof) -> Option<u64> {
+    let levels = u32::try_from(proof.ipp_depth()).ok()?;
+    let dimension = 1u64.checked_shl(levels)?;
+    let levels = u64::from(levels);
+    let exponentiations = (dimension - 1).checked_mul(2)?.checked_add(8 + 2 * levels + 3)?;
+    let scalars = 15 + 2 * levels + 2;
+    let per_exp = modexp_gas(EVM_WORD_BYTES, 256) + MODEXP_CALL_GAS;
+    exponentiations.checked_mul(per_exp)?.checked_add(TX_BASE_GAS + scalars * EVM_WORD_BYTES * CALLDATA_BYTE_GAS)
+}
+
+/// Save proof in EVM-compatible format to file
+pub fn save_proof_for_evm(path: &str, proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result<()> {
+    let content = serialize_proof_for_evm(proof, g, h, n);
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Save proof in JSON format for JavaScript integration
+pub fn save_proof_json(path: &str, proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result<()> {
+    let content = export_proof_json(proof, g, h, n);
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Reject moduli that do not fit in uint256 and proofs that do not verify,
+/// so an aggregated export is never silently truncated or invalid on-chain
+fn check_aggregated_exportable(proof: &AggregatedCuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result<()> {
+    if n.bits() > 256 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "modulus n >= 2^256 does not fit in uint256"));
+    }
+    if !cuproof_verify_aggregated(proof, g, h, n) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "aggregated proof does not verify"));
+    }
+    Ok(())
+}
+
+/// Shared scalars of an aggregated proof, reduced mod n with T1, T2, t_hat, tau_x
+/// recalculated the same way as for single proofs
+/// Order: [A, S, T1, T2, tau_x, mu, t_hat, t0, t1, t2, tau1, tau2]
+#[allow(non_snake_case)]
+fn aggregated_scalars(proof: &AggregatedCuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> Vec<BigInt> {
+    let t0_mod = &proof.t0 % n;
+    let t1_mod = &proof.t1 % n;
+    let tau1_mod = &proof.tau1 % n;
+    let t2_mod = &proof.t2 % n;
+    let tau2_mod = &proof.tau2 % n;
+
+    let T1_recalc = pedersen_commit(g, h, &t1_mod, &tau1_mod, n);
+    let T2_recalc = pedersen_commit(g, h, &t2_mod, &tau2_mod, n);
+    let x_recalc = fiat_shamir_uint256(&[&T1_recalc, &T2_recalc]) % n;
+    let t_hat_recalc = (&t0_mod + &(&t1_mod * &x_recalc) + &(&t2_mod * &x_recalc * &x_recalc)) % n;
+    let tau_x_recalc = (&tau2_mod * &x_recalc * &x_recalc + &tau1_mod * &x_recalc) % n;
+
+    vec![
+        proof.A.clone(), proof.S.clone(), T1_recalc, T2_recalc, tau_x_recalc,
+        proof.mu.clone(), t_hat_recalc, t0_mod, t1_mod, t2_mod, tau1_mod, tau2_mod,
+    ]
+}
+
+const AGGREGATED_SCALAR_NAMES: [&str; 12] = [
+    "A", "S", "T1", "T2", "tau_x", "mu", "t_hat", "t0", "t1", "t2", "tau1", "tau2",
+];
+
+/// Serialize an aggregated proof to EVM-compatible format
+/// Shared scalars are emitted once; per-value commitments C_i, C_v1_i, C_v2_i as uint256[]
+/// Fails if n >= 2^256 or if the proof does not verify
+pub fn serialize_aggregated_for_evm(proof: &AggregatedCuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result<String> {
+    check_aggregated_exportable(proof, g, h, n)?;
+    let scalars = aggregated_scalars(proof, g, h, n);
+
+    let mut output = String::new();
+    output.push_str("// Aggregated Cuproof for EVM (256-bit modulus)\n");
+    output.push_str("// Use this data with CuproofAggregatedVerifier256.sol\n\n");
+
+    output.push_str("// Shared scalars (12 values):\n");
+    output.push_str("// [A, S, T1, T2, tau_x, mu, t_hat, t0, t1, t2, tau1, tau2]\n");
+    output.push_str("uint256[12] memory scalars = [\n");
+    for (i, scalar) in scalars.iter().enumerate() {
+        output.push_str(&format!("    uint256(0x{})", bigint_to_uint256(scalar, n)));
+        if i < scalars.len() - 1 { output.push(','); }
+        output.push_str(&format!(" // {}\n", AGGREGATED_SCALAR_NAMES[i]));
+    }
+    output.push_str("];\n\n");
+
+    for (name, values) in [("C", proof.commitments()), ("C_v1", &proof.C_v1), ("C_v2", &proof.C_v2), ("ipp_L", &proof.ipp_proof.L), ("ipp_R", &proof.ipp_proof.R)] {
+        output.push_str(&format!("uint256[] memory {} = new uint256[]({});\n", name, values.len()));
+        for (i, val) in values.iter().enumerate() {
+            output.push_str(&format!("{}[{}] = uint256(0x{});\n", name, i, bigint_to_uint256(val, n)));
+        }
+        output.push('\n');
+    }
+
+    output.push_str("// IPP Proof scalars:\n");
+    output.push_str(&format!("uint256 ipp_a = uint256(0x{});\n", bigint_to_uint256(&proof.ipp_proof.a, n)));
+    output.push_str(&format!("uint256 ipp_b = uint256(0x{});\n", bigint_to_uint256(&proof.ipp_proof.b, n)));
+
+    Ok(output)
+}
+
+/// Export an aggregated proof to JSON for JavaScript/TypeScript integration
+/// Keys: scalars (12 shared values), C, C_v1, C_v2 (one entry per value), ipp_L, ipp_R, ipp_a, ipp_b
+/// Fails if n >= 2^256 or if the proof does not verify
+pub fn export_aggregated_json(proof: &AggregatedCuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result<String> {
+    check_aggregated_exportable(proof, g, h, n)?;
+    let scalars = aggregated_scalars(proof, g, h, n);
+
+    let mut json = String::new();
+    json.push_str("{\n");
+    for (name, values) in [("scalars", &scalars), ("C", &proof.C), ("C_v1", &proof.C_v1), ("C_v2", &proof.C_v2), ("ipp_L", &proof.ipp_proof.L), ("ipp_R", &proof.ipp_proof.R)] {
+        json.push_str(&format!("  \"{}\": [\n", name));
+        for (i, val) in values.iter().enumerate() {
+            json.push_str(&format!("    \"0x{}\"", bigint_to_uint256(val, n)));
+            if i < values.len() - 1 { json.push(','); }
+            json.push('\n');
+        }
+        json.push_str("  ],\n");
+    }
+    json.push_str(&format!("  \"ipp_a\": \"0x{}\",\n", bigint_to_uint256(&proof.ipp_proof.a, n)));
+    json.push_str(&format!("  \"ipp_b\": \"0x{}\"\n", bigint_to_uint256(&proof.ipp_proof.b, n)));
+    json.push_str("}\n");
+    Ok(json)
+}
+
+/// Save an aggregated proof in EVM-compatible format to file
+pub fn save_aggregated_for_evm(path: &str, proof: &AggregatedCuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result<()> {
+    let content = serialize_aggregated_for_evm(proof, g, h, n)?;
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Save an aggregated proof in JSON format for JavaScript integration
+pub fn save_aggregated_json(path: &str, proof: &AggregatedCuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result<()> {
+    let content = export_aggregated_json(proof, g, h, n)?;
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup::setup_256;
+    use crate::range_proof::{cuproof_prove, cuproof_prove_with_dimension};
+    use crate::aggregate::cuproof_prove_aggregated;
+    use crate::util::random_bigint;
+
+    // Purpose: the uint256 transcript is deterministic and depends on input order and value
+    // Params: small constant inputs
+    // Output: equality / inequality assertions on fiat_shamir_uint256
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn fs_uint256_deterministic_and_order_sensitive() {
+        let a = BigInt::from(123);
+        let b = BigInt::from(456);
+        let h1 = fiat_shamir_uint256(&[&a, &b]);
+        assert_eq!(h1, fiat_shamir_uint256(&[&a, &b]));
+        assert_ne!(h1, fiat_shamir_uint256(&[&b, &a]));
+        assert_ne!(h1, fiat_shamir_uint256(&[&a, &BigInt::from(457)]));
+    }
+
+    // Purpose: inputs wider than uint256 hash without panicking; uint256-sized ones match abi.encodePacked
+    // Params: a 300-bit input next to a small one; a 256-bit word with its top bit set
+    // Output: deterministic, value- and sign-sensitive digests; the packed encoding for uint256 words
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn fs_uint256_accepts_inputs_wider_than_uint256() {
+        let small = BigInt::from(123);
+        let wide = (BigInt::from(1) << 300) + 12345;
+        let h = fiat_shamir_uint256(&[&small, &wide]);
+        assert_eq!(h, fiat_shamir_uint256(&[&small, &wide]));
+        assert_ne!(h, fiat_shamir_uint256(&[&small, &(&wide + 1)]));
+        assert_ne!(h, fiat_shamir_uint256(&[&small, &-&wide]));
+        // Values that fit a uint256 keep the plain 32-byte encoding
+        let word = (BigInt::from(1) << 255) + 1;
+        let mut packed = [0u8; 64];
+        packed[31] = 123;
+        packed[32] = 0x80;
+        packed[63] = 1;
+        assert_eq!(fiat_shamir_uint256(&[&small, &word]), BigInt::from_bytes_be(num_bigint::Sign::Plus, &Keccak256::digest(packed)));
+    }
+
+    // Purpose: the single-proof exporters emit the scalars and IPP vectors the contract reads
+    // Params: setup_256 params, range [1, 100], v = 42
+    // Output: the Solidity and JSON outputs name scalars, ipp_L and ipp_R
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn test_serialize_proof() {
+        let (g, h, n) = setup_256();
+        let a = BigInt::from(1);
+        let b = BigInt::from(100);
+        let v = BigInt::from(42);
+        let r = random_bigint(128);
+        let proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
+
+        let evm_format = serialize_proof_for_evm(&proof, &g, &h, &n);
+        assert!(evm_format.contains("scalars"));
+        assert!(evm_format.contains("ipp_L"));
+        assert!(evm_format.contains("ipp_R"));
+
+        let json_format = export_proof_json(&proof, &g, &h, &n);
+        assert!(json_format.contains("\"scalars\""));
+        assert!(json_format.contains("\"ipp_L\""));
+    }
+
+    // Purpose: the gas estimate grows with the IPP dimension and uses the EIP-2565 modexp price
+    // Params: setup_256 params, range [1, 100], v = 42 at dimensions 16, 64, 256
+    // Output: strictly increasing estimates; modexp_gas(32, 256) = 1360, modexp_gas(32, 1) = 200
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn test_estimate_evm_gas_grows_with_dimension() {
+        let (g, h, n) = setup_256();
+        let a = BigInt::from(1);
+        let b = BigInt::from(100);
+        let v = BigInt::from(42);
+        let r = random_bigint(128);
+        let estimates: Vec<u64> = [16usize, 64, 256].iter()
+            .map(|&d| estimate_evm_gas(&cuproof_prove_with_dimension(&v, &r, &a, &b, &g, &h, &n, d)).unwrap())
+            .collect();
+        assert!(estimates.windows(2).all(|w| w[0] < w[1]), "{:?}", estimates);
+        // One full 256-bit modexp under EIP-2565: 4^2 * 255 / 3
+        assert_eq!(modexp_gas(32, 256), 1360);
+        assert_eq!(modexp_gas(32, 1), 200);
+    }
+
+    // Purpose: an IPP too deep for a u64 estimate gives no estimate, not an overflow
+    // Params: setup_256 params, a dimension-16 proof with L/R padded to 64 levels
+    // Output: estimate_evm_gas returns None
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn test_estimate_evm_gas_rejects_deep_ipp() {
+        let (g, h, n) = setup_256();
+        let mut proof = cuproof_prove_with_dimension(&BigInt::from(42), &random_bigint(128), &BigInt::from(1), &BigInt::from(100), &g, &h, &n, 16);
+        proof.ipp_proof.L.resize(64, BigInt::from(1));
+        proof.ipp_proof.R.resize(64, BigInt::from(1));
+        assert_eq!(estimate_evm_gas(&proof), None);
+    }
+
+    // Purpose: aggregated exports carry one commitment per value and refuse bad proofs or moduli
+    // Params: setup_256 params, range [1, 100], values 5, 42, 99
+    // Output: three C entries in JSON and Solidity; the named contract ships with the repo;
+    //         a tampered T1 and a modulus of 2^256 are rejected
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn test_export_aggregated_three_values() {
+        let (g, h, n) = setup_256();
+        let a = BigInt::from(1);
+        let b = BigInt::from(100);
+        let values = vec![BigInt::from(5), BigInt::from(42), BigInt::from(99)];
+        let blindings: Vec<BigInt> = (0..3).map(|_| random_bigint(128)).collect();
+        let proof = cuproof_prove_aggregated(&values, &blindings, &a, &b, &g, &h, &n);
+
+        let json = export_aggregated_json(&proof, &g, &h, &n).unwrap();
+        let c_section = json.split("\"C\": [").nth(1).unwrap().split(']').next().unwrap();
+        assert_eq!(c_section.matches("\"0x").count(), 3);
+        assert!(json.contains("\"C_v1\""));
+        assert!(json.contains("\"ipp_L\""));
+
+        let evm = serialize_aggregated_for_evm(&proof, &g, &h, &n).unwrap();
+        assert!(evm.contains("uint256[] memory C = new uint256[](3);"));
+        // The contract the output points at must ship with the repo
+        let contract = "CuproofAggregatedVerifier256.sol";
+        assert!(evm.contains(contract));
+        assert!(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("cuproof-blockchain/contracts").join(contract).is_file());
+
+        let mut bad = proof.clone();
+        bad.T1 = &bad.T1 + BigInt::from(1);
+        assert!(export_aggregated_json(&bad, &g, &h, &n).is_err());
+
+        let too_big = BigInt::from(1) << 256;
+        assert_eq!(export_aggregated_json(&proof, &g, &h, &too_big).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    // Purpose: a JSON export imports back to the EVM-reduced proof, which passes the contract's checks
+    // Params: setup_256 params, range [1, 100], v = 42
+    // Output: the imported proof verifies with cuproof_verify_reduced and re-exports to the same
+    //         document; malformed documents are rejected
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn test_import_proof_json_round_trip() {
+        let (g, h, n) = setup_256();
+        let a = BigInt::from(1);
+        let b = BigInt::from(100);
+        let v = BigInt::from(42);
+        let r = random_bigint(128);
+        let proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
+
+        let json = export_proof_json(&proof, &g, &h, &n);
+        let imported = import_proof_json(&json, &n).unwrap();
+        assert_eq!(imported.ipp_proof.L.len(), proof.ipp_proof.L.len());
+        assert_eq!(imported.C, &proof.C % &n);
+        assert_eq!(cuproof_verify_reduced(&imported, &g, &h, &n), Ok(()));
+        // Re-exporting the reduced form reproduces the same document
+        assert_eq!(export_proof_json(&imported, &g, &h, &n), json);
+
+        let truncated = json.replacen("\"ipp_b\"", "\"ipp_c\"", 1);
+        assert_eq!(import_proof_json(&truncated, &n).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let not_hex = json.replacen("0x", "0xzz", 1);
+        assert!(import_proof_json(&not_hex, &n).is_err());
+    }
+
+    // Purpose: cuproof_verify_reduced names the contract check a tampered reduced proof fails
+    // Params: setup_256 params, range [1, 100], v = 42, the evm_reduced form of its proof
+    // Output: Ok for the reduced proof; T1Mismatch and THatInconsistent after tampering; the
+    //         unreduced proof fails, since its x comes from the prover's transcript
+    // Usage: `cargo test -- evm::` or `cargo test`
+    #[test]
+    fn verify_reduced_reports_contract_checks() {
+        let (g, h, n) = setup_256();
+        let proof = cuproof_prove(&BigInt::from(42), &random_bigint(128), &BigInt::from(1), &BigInt::from(100), &g, &h, &n);
+        let reduced = evm_reduced(&proof, &g, &h, &n);
+        assert_eq!(cuproof_verify_reduced(&reduced, &g, &h, &n), Ok(()));
+
+        let mut bad = reduced.clone();
+        bad.T1 = (&bad.T1 + 1) % &n;
+        assert_eq!(cuproof_verify_reduced(&bad, &g, &h, &n), Err(VerifyError::T1Mismatch));
+        let mut bad = reduced.clone();
+        bad.t_hat = (&bad.t_hat + 1) % &n;
+        assert_eq!(cuproof_verify_reduced(&bad, &g, &h, &n), Err(VerifyError::THatInconsistent));
+        assert!(cuproof_verify_reduced(&proof, &g, &h, &n).is_err());
+    }
+}
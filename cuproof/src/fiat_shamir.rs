@@ -1,13 +1,145 @@
+use crate::setup::miller_rabin_deterministic;
 use num_bigint::BigInt;
+use num_traits::One;
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Hash backend for Fiat–Shamir challenges
+///
+/// A hasher starts empty (`Default`), absorbs the transcript bytes and squeezes a
+/// non-negative challenge. Prover and verifier must agree on the hasher, otherwise every
+/// challenge differs and verification fails.
+pub trait ChallengeHasher: Default {
+    fn absorb(&mut self, bytes: &[u8]);
+    fn squeeze(&self) -> BigInt;
+}
+
+/// SHA-256 backend, the default used by `fiat_shamir` unless the `keccak` feature is enabled
+#[derive(Clone, Default)]
+pub struct Sha256Hasher(Sha256);
+
+impl ChallengeHasher for Sha256Hasher {
+    fn absorb(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn squeeze(&self) -> BigInt {
+        BigInt::from_bytes_be(num_bigint::Sign::Plus, &self.0.clone().finalize())
+    }
+}
+
+/// Keccak-256 backend, matching the EVM `keccak256` opcode
+#[derive(Clone, Default)]
+pub struct Keccak256Hasher(Keccak256);
+
+impl ChallengeHasher for Keccak256Hasher {
+    fn absorb(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn squeeze(&self) -> BigInt {
+        BigInt::from_bytes_be(num_bigint::Sign::Plus, &self.0.clone().finalize())
+    }
+}
+
+/// Hasher of `fiat_shamir` and of every prover/verifier entry point without an explicit hasher:
+/// `Sha256Hasher`, or `Keccak256Hasher` with the `keccak` feature
+#[cfg(not(feature = "keccak"))]
+pub type DefaultChallengeHasher = Sha256Hasher;
+#[cfg(feature = "keccak")]
+pub type DefaultChallengeHasher = Keccak256Hasher;
 
 pub fn fiat_shamir(inputs: &[&BigInt]) -> BigInt {
-    let mut hasher = Sha256::new();
+    fiat_shamir_with::<DefaultChallengeHasher>(inputs)
+}
+
+/// Same transcript encoding as `fiat_shamir` (decimal strings), hashed with `H`
+pub fn fiat_shamir_with<H: ChallengeHasher>(inputs: &[&BigInt]) -> BigInt {
+    let mut hasher = H::default();
     for i in inputs {
-        hasher.update(i.to_str_radix(10).as_bytes());
+        hasher.absorb(i.to_str_radix(10).as_bytes());
+    }
+    hasher.squeeze()
+}
+
+/// Prime modulus Fiat–Shamir challenges are reduced by, in place of the composite RSA modulus n
+///
+/// 2^256 - 189, the largest 256-bit prime, whenever n exceeds it: a 256-bit digest then lands in
+/// [0, q) except with probability 2^-248, and every real setup shares one challenge space.
+/// For smaller (toy) moduli, the largest prime below n, so challenges stay below n. That prime
+/// is found with fixed Miller–Rabin witnesses, so prover and verifier always agree on it.
+/// The prime search runs once per modulus; later calls (every `RsaBackend::new`, so every
+/// 256-bit or keccak verify) are served from a process-wide cache.
+/// - params: n RSA modulus of the public parameters, n > 2
+/// - usage: `GroupBackend::challenge` reduces with it, so prover and verifier share it
+pub fn challenge_modulus(n: &BigInt) -> BigInt {
+    let q = (BigInt::one() << 256u32) - 189;
+    if n > &q { return q; }
+    if n <= &BigInt::from(3) { return BigInt::from(2); }
+    let cache = SMALL_CHALLENGE_MODULI.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(cached) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(n) { return cached.clone(); }
+    let mut candidate: BigInt = n - 1;
+    while !miller_rabin_deterministic(candidate.magnitude()) { candidate -= 1; }
+    cache.lock().unwrap_or_else(|e| e.into_inner()).insert(n.clone(), candidate.clone());
+    candidate
+}
+
+/// `challenge_modulus` results for moduli of at most 256 bits, keyed by n
+static SMALL_CHALLENGE_MODULI: OnceLock<Mutex<HashMap<BigInt, BigInt>>> = OnceLock::new();
+
+pub fn fiat_shamir_length_prefixed(inputs: &[&BigInt]) -> BigInt {
+    fiat_shamir_length_prefixed_with::<DefaultChallengeHasher>(inputs)
+}
+
+/// Length-prefixed transcript hashed with `H`: the input count, then each input as its byte
+/// length followed by its two's-complement big-endian bytes (count and lengths as u64 big-endian)
+///
+/// `fiat_shamir_with` concatenates decimal strings with no separator, so `[12, 3]` and `[1, 23]`
+/// both absorb "123"; with the prefixes two different input lists never share an encoding.
+/// The challenges differ from `fiat_shamir_with`, so prover and verifier must both opt in.
+pub fn fiat_shamir_length_prefixed_with<H: ChallengeHasher>(inputs: &[&BigInt]) -> BigInt {
+    let mut hasher = H::default();
+    hasher.absorb(&(inputs.len() as u64).to_be_bytes());
+    for i in inputs {
+        let bytes = i.to_signed_bytes_be();
+        hasher.absorb(&(bytes.len() as u64).to_be_bytes());
+        hasher.absorb(&bytes);
+    }
+    hasher.squeeze()
+}
+
+/// Incremental Fiat–Shamir transcript with the same encoding as `fiat_shamir_with`
+///
+/// Appending x_1..x_k and calling `challenge` gives `fiat_shamir_with::<H>(&[x_1, .., x_k])`.
+/// `fork` copies the state so branches (e.g. OR-proofs) can share a common prefix and then be
+/// extended independently.
+#[derive(Clone, Default)]
+pub struct Transcript<H: ChallengeHasher = DefaultChallengeHasher> {
+    hasher: H,
+}
+
+impl<H: ChallengeHasher> Transcript<H> {
+    pub fn new() -> Self {
+        Self { hasher: H::default() }
+    }
+
+    pub fn append(&mut self, x: &BigInt) {
+        self.hasher.absorb(x.to_str_radix(10).as_bytes());
+    }
+
+    /// Challenge over everything appended so far; the transcript can keep growing afterwards
+    pub fn challenge(&self) -> BigInt {
+        self.hasher.squeeze()
+    }
+}
+
+impl<H: ChallengeHasher + Clone> Transcript<H> {
+    /// Independent copy of the current state; appends to either side do not affect the other
+    pub fn fork(&self) -> Self {
+        self.clone()
     }
-    let hash = hasher.finalize();
-    BigInt::from_bytes_be(num_bigint::Sign::Plus, &hash)
 }
 
 #[cfg(test)]
@@ -34,4 +166,92 @@ mod tests {
         let h3 = fiat_shamir(&[&a, &c]);
         assert_ne!(h1, h3);
     }
-}
\ No newline at end of file
+
+    // Purpose: the hash backend is pluggable and actually changes the challenges
+    // Params: same BigInt inputs through SHA-256 and Keccak-256
+    // Output: the feature-selected hasher matches `fiat_shamir`, the two hashers differ
+    // Usage: `cargo test -- src::fiat_shamir` or `cargo test`
+    #[test]
+    fn fs_hashers_differ() {
+        let a = BigInt::from(123);
+        let b = BigInt::from(456);
+        let sha = fiat_shamir_with::<Sha256Hasher>(&[&a, &b]);
+        let keccak = fiat_shamir_with::<Keccak256Hasher>(&[&a, &b]);
+        assert_eq!(fiat_shamir_with::<DefaultChallengeHasher>(&[&a, &b]), fiat_shamir(&[&a, &b]));
+        assert_eq!(fiat_shamir(&[&a, &b]), if cfg!(feature = "keccak") { keccak.clone() } else { sha.clone() });
+        assert_ne!(sha, keccak);
+        assert_eq!(keccak, fiat_shamir_with::<Keccak256Hasher>(&[&a, &b]));
+    }
+
+    // Purpose: a forked transcript matches its parent on equal appends and diverges otherwise
+    // Params: common prefix (123, 456), branch appends 789 / 790
+    // Output: challenge equalities, and agreement with the one-shot `fiat_shamir`
+    // Usage: `cargo test -- src::fiat_shamir` or `cargo test`
+    #[test]
+    fn transcript_fork_branches() {
+        let (a, b) = (BigInt::from(123), BigInt::from(456));
+        let mut t = Transcript::<DefaultChallengeHasher>::new();
+        t.append(&a);
+        t.append(&b);
+        assert_eq!(t.challenge(), fiat_shamir(&[&a, &b]));
+
+        let mut left = t.fork();
+        let mut right = t.fork();
+        assert_eq!(left.challenge(), t.challenge());
+        t.append(&BigInt::from(789));
+        left.append(&BigInt::from(789));
+        assert_eq!(left.challenge(), t.challenge());
+        right.append(&BigInt::from(790));
+        assert_ne!(right.challenge(), t.challenge());
+        assert_eq!(right.challenge(), fiat_shamir(&[&a, &b, &BigInt::from(790)]));
+    }
+
+    // Purpose: challenges reduced by challenge_modulus lie in [0, challenge_modulus) and it is prime
+    // Params: a 512-bit fast setup modulus, a toy modulus 1009 * 1013, 32 transcripts each
+    // Output: 2^256 - 189 for the large modulus, the largest prime below the toy one; every
+    //         backend challenge falls in [0, challenge_modulus); the toy modulus is cached
+    // Usage: `cargo test -- src::fiat_shamir` or `cargo test`
+    #[test]
+    fn challenges_fall_below_challenge_modulus() {
+        use crate::backend::{GroupBackend, RsaBackend};
+        use crate::setup::{fast_test_setup, miller_rabin};
+        let (g, h, n) = fast_test_setup();
+        let q = challenge_modulus(&n);
+        assert_eq!(q, (BigInt::from(1) << 256u32) - 189);
+        assert!(miller_rabin(q.magnitude(), 16));
+
+        let toy = BigInt::from(1009 * 1013);
+        let q_toy = challenge_modulus(&toy);
+        assert_eq!(q_toy, BigInt::from(1_022_113));
+        assert_eq!(challenge_modulus(&BigInt::from(7919 + 1)), BigInt::from(7919));
+        // the second lookup of a small modulus comes from the cache, not a new prime search
+        assert!(SMALL_CHALLENGE_MODULI.get().unwrap().lock().unwrap().contains_key(&toy));
+        assert_eq!(challenge_modulus(&toy), q_toy);
+
+        for (n, q) in [(&n, &q), (&toy, &q_toy)] {
+            let backend = RsaBackend::<DefaultChallengeHasher>::new(&BigInt::from(2), &BigInt::from(3), n);
+            for i in 0..32 {
+                let ch = backend.challenge(&[&g, &h, &BigInt::from(i)]);
+                assert!(ch >= BigInt::from(0) && &ch < q);
+            }
+        }
+    }
+
+    // Purpose: length-prefixed absorb separates input groupings that the plain encoding merges
+    // Params: [12, 3] vs [1, 23] (both "123" in decimal), [123] vs [12, 3], [0] vs []
+    // Output: the plain challenges collide, the length-prefixed ones all differ
+    // Usage: `cargo test -- src::fiat_shamir` or `cargo test`
+    #[test]
+    fn fs_length_prefixed_separates_groupings() {
+        let n = BigInt::from;
+        assert_eq!(fiat_shamir(&[&n(12), &n(3)]), fiat_shamir(&[&n(1), &n(23)]));
+        assert_eq!(fiat_shamir(&[&n(12), &n(3)]), fiat_shamir(&[&n(123)]));
+
+        let lp = fiat_shamir_length_prefixed;
+        assert_ne!(lp(&[&n(12), &n(3)]), lp(&[&n(1), &n(23)]));
+        assert_ne!(lp(&[&n(12), &n(3)]), lp(&[&n(123)]));
+        assert_ne!(lp(&[&n(0)]), lp(&[]));
+        assert_eq!(lp(&[&n(12), &n(3)]), fiat_shamir_length_prefixed_with::<DefaultChallengeHasher>(&[&n(12), &n(3)]));
+        assert_ne!(fiat_shamir_length_prefixed_with::<Sha256Hasher>(&[&n(12), &n(3)]), fiat_shamir_length_prefixed_with::<Keccak256Hasher>(&[&n(12), &n(3)]));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{util::*, commitment::*, fiat_shamir::*};
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+
+/// Proof that C1 = Commit(v1, r1) and C2 = Commit(v2, r2) open to different values
+///
+/// With D = C1/C2 = g^d h^rho (d = v1 - v2, rho = r1 - r2) and a random multiplier t, the
+/// prover publishes C = g^(d t) = D^t h^(-rho t) and shows knowledge of (alpha, beta, gamma)
+/// with C = D^alpha h^beta and C = g^gamma:
+/// - T1 = D^(k_a) h^(k_b), T2 = g^(k_g) for random nonces
+/// - c = H(C1, C2, C, T1, T2)
+/// - s_a = k_a + c t, s_b = k_b - c rho t, s_g = k_g + c d t, over the integers
+///
+/// The verifier rejects C = 1. If d = 0 then D = h^rho, and a valid proof with C != 1 would
+/// give a relation g^gamma = h^(rho alpha + beta), which binding of the commitment rules out.
+/// The D^alpha check is done in the inverse-free form C1^(s_a) h^(s_b) = T1 C^c C2^(s_a).
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InequalityProof {
+    pub C: BigInt,
+    pub T1: BigInt,
+    pub T2: BigInt,
+    pub s_a: BigInt,
+    pub s_b: BigInt,
+    pub s_g: BigInt,
+}
+
+#[allow(non_snake_case)]
+fn inequality_challenge(C1: &BigInt, C2: &BigInt, C: &BigInt, T1: &BigInt, T2: &BigInt) -> BigInt {
+    fiat_shamir(&[C1, C2, C, T1, T2])
+}
+
+/// Prove that Commit(v1, r1) and Commit(v2, r2) commit to different values
+/// - params: v1/r1 and v2/r2 the two openings, g/h/n public parameters
+/// - returns: InequalityProof (the commitments are recomputed by the verifier's caller)
+/// - usage: equal values still yield a proof, but one with C = 1 that `verify_inequality` rejects
+#[allow(non_snake_case)]
+pub fn prove_inequality(v1: &BigInt, r1: &BigInt, v2: &BigInt, r2: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> InequalityProof {
+    let C1 = pedersen_commit(g, h, v1, r1, n);
+    let C2 = pedersen_commit(g, h, v2, r2, n);
+    let D = C1.clone() * mod_inverse(&C2, n).expect("C2 is a commitment, so invertible modulo n") % n;
+    let d = v1 - v2;
+    let rho = r1 - r2;
+    // t must exceed the group order by the slack so that g^(d t) hides d
+    let t = random_bigint((n.bits() + SCHNORR_SLACK_BITS) as usize) + BigInt::one();
+    let (alpha, beta, gamma) = (t.clone(), -(&rho * &t), &d * &t);
+    let C = mod_exp(g, &gamma, n);
+    let ((T1, T2), s) = schnorr_responses(&[alpha, beta, gamma],
+        |k| (mod_exp(&D, &k[0], n) * mod_exp(h, &k[1], n) % n, mod_exp(g, &k[2], n)),
+        |(T1, T2)| inequality_challenge(&C1, &C2, &C, T1, T2));
+    let [s_a, s_b, s_g]: [BigInt; 3] = s.try_into().expect("one response per secret");
+    InequalityProof { C, T1, T2, s_a, s_b, s_g }
+}
+
+/// Verify an InequalityProof for commitments c1 and c2
+/// - params: c1, c2 commitments, proof, g/h/n public parameters
+/// - returns: true if c1 and c2 open to different values
+/// - usage: pair with `prove_inequality`; complements `verify_equality`
+#[allow(non_snake_case)]
+pub fn verify_inequality(c1: &BigInt, c2: &BigInt, proof: &InequalityProof, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    if proof.s_a.is_negative() || proof.s_b.is_negative() || proof.s_g.is_negative() { return false; }
+    let C = &proof.C % n;
+    if C.is_zero() || C.is_one() { return false; }
+    if (&proof.T1 % n).is_zero() || (&proof.T2 % n).is_zero() { return false; }
+    let c = inequality_challenge(c1, c2, &proof.C, &proof.T1, &proof.T2);
+    let C_c = mod_exp(&C, &c, n);
+
+    let lhs1 = mod_exp(c1, &proof.s_a, n) * mod_exp(h, &proof.s_b, n) % n;
+    let rhs1 = &proof.T1 * &C_c % n * mod_exp(c2, &proof.s_a, n) % n;
+    let lhs2 = mod_exp(g, &proof.s_g, n);
+    let rhs2 = &proof.T2 * &C_c % n;
+    ct_eq(&lhs1, &rhs1, n) & ct_eq(&lhs2, &rhs2, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup::fast_test_setup;
+
+    // Purpose: distinct committed values verify, identical values or tampered proofs do not
+    // Params: fast_test_setup params, random 256-bit blindings
+    // Output: boolean assertions on verify_inequality
+    // Usage: `cargo test -- src::inequality` or `cargo test`
+    #[test]
+    fn inequality_pass_and_fail() {
+        let (g, h, n) = fast_test_setup();
+        let r1 = random_bigint(256);
+        let r2 = random_bigint(256);
+
+        // Both orderings, so d = v1 - v2 is exercised with either sign
+        for (v1, v2) in [(42, 43), (1000, 7)] {
+            let (v1, v2) = (BigInt::from(v1), BigInt::from(v2));
+            let c1 = pedersen_commit(&g, &h, &v1, &r1, &n);
+            let c2 = pedersen_commit(&g, &h, &v2, &r2, &n);
+            let proof = prove_inequality(&v1, &r1, &v2, &r2, &g, &h, &n);
+            assert!(verify_inequality(&c1, &c2, &proof, &g, &h, &n));
+
+            // The proof is bound to its commitments
+            let c2_other = pedersen_commit(&g, &h, &v2, &(&r2 + 1), &n);
+            assert!(!verify_inequality(&c1, &c2_other, &proof, &g, &h, &n));
+
+            let mut bad = proof.clone();
+            bad.s_g += 1;
+            assert!(!verify_inequality(&c1, &c2, &bad, &g, &h, &n));
+        }
+
+        // Identical values: the honest prover's C is 1 and the proof is rejected
+        let v = BigInt::from(42);
+        let c1 = pedersen_commit(&g, &h, &v, &r1, &n);
+        let c2 = pedersen_commit(&g, &h, &v, &r2, &n);
+        let proof = prove_inequality(&v, &r1, &v, &r2, &g, &h, &n);
+        assert!(proof.C.is_one());
+        assert!(!verify_inequality(&c1, &c2, &proof, &g, &h, &n));
+    }
+}
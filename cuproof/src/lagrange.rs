@@ -1,85 +1,228 @@
-use num_bigint::{BigInt, ToBigInt};
-use num_traits::{One, ToPrimitive};
+use num_bigint::{BigInt, RandBigInt, ToBigInt};
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use rand::rngs::OsRng;
+use crate::setup::{miller_rabin, miller_rabin_deterministic, MILLER_RABIN_ROUNDS};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Why `find_3_squares` has no witness for its input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SquaresError {
+	/// The input is negative (for v1/v2 this means v lies outside [a, b])
+	Negative,
+	/// The input has the form 4^k (8m + 7), which is never a sum of three squares (Legendre)
+	FourSquaresRequired,
+}
+
+impl fmt::Display for SquaresError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+impl std::error::Error for SquaresError {}
+
+/// True if n = 4^k (8m + 7), i.e. n needs four squares
+pub fn needs_four_squares(n: &BigInt) -> bool {
+	if n.is_zero() { return false; }
+	let mut m = n.clone();
+	let four = BigInt::from(4u32);
+	while (&m % &four).is_zero() {
+		m /= &four;
+	}
+	&m % BigInt::from(8u32) == BigInt::from(7u32)
+}
+
+/// Largest input the 3/4-squares helpers brute-force; above it they switch to big-integer methods
+const BRUTE_FORCE_LIMIT: u64 = 1_000_000;
 
 pub fn find_4_squares(n: &BigInt) -> Vec<BigInt> {
-	let n_u = n.to_u64().unwrap_or(0);
-	for a in 0..=n_u {
-		for b in 0..=a {
-			for c in 0..=b {
-				let rem = n_u - a*a - b*b - c*c;
-				let d = (rem as f64).sqrt().floor() as u64;
-				if a*a + b*b + c*c + d*d == n_u {
-					return vec![a, b, c, d].into_iter().map(|x| x.to_bigint().unwrap()).collect();
+	if n.is_negative() { panic!("Cannot find 4 squares for {}", n); }
+	if let Some(n_u) = n.to_u64().filter(|&n_u| n_u <= BRUTE_FORCE_LIMIT) {
+		for a in 0..=n_u {
+			if a*a > n_u { break; }
+			for b in 0..=a {
+				let ab = a*a + b*b;
+				if ab > n_u { break; }
+				for c in 0..=b {
+					let abc = ab + c*c;
+					if abc > n_u { break; }
+					let d = ((n_u - abc) as f64).sqrt().floor() as u64;
+					if abc + d*d == n_u {
+						return vec![a, b, c, d].into_iter().map(|x| x.to_bigint().unwrap()).collect();
+					}
 				}
 			}
 		}
 	}
-	panic!("Cannot find 4 squares for {}", n);
+	four_squares_big(n)
 }
 
-pub fn find_3_squares(n: &BigInt) -> Vec<BigInt> {
-	// For large numbers, use a simplified approach
-	// Since we're dealing with numbers of form 4x+1, we can use known patterns
-	
-	// Try to convert to u64 first for small numbers
-	if let Some(n_u) = n.to_u64() {
-		if n_u <= 1000000 { // Limit for brute force
-			for a in 0..=n_u {
-				for b in 0..=a {
-					let ab = a*a + b*b;
-					if ab > n_u { break; }
-					let rem = n_u - ab;
-					let c = (rem as f64).sqrt().floor() as u64;
-					if a*a + b*b + c*c == n_u {
-						return vec![a, b, c].into_iter().map(|x| x.to_bigint().unwrap()).collect();
-					}
-				}
+/// Rabin–Shallit for inputs past the brute-force limit: strip factors of 4, then draw random a, b
+/// until p = n - a^2 - b^2 is a prime p ≡ 1 (mod 4) and split p with `two_squares_prime`
+fn four_squares_big(n: &BigInt) -> Vec<BigInt> {
+	let four = BigInt::from(4u32);
+	if (n % &four).is_zero() {
+		return find_4_squares(&(n / &four)).into_iter().map(|x| x * 2).collect();
+	}
+	let (zero, one) = (BigInt::zero(), BigInt::one());
+	let mut rng = OsRng;
+	loop {
+		let a = rng.gen_bigint_range(&zero, &(n.sqrt() + &one));
+		let rest = n - &a * &a;
+		let b = rng.gen_bigint_range(&zero, &(rest.sqrt() + &one));
+		let p = rest - &b * &b;
+		if &p % &four != one { continue; }
+		if !miller_rabin(p.magnitude(), MILLER_RABIN_ROUNDS) { continue; }
+		if let Some((c, d)) = two_squares_prime(&p) {
+			return vec![a, b, c, d];
+		}
+	}
+}
+
+/// Write a prime p ≡ 1 (mod 4) as c^2 + d^2 (Hermite–Serret): take x with x^2 ≡ -1 (mod p) and run
+/// Euclid on (p, x) until the remainder drops to sqrt(p) or below
+fn two_squares_prime(p: &BigInt) -> Option<(BigInt, BigInt)> {
+	let p_minus_one = p - BigInt::one();
+	let exp = &p_minus_one >> 2;
+	// q^((p-1)/4) is a square root of -1 for any non-residue q; a small one always exists
+	let x = (2u32..1000).map(|q| BigInt::from(q).modpow(&exp, p)).find(|x| x * x % p == p_minus_one)?;
+	let root = p.sqrt();
+	let (mut r0, mut r1) = (p.clone(), x);
+	while r1 > root {
+		let r2 = &r0 % &r1;
+		r0 = r1;
+		r1 = r2;
+	}
+	let d_sq = p - &r1 * &r1;
+	let d = d_sq.sqrt();
+	(&d * &d == d_sq).then_some((r1, d))
+}
+
+pub fn find_3_squares(n: &BigInt) -> Result<Vec<BigInt>, SquaresError> {
+	if n.is_negative() { return Err(SquaresError::Negative); }
+	if needs_four_squares(n) { return Err(SquaresError::FourSquaresRequired); }
+
+	let witness = match n.to_u64().filter(|&n_u| n_u <= BRUTE_FORCE_LIMIT) {
+		Some(n_u) => three_squares_small(n_u),
+		None => three_squares_big(n),
+	};
+	assert_eq!(witness.iter().map(|x| x * x).sum::<BigInt>(), *n, "three-square witness must sum to n");
+	Ok(witness)
+}
+
+/// First brute-force hit a >= b, c for an unobstructed n up to the brute-force limit
+fn three_squares_small(n_u: u64) -> Vec<BigInt> {
+	for a in 0..=n_u {
+		for b in 0..=a {
+			let ab = a*a + b*b;
+			if ab > n_u { break; }
+			let c = (n_u - ab).isqrt();
+			if ab + c*c == n_u {
+				return vec![a, b, c].into_iter().map(|x| x.to_bigint().unwrap()).collect();
 			}
 		}
 	}
-	
-	// For large numbers, use a heuristic approach
-	// Since n = 4x + 1, we can try some common patterns
-	let one = BigInt::one();
-	let two = BigInt::from(2u32);
-	let four = BigInt::from(4u32);
-	
-	// Try n = (2^k)^2 + (2^(k-1))^2 + 1^2 for some k
-	let mut k = 1u32;
-	while k <= 32 {
-		let term1 = &two.pow(k);
-		let term2 = &two.pow(k-1);
-		let sum = term1 * term1 + term2 * term2 + &one;
-		
-		if &sum == n {
-			return vec![term1.clone(), term2.clone(), one.clone()];
+	unreachable!("{} is not of the form 4^k (8m + 7), so it is a sum of three squares", n_u)
+}
+
+/// Inputs past the brute-force limit: strip factors of 4, then walk a down from sqrt(n) until
+/// n - a^2 is a prime p ≡ 1 (mod 4) (n ≡ 1, 2 mod 4) or twice one (n ≡ 3 mod 8), and split p with
+/// `two_squares_prime`. The primality test uses fixed bases, so the witness is reproducible.
+fn three_squares_big(n: &BigInt) -> Vec<BigInt> {
+	let (one, two, four) = (BigInt::one(), BigInt::from(2u32), BigInt::from(4u32));
+	if (n % &four).is_zero() {
+		return three_squares_big(&(n / &four)).into_iter().map(|x| x * 2).collect();
+	}
+	// n - a^2 must be 1 (mod 4), or 2 (mod 8) when n ≡ 3 (mod 8): a is even only for n ≡ 1 (mod 4)
+	let halve = n % BigInt::from(8u32) == BigInt::from(3u32);
+	let a_parity = if n % &four == one { BigInt::zero() } else { one.clone() };
+	let mut a = n.sqrt();
+	if &a % &two != a_parity { a -= &one; }
+	while !a.is_negative() {
+		let rest = n - &a * &a;
+		let p = if halve { &rest / &two } else { rest };
+		if &p % &four == one && miller_rabin_deterministic(p.magnitude())
+			&& let Some((c, d)) = two_squares_prime(&p) {
+			// 2(c^2 + d^2) = (c + d)^2 + (c - d)^2
+			return if halve { vec![a, &c + &d, (c - d).abs()] } else { vec![a, c, d] };
 		}
-		
-		if &sum > n {
-			break;
+		a -= &two;
+	}
+	unreachable!("no prime n - a^2 found for {}", n)
+}
+
+/// Same as `find_3_squares`, preferring the witness with the smallest largest component
+///
+/// `find_3_squares` returns the first brute-force hit, whose last component can be close to
+/// sqrt(n) (29 = 2^2 + 0^2 + 5^2). Here every largest component m from ceil(sqrt(n / 3)) up is
+/// tried and the first m admitting b, c <= m is returned (29 = 4^2 + 3^2 + 2^2). The sum of
+/// squares is n either way, so only the size of the largest witness entry changes.
+/// Inputs above the brute-force limit fall back to `find_3_squares`.
+pub fn find_3_squares_balanced(n: &BigInt) -> Result<Vec<BigInt>, SquaresError> {
+	if n.is_negative() { return Err(SquaresError::Negative); }
+	if needs_four_squares(n) { return Err(SquaresError::FourSquaresRequired); }
+	let Some(n_u) = n.to_u64().filter(|&n_u| n_u <= BRUTE_FORCE_LIMIT) else { return find_3_squares(n); };
+
+	let mut m = n_u.div_ceil(3).isqrt();
+	while 3 * m * m < n_u { m += 1; }
+	while m * m <= n_u {
+		let rest = n_u - m * m;
+		for b in (0..=m).rev() {
+			if b * b > rest { continue; }
+			let c_sq = rest - b * b;
+			if c_sq > b * b { break; }
+			let c = c_sq.isqrt();
+			if c * c == c_sq {
+				return Ok([m, b, c].into_iter().map(BigInt::from).collect());
+			}
+		}
+		m += 1;
+	}
+	// Unreachable for inputs that are sums of three squares
+	find_3_squares(n)
+}
+
+/// Memoized `find_3_squares_balanced`, keyed by the input value
+///
+/// v1 = 4(v - a) + 1 and v2 = 4(b - v) + 1 depend on v, so a fixed [a, b] alone gives nothing
+/// to reuse; hits come from the same v1 or v2 recurring, e.g. re-proving a batch of values.
+/// Errors are cached too, so a rejected input is not searched again.
+#[derive(Clone, Debug, Default)]
+pub struct DecompositionCache {
+	entries: HashMap<BigInt, Result<Vec<BigInt>, SquaresError>>,
+	misses: usize,
+}
+
+impl DecompositionCache {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Three-square witness of n, computed on the first request and cloned from the cache after
+	pub fn get(&mut self, n: &BigInt) -> Result<Vec<BigInt>, SquaresError> {
+		if let Some(cached) = self.entries.get(n) {
+			return cached.clone();
 		}
-		k += 1;
+		self.misses += 1;
+		let result = find_3_squares_balanced(n);
+		self.entries.insert(n.clone(), result.clone());
+		result
 	}
-	
-	// Fallback: use a simple decomposition
-	// For demo purposes, we'll use a basic pattern
-	let sqrt_n = n.sqrt();
-	let a = &sqrt_n / &two;
-	let b = &sqrt_n / &four;
-	let c = &one;
-	
-	// Ensure a^2 + b^2 + c^2 <= n
-	let a_sq = a.clone() * a.clone();
-	let b_sq = b.clone() * b.clone();
-	let c_sq = c.clone() * c.clone();
-	let sum_squares = a_sq + b_sq + c_sq;
-	
-	if sum_squares <= *n {
-		return vec![a.clone(), b.clone(), c.clone()];
+
+	/// Number of `find_3_squares_balanced` calls made so far, one per distinct input
+	pub fn misses(&self) -> usize {
+		self.misses
+	}
+
+	/// Number of distinct inputs cached
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
 	}
-	
-	// Last resort: use small values
-	vec![BigInt::from(1u32), BigInt::from(1u32), BigInt::from(1u32)]
 }
 
 #[cfg(test)]
@@ -100,9 +243,113 @@ mod tests {
         assert_eq!(sum4, 30u128);
 
         // 3-squares heuristic should return 3 components for 4k+1 (e.g., 29 = 4*7+1)
-        let three = find_3_squares(&BigInt::from(29));
+        let three = find_3_squares(&BigInt::from(29)).unwrap();
         assert_eq!(three.len(), 3);
         let sum3: u128 = three.iter().map(|x| x.to_u128().unwrap()).map(|x| x*x).sum();
         assert_eq!(sum3, 29u128);
     }
-}
\ No newline at end of file
+
+    // Purpose: inputs above 2^64 get a genuine 4-squares witness instead of the old [0, 0, 0, 0]
+    // Params: 2^64 + 3, 2^70 + 12345, 16 * (2^66 + 7) (exercises the factor-of-4 path), a 256-bit value
+    // Output: four non-negative components whose squares sum to the input
+    // Usage: `cargo test -- src::lagrange` or `cargo test`
+    #[test]
+    fn four_squares_above_u64() {
+        let big = |e: u32| BigInt::one() << e;
+        let inputs = [big(64) + 3, big(70) + 12345, (big(66) + 7) * 16, big(255) + 987654321];
+        for n in inputs {
+            let four = find_4_squares(&n);
+            assert_eq!(four.len(), 4);
+            assert!(four.iter().all(|x| !x.is_negative()));
+            assert_eq!(four.iter().map(|x| x * x).sum::<BigInt>(), n);
+        }
+    }
+
+    // Purpose: inputs of the form 4^k(8m+7) are reported instead of getting a wrong witness
+    // Params: 7 = 8*0+7, 28 = 4*7, 29 as a control, -3 as a negative input
+    // Output: FourSquaresRequired for 7 and 28 (which do have 4-square witnesses), Ok for 29
+    // Usage: `cargo test -- src::lagrange` or `cargo test`
+    #[test]
+    fn three_square_obstruction_is_detected() {
+        for n in [7u128, 28] {
+            let big = BigInt::from(n);
+            assert!(needs_four_squares(&big));
+            assert_eq!(find_3_squares(&big), Err(SquaresError::FourSquaresRequired));
+            let sum4: u128 = find_4_squares(&big).iter().map(|x| x.to_u128().unwrap()).map(|x| x*x).sum();
+            assert_eq!(sum4, n);
+        }
+        assert!(!needs_four_squares(&BigInt::from(29)));
+        assert!(find_3_squares(&BigInt::from(29)).is_ok());
+        assert_eq!(find_3_squares(&BigInt::from(-3)), Err(SquaresError::Negative));
+    }
+
+    // Purpose: the balanced witness is valid and never has a larger component than the first hit
+    // Params: every n = 4k + 1 up to 20,001, 29 as a worked example, obstructed and negative inputs
+    // Output: squares sum to n; max component <= that of find_3_squares, and minimal (checked
+    //         against the lower bound ceil(sqrt(n / 3))) for 29; same errors as find_3_squares
+    // Usage: `cargo test -- src::lagrange` or `cargo test`
+    #[test]
+    fn balanced_three_squares_minimize_largest_component() {
+        let max_component = |d: &[BigInt]| d.iter().max().unwrap().clone();
+        let mut strictly_smaller = 0;
+        for k in 0..=5_000u64 {
+            let n = BigInt::from(4 * k + 1);
+            let balanced = find_3_squares_balanced(&n).unwrap();
+            let first = find_3_squares(&n).unwrap();
+            assert_eq!(balanced.iter().map(|x| x * x).sum::<BigInt>(), n);
+            assert!(balanced.iter().all(|x| !x.is_negative()));
+            assert!(max_component(&balanced) <= max_component(&first));
+            if max_component(&balanced) < max_component(&first) { strictly_smaller += 1; }
+        }
+        assert!(strictly_smaller > 0);
+
+        assert_eq!(find_3_squares(&BigInt::from(29)).unwrap(), [2, 0, 5].map(BigInt::from));
+        assert_eq!(find_3_squares_balanced(&BigInt::from(29)).unwrap(), [4, 3, 2].map(BigInt::from));
+        assert_eq!(find_3_squares_balanced(&BigInt::from(28)), Err(SquaresError::FourSquaresRequired));
+        assert_eq!(find_3_squares_balanced(&BigInt::from(-3)), Err(SquaresError::Negative));
+    }
+
+    // Purpose: inputs past the brute-force limit get a genuine witness instead of a pattern guess
+    // Params: 4,000,001 and 4 (2^32 - 1) + 1 (v1 of prove_bits for k = 32), one input per
+    //         residue class mod 8 that has three squares, 16 (2^70 + 1) for the factor-of-4 path,
+    //         2^64 + 7 (obstructed, 7 mod 8) and a 256-bit value
+    // Output: three non-negative components whose squares sum to the input, also through
+    //         find_3_squares_balanced; FourSquaresRequired for the obstructed input
+    // Usage: `cargo test -- src::lagrange` or `cargo test`
+    #[test]
+    fn three_squares_above_brute_force_limit() {
+        let big = |e: u32| BigInt::one() << e;
+        let mut inputs = vec![BigInt::from(4_000_001u64), BigInt::from(4 * (u32::MAX as u64) + 1), (big(70) + 1) * 16, big(255) + 987654321];
+        inputs.extend([1u32, 2, 3, 5, 6].map(|r| BigInt::from(8 * 1_000_003u64 + r as u64)));
+        for n in inputs {
+            for three in [find_3_squares(&n).unwrap(), find_3_squares_balanced(&n).unwrap()] {
+                assert_eq!(three.len(), 3);
+                assert!(three.iter().all(|x| !x.is_negative()));
+                assert_eq!(three.iter().map(|x| x * x).sum::<BigInt>(), n);
+            }
+        }
+        assert_eq!(find_3_squares(&(big(64) + 7)), Err(SquaresError::FourSquaresRequired));
+    }
+
+    // Purpose: a cache hit returns the same decomposition without calling the solver again
+    // Params: v1 = 169 for v = 42 in [1, 100], requested three times; 7 (obstructed) twice
+    // Output: identical witnesses, one solver call per distinct input, the error cached as well
+    // Usage: `cargo test -- src::lagrange` or `cargo test`
+    #[test]
+    fn decomposition_cache_hits_skip_the_solver() {
+        let mut cache = DecompositionCache::new();
+        let v1 = BigInt::from(169);
+        let first = cache.get(&v1).unwrap();
+        assert_eq!(first, find_3_squares_balanced(&v1).unwrap());
+        assert_eq!(cache.misses(), 1);
+        for _ in 0..2 {
+            assert_eq!(cache.get(&v1).unwrap(), first);
+        }
+        assert_eq!(cache.misses(), 1);
+
+        for _ in 0..2 {
+            assert_eq!(cache.get(&BigInt::from(7)), Err(SquaresError::FourSquaresRequired));
+        }
+        assert_eq!((cache.misses(), cache.len()), (2, 2));
+    }
+}
@@ -6,11 +6,25 @@ pub mod range_proof;
 pub mod verify;
 pub mod util;
 pub mod benchmark;
+pub mod equality;
+pub mod inequality;
+pub mod backend;
+pub mod sum;
+pub mod bound;
+pub mod aggregate;
+pub mod evm;
+#[cfg(feature = "track-memory")]
+pub mod memory;
+#[cfg(feature = "alloc-baseline")]
+pub mod baseline;
+
+// Structural and range verification, see `verify` for how they relate
+pub use verify::{cuproof_verify, cuproof_verify_with_range};
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use num_bigint::ToBigInt;
+    use num_bigint::{BigInt, ToBigInt};
     use std::time::Instant;
 
     #[test]
@@ -123,4 +137,43 @@ mod tests {
         println!("  Average proof generation time: {:?}", total_prove_time / test_ranges_len as u32);
         println!("  Average proof verification time: {:?}", total_verify_time / test_ranges_len as u32);
     }
+
+    // Purpose: both verifiers are reachable from the crate root and called independently
+    // Params: fast_test_setup params, v = 42 in [1, 100]
+    // Output: structural check passes for any range; the range check only for [1, 100]
+    // Usage: `cargo test -- src::tests` or `cargo test`
+    #[test]
+    fn test_verify_entry_points_are_separate() {
+        let (g, h, n) = setup::fast_test_setup();
+        let (a, b) = (BigInt::from(1), BigInt::from(100));
+        let proof = range_proof::cuproof_prove(&BigInt::from(42), &BigInt::from(7), &a, &b, &g, &h, &n);
+        assert!(crate::cuproof_verify(&proof, &g, &h, &n));
+        assert!(crate::cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b));
+        assert!(!crate::cuproof_verify_with_range(&proof, &g, &h, &n, &a, &BigInt::from(50)));
+    }
+
+    // Purpose: the `sha256` / `keccak` feature picks the hasher and setup of the default entry points
+    // Params: default_setup params, v = 42 in [1, 100]
+    // Output: proof from `cuproof_prove` verifies with `cuproof_verify`; fiat_shamir matches the
+    //         feature's hasher; the modulus is 256-bit under `keccak`, 2048-bit otherwise
+    // Usage: `cargo test -- src::tests` and `cargo test --features keccak -- src::tests`
+    #[test]
+    fn test_feature_selects_hasher_and_setup() {
+        use fiat_shamir::{fiat_shamir, fiat_shamir_with, Keccak256Hasher, Sha256Hasher};
+        let (g, h, n) = setup::default_setup();
+        assert!(setup::validate_params(&g, &h, &n).is_ok());
+        let expected_bits = if cfg!(feature = "keccak") { 256 } else { 2048 };
+        assert!(n.bits() >= expected_bits - 1 && n.bits() <= expected_bits);
+
+        let challenge = fiat_shamir(&[&g, &h]);
+        if cfg!(feature = "keccak") {
+            assert_eq!(challenge, fiat_shamir_with::<Keccak256Hasher>(&[&g, &h]));
+        } else {
+            assert_eq!(challenge, fiat_shamir_with::<Sha256Hasher>(&[&g, &h]));
+        }
+
+        let (a, b) = (BigInt::from(1), BigInt::from(100));
+        let proof = range_proof::cuproof_prove(&BigInt::from(42), &BigInt::from(7), &a, &b, &g, &h, &n);
+        assert!(crate::cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b));
+    }
 }
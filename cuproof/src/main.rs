@@ -1,31 +1,57 @@
 use std::env;
+use std::time::Instant;
 use num_bigint::BigInt;
-use cuproof::setup::{trusted_setup, fast_test_setup};
-use cuproof::range_proof::{cuproof_prove};
-use cuproof::verify::{cuproof_verify, cuproof_verify_with_range};
-use cuproof::util::{save_params, load_params, save_proof, load_proof, hex_to_bigint};
-use cuproof::benchmark::{benchmark_multiple_ranges, print_benchmark_summary};
+use cuproof::setup::{trusted_setup, trusted_setup_safe, fast_test_setup, trusted_setup_derived_h, fast_test_setup_derived_h, setup_from_seed};
+use cuproof::range_proof::{cuproof_prove_checked, cuproof_prove_with_dimension, cuproof_prove_with_rng, proof_size_bytes, ProofBuilder, ProveError, DEFAULT_DIMENSION};
+use cuproof::verify::cuproof_verify_with_range_and_dimension;
+use cuproof::util::{save_params, load_params, save_proof, load_proof, load_proof_legacy, hex_to_bigint, try_hex_to_bigint, bigint_to_hex, proof_to_base64};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use sha2::{Digest, Sha256};
+use cuproof::benchmark::{BenchmarkResult, benchmark_multiple_ranges, benchmark_multiple_ranges_streaming, print_benchmark_result, print_benchmark_summary};
+use std::io::{self, Write};
+use std::path::Path;
+
+// `benchmark --track-memory` reads the peaks this allocator records
+#[cfg(feature = "track-memory")]
+#[global_allocator]
+static GLOBAL: cuproof::memory::TrackingAllocator = cuproof::memory::TrackingAllocator;
 
 /// CLI entry: supports commands
-/// - setup [fast|trusted] <params_path>
-/// - prove <params_path> <a> <b> <v> <proof_path>
-/// - verify <params_path> <proof_path>
-/// - benchmark [fast|trusted] [range_lengths...]
+/// - setup [fast|trusted|trusted-safe|fast-derived|trusted-derived] <params_path>
+/// - prove <params_path> <a> <b> <v> <proof_path> [--blinding <hex>] [--dimension <n>] [--base64]
+///   (`--value-stdin` drops <v> and reads it from stdin; `--blinding-env <VAR>` reads the blinding
+///   from an environment variable; either keeps the secret out of argv and shell history)
+/// - prove-batch <params_path> <input_csv> <out_dir>
+/// - verify <params_path> <a> <b> <proof_path> [--dimension <n>] (default `DEFAULT_DIMENSION`;
+///   must match the dimension the proof was made with)
+/// - inspect <proof_path>
+/// - benchmark [fast|trusted] [range_lengths...] [--csv <path>] [--track-memory]
+/// - gen-vectors <out_dir>
+/// - global `--json`: prove/verify print a single JSON object
 fn main() {
+    // Global `--json`: machine-readable output for prove/verify; removed before positional parsing
     let args: Vec<String> = env::args().collect();
+    let json = args.iter().any(|s| s == "--json");
+    let args: Vec<String> = args.into_iter().filter(|s| s != "--json").collect();
     if args.len() < 2 {
-        eprintln!("Usage:\n  setup [fast|trusted] <params_path>\n  prove <params_path> <a_hex> <b_hex> <v_hex> <proof_path>\n  verify <params_path> <a_hex> <b_hex> <proof_path>\n  benchmark [fast|trusted] [range_lengths...]");
+        eprintln!("Usage:\n  setup [fast|trusted|trusted-safe|fast-derived|trusted-derived] <params_path>\n  prove <params_path> <a_hex> <b_hex> <v_hex> <proof_path> [--blinding <hex>] [--dimension <n>] [--base64] [--value-stdin] [--blinding-env <VAR>]\n  prove-batch <params_path> <input_csv> <out_dir>\n  verify <params_path> <a_hex> <b_hex> <proof_path> [--dimension <n>] [--legacy-proof]\n  inspect <proof_path>\n  benchmark [fast|trusted] [range_lengths...] [--csv <path>] [--track-memory]\n  gen-vectors <out_dir>\n  --json (prove/verify): print one JSON object instead of status lines");
         return;
     }
     match args[1].as_str() {
         "setup" => {
-            if args.len() < 4 { eprintln!("Usage: setup [fast|trusted] <params_path>"); return; }
+            if args.len() < 4 { eprintln!("Usage: setup [fast|trusted|trusted-safe|fast-derived|trusted-derived] <params_path>"); return; }
             let mode = args[2].as_str();
             let path = &args[3];
             let (g, h, n) = match mode {
                 "fast" => fast_test_setup(),
                 "trusted" => trusted_setup(2048),
-                _ => { eprintln!("mode must be fast or trusted"); return; }
+                // n = p * q for safe primes p, q (slow)
+                "trusted-safe" => trusted_setup_safe(2048),
+                // h = derive_h(g, n): nobody knows log_g(h)
+                "fast-derived" => fast_test_setup_derived_h(),
+                "trusted-derived" => trusted_setup_derived_h(2048),
+                _ => { eprintln!("mode must be fast, trusted, trusted-safe, fast-derived or trusted-derived"); return; }
             };
             if let Err(e) = save_params(path, &g, &h, &n) {
                 eprintln!("Failed to save params: {}", e);
@@ -34,27 +60,122 @@ fn main() {
             println!("Saved public parameters to {}", path);
         }
         "prove" => {
-            if args.len() < 7 { eprintln!("Usage: prove <params_path> <a_hex> <b_hex> <v_hex> <proof_path>"); return; }
-            let params_path = &args[2];
-            let a = hex_to_bigint(&args[3]);
-            let b = hex_to_bigint(&args[4]);
-            let v = hex_to_bigint(&args[5]);
-            let proof_path = &args[6];
+            let usage = "Usage: prove <params_path> <a_hex> <b_hex> <v_hex> <proof_path> [--blinding <hex>] [--dimension <n>] [--base64] [--value-stdin] [--blinding-env <VAR>]";
+            // Positional arguments are those that are neither flags nor flag values
+            let mut positional = Vec::new();
+            let mut rest = args[2..].iter();
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--blinding" | "--dimension" | "--blinding-env" => { rest.next(); }
+                    s if s.starts_with("--") => {}
+                    _ => positional.push(arg.as_str()),
+                }
+            }
+            let value_stdin = args.iter().any(|s| s == "--value-stdin");
+            let (params_path, a, b, v, proof_path) = match (value_stdin, positional.as_slice()) {
+                (false, [params, a, b, v, proof, ..]) => (*params, hex_to_bigint(a), hex_to_bigint(b), hex_to_bigint(v), *proof),
+                (true, [params, a, b, proof, ..]) => {
+                    let mut line = String::new();
+                    if let Err(e) = io::stdin().read_line(&mut line) {
+                        eprintln!("Failed to read the value from stdin: {}", e);
+                        return;
+                    }
+                    if line.trim().is_empty() { eprintln!("--value-stdin: no value on stdin"); return; }
+                    (*params, hex_to_bigint(a), hex_to_bigint(b), hex_to_bigint(&line), *proof)
+                }
+                _ => { eprintln!("{}", usage); return; }
+            };
             let (g, h, n) = match load_params(params_path) {
                 Ok(t) => t,
                 Err(e) => { eprintln!("Failed to load params: {}", e); return; }
             };
             // NOTE: In practice, r must be random and kept secret by prover
-            let r = cuproof::util::random_bigint(256);
-            let proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
+            let blinding = match (args.iter().position(|s| s == "--blinding"), args.iter().position(|s| s == "--blinding-env")) {
+                (Some(_), Some(_)) => { eprintln!("--blinding and --blinding-env are mutually exclusive"); return; }
+                (Some(i), None) => match args.get(i + 1).map(|hex| try_hex_to_bigint(hex)) {
+                    Some(Some(r)) => Some(r),
+                    Some(None) => { eprintln!("--blinding: not a hex value"); return; }
+                    None => { eprintln!("--blinding requires a hex value"); return; }
+                },
+                (None, Some(i)) => match args.get(i + 1).map(|var| (var, env::var(var))) {
+                    Some((var, Ok(hex))) => match try_hex_to_bigint(&hex) {
+                        Some(r) => Some(r),
+                        None => { eprintln!("--blinding-env: {} is not a hex value", var); return; }
+                    },
+                    Some((var, Err(e))) => { eprintln!("--blinding-env: cannot read {}: {}", var, e); return; }
+                    None => { eprintln!("--blinding-env requires a variable name"); return; }
+                },
+                (None, None) => None,
+            };
+            // IPP vector length; `verify` must be given the same `--dimension`
+            let dimension = match dimension_flag(&args) {
+                Ok(d) => d,
+                Err(e) => { eprintln!("{}", e); return; }
+            };
+            if blinding.is_some() && dimension != DEFAULT_DIMENSION {
+                eprintln!("--blinding and --blinding-env only support the default dimension {}", DEFAULT_DIMENSION);
+                return;
+            }
+            let start = Instant::now();
+            let proof = match blinding {
+                // WARNING: a pinned blinding makes the whole proof deterministic. Use it only
+                // for test vectors; anyone who learns r can open C, and reusing r across
+                // statements reuses the derived nonces. `--blinding-env` keeps r out of argv,
+                // so only `--blinding` warns; the proof is deterministic either way.
+                Some(r) => {
+                    if args.iter().any(|s| s == "--blinding") {
+                        eprintln!("WARNING: --blinding pins r; the proof is deterministic and not for production use");
+                    }
+                    let mut rng = pinned_blinding_rng(&r, &v, &a, &b, &g, &h, &n);
+                    cuproof_prove_with_rng(&v, &r, &a, &b, &g, &h, &n, &mut rng)
+                }
+                None => {
+                    let r = cuproof::util::random_bigint(256);
+                    cuproof_prove_with_dimension(&v, &r, &a, &b, &g, &h, &n, dimension)
+                }
+            };
+            let prove_ms = start.elapsed().as_millis();
             if let Err(e) = save_proof(proof_path, &proof) {
                 eprintln!("Failed to save proof: {}", e);
                 return;
             }
+            let base64 = args.iter().any(|s| s == "--base64").then(|| proof_to_base64(&proof));
+            if json {
+                let extra = base64.map(|b| format!(",\"base64\":{}", json_string(&b))).unwrap_or_default();
+                println!("{{\"proof_path\":{},\"size_bytes\":{},\"prove_ms\":{}{}}}",
+                         json_string(proof_path), proof_size_bytes(&proof), prove_ms, extra);
+                return;
+            }
             println!("Saved proof to {}", proof_path);
+            if let Some(b) = base64 {
+                println!("{}", b);
+            }
+        }
+        "prove-batch" => {
+            if args.len() < 5 { eprintln!("Usage: prove-batch <params_path> <input_csv> <out_dir>"); return; }
+            let (g, h, n) = match load_params(&args[2]) {
+                Ok(t) => t,
+                Err(e) => { eprintln!("Failed to load params: {}", e); return; }
+            };
+            let csv = match std::fs::read_to_string(&args[3]) {
+                Ok(s) => s,
+                Err(e) => { eprintln!("Failed to read {}: {}", args[3], e); return; }
+            };
+            let (mut proved, mut failed) = (0usize, 0usize);
+            for (row, line) in csv.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+                if line.is_empty() || line.starts_with('#') { continue; }
+                let path = Path::new(&args[4]).join(format!("proof_{}.txt", row));
+                match prove_csv_row(line, &g, &h, &n).and_then(|proof| {
+                    save_proof(&path.to_string_lossy(), &proof).map_err(|e| format!("failed to save proof: {}", e))
+                }) {
+                    Ok(()) => { proved += 1; println!("row {}: saved {}", row, path.display()); }
+                    Err(e) => { failed += 1; eprintln!("row {}: FAILED: {}", row, e); }
+                }
+            }
+            println!("Proved {} of {} rows ({} failed)", proved, proved + failed, failed);
         }
         "verify" => {
-            if args.len() < 6 { eprintln!("Usage: verify <params_path> <a_hex> <b_hex> <proof_path>"); return; }
+            if args.len() < 6 { eprintln!("Usage: verify <params_path> <a_hex> <b_hex> <proof_path> [--dimension <n>] [--legacy-proof]"); return; }
             let params_path = &args[2];
             let a = hex_to_bigint(&args[3]);
             let b = hex_to_bigint(&args[4]);
@@ -63,16 +184,54 @@ fn main() {
                 Ok(t) => t,
                 Err(e) => { eprintln!("Failed to load params: {}", e); return; }
             };
+            // Files from before the proof header carry no mandatory checksum, so they need an explicit opt-in
+            let load = if args.iter().any(|s| s == "--legacy-proof") { load_proof_legacy } else { load_proof };
+            let proof = match load(proof_path) {
+                Ok(p) => p,
+                Err(e) => { eprintln!("Failed to load proof: {}", e); return; }
+            };
+            // The verifier fixes the dimension; a proof at any other one is a DimensionMismatch
+            let dimension = match dimension_flag(&args) {
+                Ok(d) => d,
+                Err(e) => { eprintln!("{}", e); return; }
+            };
+            let start = Instant::now();
+            let result = proof.check_ranges(&n).and_then(|()| cuproof_verify_with_range_and_dimension(&proof, &g, &h, &n, &a, &b, dimension));
+            let verify_ms = start.elapsed().as_millis();
+            match (result, json) {
+                (Ok(()), false) => println!("VALID"),
+                (Err(e), false) => println!("INVALID: {}", e),
+                (Ok(()), true) => println!("{{\"valid\":true,\"verify_ms\":{}}}", verify_ms),
+                (Err(e), true) => println!("{{\"valid\":false,\"verify_ms\":{},\"error\":{}}}", verify_ms, json_string(&e.to_string())),
+            }
+        }
+        "inspect" => {
+            if args.len() < 3 { eprintln!("Usage: inspect <proof_path>"); return; }
+            let proof_path = &args[2];
             let proof = match load_proof(proof_path) {
                 Ok(p) => p,
                 Err(e) => { eprintln!("Failed to load proof: {}", e); return; }
             };
-            let ok = cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b);
-            println!("{}", if ok { "VALID" } else { "INVALID" });
+            println!("Proof: {}", proof_path);
+            let scalars = [
+                ("A", &proof.A), ("S", &proof.S), ("T1", &proof.T1), ("T2", &proof.T2),
+                ("tau_x", &proof.tau_x), ("mu", &proof.mu), ("t_hat", &proof.t_hat),
+                ("C", &proof.C), ("C_v1", &proof.C_v1), ("C_v2", &proof.C_v2),
+                ("t0", &proof.t0), ("t1", &proof.t1), ("t2", &proof.t2),
+                ("tau1", &proof.tau1), ("tau2", &proof.tau2),
+                ("ipp.a", &proof.ipp_proof.a), ("ipp.b", &proof.ipp_proof.b),
+            ];
+            for (name, x) in scalars {
+                println!("  {:<6} {} bits", name, x.bits());
+            }
+            let (l_len, r_len) = (proof.ipp_proof.L.len(), proof.ipp_proof.R.len());
+            println!("IPP levels: {} (L={}, R={}, {})", l_len, l_len, r_len,
+                     if l_len == r_len { "consistent" } else { "INCONSISTENT" });
+            println!("Proof size: {} bytes", proof_size_bytes(&proof));
         }
         "benchmark" => {
             if args.len() < 3 { 
-                eprintln!("Usage: benchmark [fast|trusted] [range_lengths...]");
+                eprintln!("Usage: benchmark [fast|trusted] [range_lengths...] [--csv <path>] [--track-memory]");
                 eprintln!("Example: benchmark fast 8 16 32 64");
                 eprintln!("Example: benchmark trusted 8 16 32 64 128 256 512 1024");
                 return; 
@@ -88,19 +247,38 @@ fn main() {
                 }
             };
             
-            // Parse range lengths from command line arguments
+            // Parse range lengths (and optional `--csv <path>` / `--track-memory`) from command line arguments
             let mut range_lengths = Vec::new();
-            if args.len() > 3 {
-                for i in 3..args.len() {
-                    match args[i].parse::<usize>() {
-                        Ok(length) => range_lengths.push(length),
-                        Err(_) => {
-                            eprintln!("Invalid range length: {}", args[i]);
-                            return;
-                        }
+            let mut csv_path = None;
+            let mut rest = args[3..].iter();
+            while let Some(arg) = rest.next() {
+                if arg == "--csv" {
+                    match rest.next() {
+                        Some(path) => csv_path = Some(path.clone()),
+                        None => { eprintln!("--csv requires a path"); return; }
+                    }
+                    continue;
+                }
+                if arg == "--track-memory" {
+                    #[cfg(feature = "track-memory")]
+                    cuproof::memory::enable();
+                    #[cfg(not(feature = "track-memory"))]
+                    {
+                        eprintln!("--track-memory requires a build with --features track-memory");
+                        return;
+                    }
+                    #[cfg(feature = "track-memory")]
+                    continue;
+                }
+                match arg.parse::<usize>() {
+                    Ok(length) => range_lengths.push(length),
+                    Err(_) => {
+                        eprintln!("Invalid range length: {}", arg);
+                        return;
                     }
                 }
-            } else {
+            }
+            if range_lengths.is_empty() {
                 // Default range lengths if none specified
                 range_lengths = vec![8, 16, 32, 64, 128, 256, 512, 1024];
             }
@@ -110,11 +288,147 @@ fn main() {
             println!("Các độ dài khoảng: {:?}", range_lengths);
             println!();
             
-            let results = benchmark_multiple_ranges(range_lengths, use_fast_setup);
+            let results = match csv_path {
+                // Stream rows to the CSV file so finished ranges survive a later crash
+                Some(path) => {
+                    let mut file = match std::fs::File::create(&path) {
+                        Ok(f) => f,
+                        Err(e) => { eprintln!("Failed to create {}: {}", path, e); return; }
+                    };
+                    if let Err(e) = writeln!(file, "{}", BenchmarkResult::CSV_HEADER) {
+                        eprintln!("Failed to write {}: {}", path, e);
+                        return;
+                    }
+                    let mut results = Vec::new();
+                    benchmark_multiple_ranges_streaming(&range_lengths, use_fast_setup, |result| {
+                        print_benchmark_result(result);
+                        if let Err(e) = writeln!(file, "{}", result.to_csv_row()).and_then(|()| file.flush()) {
+                            eprintln!("Failed to write {}: {}", path, e);
+                        }
+                        results.push(result.clone());
+                    });
+                    println!("Saved CSV results to {}", path);
+                    results
+                }
+                None => benchmark_multiple_ranges(range_lengths, use_fast_setup),
+            };
             print_benchmark_summary(&results);
         }
+        "gen-vectors" => {
+            if args.len() < 3 { eprintln!("Usage: gen-vectors <out_dir>"); return; }
+            match gen_vectors(Path::new(&args[2])) {
+                Ok(count) => println!("Wrote {} test vectors to {}", count, args[2]),
+                Err(e) => eprintln!("Failed to write test vectors: {}", e),
+            }
+        }
         _ => {
             eprintln!("Unknown command");
         }
     }
 }
+
+/// `--dimension <n>` of prove/verify: a power of two of at least 8, `DEFAULT_DIMENSION` if absent
+fn dimension_flag(args: &[String]) -> Result<usize, &'static str> {
+    match args.iter().position(|s| s == "--dimension") {
+        Some(i) => match args.get(i + 1).and_then(|d| d.parse::<usize>().ok()) {
+            Some(d) if d.is_power_of_two() && d >= 8 => Ok(d),
+            _ => Err("--dimension requires a power of two of at least 8"),
+        },
+        None => Ok(DEFAULT_DIMENSION),
+    }
+}
+
+/// Quote and escape a string as a JSON string literal
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Prove one `prove-batch` row `<v_hex>,<a_hex>,<b_hex>` with a fresh random blinding
+/// - returns: the proof, or a message naming the malformed field or the prover error
+fn prove_csv_row(line: &str, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<cuproof::range_proof::Cuproof, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let [v, a, b] = fields.as_slice() else {
+        return Err(format!("expected 3 fields (value,a,b), found {}", fields.len()));
+    };
+    let parse = |name: &str, s: &str| {
+        let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("{} is not a hex number: {:?}", name, s));
+        }
+        Ok(hex_to_bigint(digits))
+    };
+    let (v, a, b) = (parse("value", v)?, parse("a", a)?, parse("b", b)?);
+    if a >= b { return Err(ProveError::InvalidRange.to_string()); }
+    let r = cuproof::util::random_bigint(256);
+    cuproof_prove_checked(&v, &r, &a, &b, g, h, n).map_err(|e| e.to_string())
+}
+
+/// Seed the prover RNG from the pinned blinding and the full statement, so the same
+/// `--blinding` reproduces the same proof while different statements get different nonces
+fn pinned_blinding_rng(r: &BigInt, v: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> StdRng {
+    let mut hasher = Sha256::new();
+    hasher.update(b"cuproof-cli-blinding");
+    for x in [r, v, a, b, g, h, n] {
+        hasher.update(x.to_str_radix(16).as_bytes());
+        hasher.update(b"|");
+    }
+    StdRng::from_seed(hasher.finalize().into())
+}
+
+/// Seed of the `gen-vectors` parameters and deterministic prover key; bump the suffix
+/// whenever the proof format changes so stale vectors are not mistaken for current ones
+const VECTOR_SEED: &[u8] = b"cuproof-test-vectors-v2";
+
+/// Statements (a, b, v) proven by `gen-vectors`, covering both range ends and a 32-bit range
+const VECTOR_STATEMENTS: [(u64, u64, u64); 5] = [
+    (1, 100, 42), (0, 255, 0), (0, 255, 255), (1000, 2000, 1500), (0, 4_294_967_295, 3_000_000_000),
+];
+
+/// Write canonical test vectors to `out_dir`: params.txt, one proof file per vector and a
+/// vectors.txt manifest of `<proof_file> <a_hex> <b_hex> <VALID|INVALID>` lines
+///
+/// Parameters come from `setup_from_seed(VECTOR_SEED)` and every proof from
+/// `ProofBuilder::deterministic`, so rerunning the command reproduces the same files.
+/// Besides one VALID vector per statement, two INVALID ones are emitted: a proof with a
+/// tampered T1, and a valid proof checked against a range it was not made for.
+fn gen_vectors(out_dir: &Path) -> io::Result<usize> {
+    std::fs::create_dir_all(out_dir)?;
+    let path_str = |name: &str| out_dir.join(name).to_string_lossy().into_owned();
+    let (g, h, n) = setup_from_seed(VECTOR_SEED, 256);
+    save_params(&path_str("params.txt"), &g, &h, &n)?;
+    let key = BigInt::from_bytes_be(num_bigint::Sign::Plus, VECTOR_SEED);
+
+    let mut manifest = String::from("# <proof_file> <a_hex> <b_hex> <expected>, checked against params.txt\n");
+    let mut entries = Vec::new();
+    for (i, &(a, b, v)) in VECTOR_STATEMENTS.iter().enumerate() {
+        let (a, b, v) = (BigInt::from(a), BigInt::from(b), BigInt::from(v));
+        let proof = ProofBuilder::new().value(&v).range(&a, &b).params(&g, &h, &n).deterministic(&key).build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        entries.push((format!("valid_{}.proof", i), proof, a, b, "VALID"));
+    }
+
+    // INVALID: the first proof with T1 tampered, and the same proof against [1, 50] instead of [1, 100]
+    let (first, a, b) = (entries[0].1.clone(), entries[0].2.clone(), entries[0].3.clone());
+    let mut tampered = first.clone();
+    tampered.T1 = (&tampered.T1 + 1u32) % &n;
+    entries.push(("invalid_tampered_t1.proof".to_string(), tampered, a.clone(), b.clone(), "INVALID"));
+    entries.push(("invalid_wrong_range.proof".to_string(), first, a, b - 50u32, "INVALID"));
+
+    for (file, proof, a, b, expected) in &entries {
+        save_proof(&path_str(file), proof)?;
+        manifest.push_str(&format!("{} {} {} {}\n", file, bigint_to_hex(a), bigint_to_hex(b), expected));
+    }
+    std::fs::write(out_dir.join("vectors.txt"), manifest)?;
+    Ok(entries.len())
+}
@@ -0,0 +1,79 @@
+//! Counting global allocator behind the `track-memory` feature
+//!
+//! The library only provides `TrackingAllocator`; the binary that wants peaks installs it
+//! (the `cuproof` CLI does, as `#[global_allocator]` under the feature), so benches and
+//! downstream binaries keep their own allocator. Once installed every allocation is counted;
+//! `enable` only switches the benchmark into reporting the peaks (`benchmark --track-memory`).
+//! Counts are process-wide, so allocations made by other threads at the same time (e.g.
+//! parallel tests) add to a peak.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// System allocator that keeps the live byte count and its high-water mark
+pub struct TrackingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn grow(bytes: usize) {
+    let now = CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK.fetch_max(now, Ordering::Relaxed);
+}
+
+fn shrink(bytes: usize) {
+    CURRENT.fetch_sub(bytes, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() { grow(layout.size()); }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() { grow(layout.size()); }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            if new_size > layout.size() { grow(new_size - layout.size()); } else { shrink(layout.size() - new_size); }
+        }
+        new_ptr
+    }
+}
+
+// The library's own unit tests are a binary of their own and measure with it too
+#[cfg(test)]
+#[global_allocator]
+static GLOBAL: TrackingAllocator = TrackingAllocator;
+
+/// Make the benchmark record peak memory for prove and verify
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// True once `enable` was called
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Run f and measure its peak allocation
+/// - returns: f's output and the highest live byte count above the count at entry
+/// - usage: wrap one prove or verify call; not reentrant, a nested call resets the outer peak
+pub fn peak_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let out = f();
+    (out, PEAK.load(Ordering::Relaxed).saturating_sub(baseline))
+}
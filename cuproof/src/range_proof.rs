@@ -1,8 +1,75 @@
 use crate::{util::*, lagrange::*, commitment::*, fiat_shamir::*};
+use crate::backend::{GroupBackend, RsaBackend};
 use num_bigint::BigInt;
-use num_traits::Zero;
+use num_traits::{One, Signed, Zero};
+use rand::{RngCore, SeedableRng};
+use rand::rngs::{OsRng, StdRng};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
 
-#[derive(Clone)]
+/// IPP dimension used by `cuproof_prove` and assumed by `cuproof_verify`
+pub const DEFAULT_DIMENSION: usize = 64;
+
+/// Reason the prover could not build a proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProveError {
+	/// v1 = 4(v - a) + 1 has no three-square witness (negative when v < a)
+	V1Decomposition(SquaresError),
+	/// v2 = 4(b - v) + 1 has no three-square witness (negative when v > b)
+	V2Decomposition(SquaresError),
+	/// `ProofBuilder::build` was called without setting this field
+	MissingField(&'static str),
+	/// The range is empty (a >= b) or unbounded on one side
+	InvalidRange,
+	/// The IPP dimension must be a non-zero power of two
+	InvalidDimension(usize),
+	/// The IPP dimension is below the 6 entries of the three-square witness
+	DimensionTooSmall(usize),
+}
+
+impl fmt::Display for ProveError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ProveError::V1Decomposition(e) => write!(f, "v1 has no three-square decomposition: {}", e),
+			ProveError::V2Decomposition(e) => write!(f, "v2 has no three-square decomposition: {}", e),
+			ProveError::MissingField(name) => write!(f, "missing field: {}", name),
+			ProveError::InvalidRange => write!(f, "range must satisfy a < b"),
+			ProveError::InvalidDimension(d) => write!(f, "dimension {} is not a non-zero power of two", d),
+			ProveError::DimensionTooSmall(d) => write!(f, "dimension {} cannot hold the 6-entry range witness", d),
+		}
+	}
+}
+
+impl std::error::Error for ProveError {}
+
+/// Three-square witnesses (d1..d6) for v1 and v2, each with the smallest largest component
+/// (see `find_3_squares_balanced`)
+fn range_witness(v1: &BigInt, v2: &BigInt) -> Result<Vec<BigInt>, ProveError> {
+	let d1 = find_3_squares_balanced(v1).map_err(ProveError::V1Decomposition)?;
+	let d2 = find_3_squares_balanced(v2).map_err(ProveError::V2Decomposition)?;
+	Ok([d1, d2].concat())
+}
+
+/// `range_witness` zero-padded to `dimension` entries: d[0..3] are the squares of v1 and the rest
+/// those of v2, which the verifier checks block by block against C_v1 and C_v2 (see
+/// `range_relation_holds`), so a repeated or otherwise altered witness is rejected
+///
+/// This is still the three-square witness of the Cuproof statement, not a bit decomposition of
+/// v1 and v2 as in Bulletproofs, which would need a different relation.
+pub(crate) fn padded_range_witness(v1: &BigInt, v2: &BigInt, dimension: usize) -> Result<Vec<BigInt>, ProveError> {
+	zero_padded(range_witness(v1, v2)?, dimension).ok_or(ProveError::DimensionTooSmall(dimension))
+}
+
+/// Witness d zero-padded to `dimension` entries; None if it does not fit
+pub(crate) fn zero_padded(mut d: Vec<BigInt>, dimension: usize) -> Option<Vec<BigInt>> {
+	if dimension < d.len() { return None; }
+	d.resize(dimension, BigInt::zero());
+	Some(d)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IPPProof {
 	pub L: Vec<BigInt>,  // Left commitments at each level
 	pub R: Vec<BigInt>,  // Right commitments at each level
@@ -10,7 +77,7 @@ pub struct IPPProof {
 	pub b: BigInt,        // Final scalar
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Cuproof {
 	pub A: BigInt,
 	pub S: BigInt,
@@ -30,6 +97,127 @@ pub struct Cuproof {
 	pub ipp_proof: IPPProof,  // Inner Product Argument proof
 }
 
+/// `Cuproof` without the fields the verifier can recompute, see `Cuproof::to_minimal`
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinimalCuproof {
+	pub A: BigInt,
+	pub S: BigInt,
+	pub tau_x: BigInt,
+	pub mu: BigInt,
+	pub C: BigInt,
+	pub C_v1: BigInt,
+	pub C_v2: BigInt,
+	pub t0: BigInt,
+	pub t1: BigInt,
+	pub t2: BigInt,
+	pub tau1: BigInt,
+	pub tau2: BigInt,
+	pub ipp_proof: IPPProof,
+}
+
+/// The fields a verifier needs: `Cuproof` without the prover's openings t0, t1, t2, tau1, tau2,
+/// see `Cuproof::to_verifier`
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifierProof {
+	pub A: BigInt,
+	pub S: BigInt,
+	pub T1: BigInt,
+	pub T2: BigInt,
+	pub tau_x: BigInt,
+	pub mu: BigInt,
+	pub t_hat: BigInt,
+	pub C: BigInt,
+	pub C_v1: BigInt,
+	pub C_v2: BigInt,
+	pub ipp_proof: IPPProof,
+}
+
+impl Cuproof {
+	/// Drop the three fields that are functions of the remaining ones:
+	/// - T1, T2: the verifier only accepts T1 = Commit(t1, tau1) and T2 = Commit(t2, tau2),
+	///   so it can recommit the openings instead of reading them
+	/// - t_hat: must equal t0 + t1 x + t2 x^2, with x = H(T1, T2) known once T1, T2 are
+	///
+	/// tau_x stays: it also carries the blindings of C_v1 and C_v2, which the openings do not
+	pub fn to_minimal(&self) -> MinimalCuproof {
+		MinimalCuproof {
+			A: self.A.clone(), S: self.S.clone(), tau_x: self.tau_x.clone(), mu: self.mu.clone(),
+			C: self.C.clone(), C_v1: self.C_v1.clone(), C_v2: self.C_v2.clone(),
+			t0: self.t0.clone(), t1: self.t1.clone(), t2: self.t2.clone(),
+			tau1: self.tau1.clone(), tau2: self.tau2.clone(),
+			ipp_proof: self.ipp_proof.clone(),
+		}
+	}
+
+	/// Drop the prover's openings t0, t1, t2, tau1, tau2: the IPP binds t_hat to A and S, and
+	/// g^t_hat h^tau_x = C_v1^z C_v2^(z^2) g^δ T1^x T2^(x^2) binds it to C_v1 and C_v2 through
+	/// T1 and T2 alone
+	/// - usage: relay this instead of the full proof. t0 = z v1 + z^2 v2 + δ, so the openings
+	///   (with t_hat) disclose v1 and hence v; `cuproof_verify_verifier_proof` needs none of them
+	pub fn to_verifier(&self) -> VerifierProof {
+		VerifierProof {
+			A: self.A.clone(), S: self.S.clone(), T1: self.T1.clone(), T2: self.T2.clone(),
+			tau_x: self.tau_x.clone(), mu: self.mu.clone(), t_hat: self.t_hat.clone(),
+			C: self.C.clone(), C_v1: self.C_v1.clone(), C_v2: self.C_v2.clone(),
+			ipp_proof: self.ipp_proof.clone(),
+		}
+	}
+
+	/// IPP recursion depth, the number of L/R folding rounds (log2 of the dimension)
+	pub fn ipp_depth(&self) -> usize {
+		debug_assert_eq!(self.ipp_proof.L.len(), self.ipp_proof.R.len(), "IPP L and R lengths differ");
+		self.ipp_proof.L.len()
+	}
+
+	/// IPP dimension the proof was built at, recorded by its recursion depth (2^levels)
+	/// - returns: None if the IPP has too many levels for a usize dimension
+	/// - usage: compare with the dimension a verifier assumes, see `VerifyError::DimensionMismatch`
+	pub fn dimension(&self) -> Option<usize> {
+		1usize.checked_shl(u32::try_from(self.ipp_depth()).ok()?)
+	}
+
+	/// Reduce the group elements (A, S, T1, T2, C, C_v1, C_v2, IPP L/R) and the responses
+	/// t_hat, tau_x, mu modulo n, in place
+	/// t0, t1, t2, tau1, tau2 are left as-is: T1 and T2 open to them as integers, so reducing
+	/// them would also require recomputing T1, T2.
+	pub fn reduce_mod(&mut self, n: &BigInt) {
+		for x in [&mut self.A, &mut self.S, &mut self.T1, &mut self.T2, &mut self.C, &mut self.C_v1, &mut self.C_v2,
+			&mut self.t_hat, &mut self.tau_x, &mut self.mu] {
+			*x = &*x % n;
+		}
+		for x in self.ipp_proof.L.iter_mut().chain(self.ipp_proof.R.iter_mut()) {
+			*x = &*x % n;
+		}
+	}
+}
+
+impl VerifierProof {
+	/// Same as `Cuproof::dimension`
+	pub fn dimension(&self) -> Option<usize> {
+		1usize.checked_shl(u32::try_from(self.ipp_proof.L.len()).ok()?)
+	}
+}
+
+impl MinimalCuproof {
+	/// Rebuild the full proof under (g, h, n), recomputing T1, T2 and t_hat
+	#[allow(non_snake_case)]
+	pub fn expand(&self, g: &BigInt, h: &BigInt, n: &BigInt) -> Cuproof {
+		let T1 = pedersen_commit(g, h, &self.t1, &self.tau1, n);
+		let T2 = pedersen_commit(g, h, &self.t2, &self.tau2, n);
+		let x = RsaBackend::<DefaultChallengeHasher>::new(g, h, n).challenge(&[&T1, &T2]);
+		let t_hat = &self.t0 + &(&self.t1 * &x) + &(&self.t2 * &x * &x);
+		Cuproof {
+			A: self.A.clone(), S: self.S.clone(), T1, T2, tau_x: self.tau_x.clone(), mu: self.mu.clone(), t_hat,
+			C: self.C.clone(), C_v1: self.C_v1.clone(), C_v2: self.C_v2.clone(),
+			t0: self.t0.clone(), t1: self.t1.clone(), t2: self.t2.clone(),
+			tau1: self.tau1.clone(), tau2: self.tau2.clone(),
+			ipp_proof: self.ipp_proof.clone(),
+		}
+	}
+}
+
 // Interactive Proof Protocol Structures
 #[derive(Clone)]
 pub struct ProverState {
@@ -51,110 +239,465 @@ pub struct ProverState {
 	pub t2: BigInt,
 	pub tau1: BigInt,
 	pub tau2: BigInt,
+	pub blindings: CommitmentBlindings,
+}
+
+/// Blindings behind C, C_v1 and C_v2 of one proof
+///
+/// v1 = 4v - 4a + 1 and v2 = 4b - 4v + 1 are linear in v, so their blindings must follow the
+/// same combination of r for the verifier's relations C_v1 g^(4a) = C^4 g and
+/// C_v1 C_v2 = g^(4(b-a)+2) to hold: r_v1 = 4r and r_v1 + r_v2 = 0
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitmentBlindings {
+	pub r: BigInt,
+	pub r_v1: BigInt,
+	pub r_v2: BigInt,
+}
+
+impl CommitmentBlindings {
+	/// Blindings derived from the caller's r: (r, 4r, -4r)
+	pub fn new(r: &BigInt) -> Self {
+		let r_v1 = 4 * r;
+		let r_v2 = -&r_v1;
+		CommitmentBlindings { r: r.clone(), r_v1, r_v2 }
+	}
+
+	/// Whether the blindings follow the v1/v2 linear combination (r_v1 = 4r, r_v2 = -4r)
+	pub fn is_consistent(&self) -> bool {
+		self.r_v1 == 4 * &self.r && (&self.r_v1 + &self.r_v2).is_zero()
+	}
+
+	/// C, C_v1, C_v2 for (v, v1, v2) under these blindings
+	#[allow(non_snake_case)]
+	pub fn commit(&self, v: &BigInt, v1: &BigInt, v2: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> (BigInt, BigInt, BigInt) {
+		let C = pedersen_commit(g, h, v, &self.r, n);
+		let C_v1 = pedersen_commit(g, h, v1, &self.r_v1, n);
+		let C_v2 = pedersen_commit(g, h, v2, &self.r_v2, n);
+		(C, C_v1, C_v2)
+	}
 }
 
+/// Overwrite the heap digits of `x` with zeros in place and leave it equal to 0.
+///
+/// num-bigint has no zeroize support, so this goes through `assign_from_slice`, which clears the
+/// digit vector and refills it within its current capacity (no reallocation for the same length).
+/// Limitation: copies made by intermediate arithmetic (temporaries, earlier reallocations, spare
+/// capacity beyond the current length) are not covered.
+#[cfg(feature = "zeroize")]
+fn wipe_bigint(x: &mut BigInt) {
+	let digits = x.bits().div_ceil(64) as usize * 2;
+	x.assign_from_slice(num_bigint::Sign::Plus, &vec![0u32; digits]);
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for ProverState {
+	fn zeroize(&mut self) {
+		for x in [&mut self.v, &mut self.r, &mut self.alpha, &mut self.rho, &mut self.v1, &mut self.v2,
+			&mut self.t0, &mut self.t1, &mut self.t2, &mut self.tau1, &mut self.tau2,
+			&mut self.blindings.r, &mut self.blindings.r_v1, &mut self.blindings.r_v2] {
+			wipe_bigint(x);
+		}
+		for vec in [&mut self.sL, &mut self.sR, &mut self.d, &mut self.l0, &mut self.r0] {
+			vec.iter_mut().for_each(wipe_bigint);
+			vec.clear();
+		}
+	}
+}
+
+/// With the `zeroize` feature, prover secrets are wiped when the state goes out of scope
+#[cfg(feature = "zeroize")]
+impl Drop for ProverState {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(self);
+	}
+}
+
+/// Interactive verifier transcript. The public parameters and the prover's messages A, S, T1,
+/// T2 are borrowed from the caller for the lifetime of the session; `Cow` still allows a
+/// detached copy via `into_owned`
+///
+/// Breaking change: the type used to own every field and had no lifetime. Code that names it
+/// now writes `VerifierState<'_>`, must keep g, h, n and the prover's messages alive while the
+/// state is in use, and calls `into_owned` to store a `VerifierState<'static>`.
 #[derive(Clone)]
-pub struct VerifierState {
-	pub g: BigInt,
-	pub h: BigInt,
-	pub n: BigInt,
+pub struct VerifierState<'a> {
+	pub g: Cow<'a, BigInt>,
+	pub h: Cow<'a, BigInt>,
+	pub n: Cow<'a, BigInt>,
+	pub A: Cow<'a, BigInt>,
+	pub S: Cow<'a, BigInt>,
+	pub T1: Cow<'a, BigInt>,
+	pub T2: Cow<'a, BigInt>,
+	pub y: BigInt,
+	pub z: BigInt,
+	pub x: BigInt,
+}
+
+impl VerifierState<'_> {
+	/// Detach the state from everything it borrows, cloning each borrowed value once
+	pub fn into_owned(self) -> VerifierState<'static> {
+		let own = |c: Cow<'_, BigInt>| Cow::Owned(c.into_owned());
+		VerifierState {
+			g: own(self.g), h: own(self.h), n: own(self.n),
+			A: own(self.A), S: own(self.S), T1: own(self.T1), T2: own(self.T2),
+			y: self.y, z: self.z, x: self.x,
+		}
+	}
+}
+
+/// Auxiliary values (v1, v2) = (4v-4a+1, 4b-4v+1) the range encoding commits to
+/// - params: v the (revealed) value, a/b public range bounds
+/// - returns: (v1, v2); both are positive exactly when v lies in [a, b]
+/// - usage: recompute what C_v1 and C_v2 must open to once v is known
+pub fn expected_auxiliary(v: &BigInt, a: &BigInt, b: &BigInt) -> (BigInt, BigInt) {
+	(4 * v - 4 * a + 1, 4 * b - 4 * v + 1)
+}
+
+/// Commitments to v1 = 4v-4a+1 and v2 = 4b-4v+1 under `blindings.r_v1` and `blindings.r_v2`
+/// (4r and -4r), i.e. exactly the values a verifier can recompute from C, a and b
+fn range_commitments<B: GroupBackend>(backend: &B, v1: &BigInt, v2: &BigInt, blindings: &CommitmentBlindings) -> (B::Element, B::Element) {
+	(backend.commit(v1, &blindings.r_v1), backend.commit(v2, &blindings.r_v2))
+}
+
+/// Whether C_v1, C_v2 are bound to C and the public range [a, b]:
+/// C_v1 g^(4a) = C^4 g and C_v1 C_v2 = g^(4(b-a)+2)
+#[allow(non_snake_case)]
+pub fn range_binding_holds(C: &BigInt, C_v1: &BigInt, C_v2: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, n: &BigInt) -> bool {
+	let lhs1 = C_v1 * mod_exp(g, &(4 * a), n) % n;
+	let rhs1 = mod_exp(C, &BigInt::from(4), n) * g % n;
+	let lhs2 = C_v1 * C_v2 % n;
+	let rhs2 = mod_exp(g, &(4 * (b - a) + 2), n);
+	ct_eq(&lhs1, &rhs1, n) & ct_eq(&lhs2, &rhs2, n)
+}
+
+/// Generator vectors g_vec, h_vec (one entry per witness coordinate) and the base u carrying
+/// the inner product, all derived with `GroupBackend::generator`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IppGenerators<E> {
+	pub g_vec: Vec<E>,
+	pub h_vec: Vec<E>,
+	pub u: E,
+}
+
+impl<E> IppGenerators<E> {
+	/// u = generator(0), g_i = generator(2i + 1), h_i = generator(2i + 2), so the generators of a
+	/// smaller dimension are a prefix of those of a larger one
+	pub fn new<B: GroupBackend<Element = E>>(backend: &B, dimension: usize) -> Self {
+		IppGenerators {
+			g_vec: (0..dimension).map(|i| backend.generator(2 * i + 1)).collect(),
+			h_vec: (0..dimension).map(|i| backend.generator(2 * i + 2)).collect(),
+			u: backend.generator(0),
+		}
+	}
+}
+
+/// Vector commitment P = g_vec^l h_vec^r u^<l, r> that an IPP opens
+pub fn ipp_commitment<B: GroupBackend>(backend: &B, gens: &IppGenerators<B::Element>, l_vec: &[BigInt], r_vec: &[BigInt]) -> B::Element {
+	let bases = [gens.g_vec.as_slice(), gens.h_vec.as_slice(), std::slice::from_ref(&gens.u)].concat();
+	let exps = [l_vec, r_vec, &[inner_product(l_vec, r_vec)]].concat();
+	backend.multi_exp(&bases, &exps)
+}
+
+/// Blinded vector commitment h^blinding g_vec^l h_vec^r, the form of A and S in a `Cuproof`
+pub(crate) fn vector_commit<B: GroupBackend>(backend: &B, gens: &IppGenerators<B::Element>, l_vec: &[BigInt], r_vec: &[BigInt], blinding: &BigInt) -> B::Element {
+	let bases = [gens.g_vec.as_slice(), gens.h_vec.as_slice()].concat();
+	backend.combine(&backend.commit(&BigInt::zero(), blinding), &backend.multi_exp(&bases, &[l_vec, r_vec].concat()))
+}
+
+/// Block of each witness coordinate for `values` range statements of `width` coordinates each
+///
+/// The three squares of v1_j form block 2j; the squares of v2_j and value j's zero padding form
+/// block 2j + 1; coordinates past the last value join the last block.
+pub(crate) fn range_blocks(values: usize, width: usize, dimension: usize) -> Vec<usize> {
+	(0..dimension).map(|i| {
+		let j = (i / width).min(values - 1);
+		2 * j + usize::from(i - j * width >= 3)
+	}).collect()
+}
+
+/// Challenge-derived weights of the range relation, see `prove_range_relation`
+///
+/// Coordinate i gets its own y_i = H(y, i) and, in block k of B, the weight Z f_i with
+/// f_i = z^k and Z = z^(B-1). Block k then enters t0 with z^(B-1+k) (`blocks[k]`), so each
+/// block's squares are tied to its own commitment rather than to the sum of all of them.
+#[allow(non_snake_case)]
+pub(crate) struct RangeWeights {
+	pub y: Vec<BigInt>,
+	pub f: Vec<BigInt>,
+	pub Z: BigInt,
+	pub blocks: Vec<BigInt>,
+}
+
+impl RangeWeights {
+	/// Weights for the coordinate-to-block map `blocks` (entries below `block_count`)
+	pub fn new<B: GroupBackend<Element = BigInt>>(backend: &B, ch_y: &BigInt, z: &BigInt, blocks: &[usize], block_count: usize) -> Self {
+		let powers: Vec<BigInt> = std::iter::successors(Some(BigInt::one()), |p| Some(p * z)).take(2 * block_count - 1).collect();
+		RangeWeights {
+			y: (0..blocks.len()).map(|i| backend.challenge(&[ch_y, &BigInt::from(i)])).collect(),
+			f: blocks.iter().map(|&k| powers[k].clone()).collect(),
+			Z: powers[block_count - 1].clone(),
+			blocks: powers[block_count - 1..].to_vec(),
+		}
+	}
+
+	/// IPP generators g'_i = g_i^(Z / f_i), h'_i = h_i: g'^l = g^(Z (d - y + sL x)) for
+	/// l_i = f_i (d_i - y_i + sL_i x)
+	pub fn generators<B: GroupBackend>(&self, backend: &B, gens: &IppGenerators<B::Element>) -> IppGenerators<B::Element> where B::Element: Clone {
+		IppGenerators {
+			g_vec: gens.g_vec.iter().zip(&self.f).map(|(g, f)| backend.exp(g, &(&self.Z / f))).collect(),
+			h_vec: gens.h_vec.clone(),
+			u: gens.u.clone(),
+		}
+	}
+
+	/// δ = -Σ Z f_i y_i^2, the part of t0 that does not depend on the witness
+	pub fn delta(&self) -> BigInt {
+		-self.f.iter().zip(&self.y).map(|(f, y)| f * y * y).sum::<BigInt>() * &self.Z
+	}
+}
+
+/// IPP statement P = g'^l h_vec^r u^t_hat of a range proof, rebuilt from public values
+///
+/// A = h^α g_vec^d h_vec^d and S = h^ρ g_vec^sL h_vec^sR, and l_i = f_i (d_i - y_i + sL_i x),
+/// r_i = Z (d_i + y_i + sR_i x) under the generators of `RangeWeights::generators`, so
+///   P = (A S^x)^Z g_vec^(-Z y) h_vec^(Z y) u^t_hat h^(-mu)  with mu = Z (α + ρ x).
+/// The IPP only opens this P if t_hat = <l, r>, which ties t_hat to the vectors in A and S
+/// rather than to the prover's t0, t1, t2.
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub(crate) fn ipp_statement<B: GroupBackend>(backend: &B, gens: &IppGenerators<B::Element>, A: &B::Element, S: &B::Element, t_hat: &BigInt, mu: &BigInt, x: &BigInt, weights: &RangeWeights) -> B::Element {
+	let bases = [std::slice::from_ref(A), std::slice::from_ref(S), &gens.g_vec, &gens.h_vec, std::slice::from_ref(&gens.u)].concat();
+	let zy: Vec<BigInt> = weights.y.iter().map(|y| y * &weights.Z).collect();
+	let neg_zy: Vec<BigInt> = zy.iter().map(|e| -e).collect();
+	let exps = [&[weights.Z.clone(), &weights.Z * x], neg_zy.as_slice(), zy.as_slice(), std::slice::from_ref(t_hat)].concat();
+	backend.combine(&backend.multi_exp(&bases, &exps), &backend.commit(&BigInt::zero(), &-mu))
+}
+
+/// Whether g^t_hat h^tau_x = Π_k V_k^(z^(B-1+k)) g^δ T1^x T2^(x^2) for the block commitments V_k
+///
+/// With t_hat = <l, r> enforced by the IPP, this holds only if t0 = Σ_k z^(B-1+k) v_k + δ, i.e.
+/// the squares in block k sum to the value V_k commits to.
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub(crate) fn range_relation_holds<B: GroupBackend<Element = BigInt>>(backend: &B, commitments: &[&BigInt], T1: &BigInt, T2: &BigInt, t_hat: &BigInt, tau_x: &BigInt, x: &BigInt, weights: &RangeWeights) -> bool {
+	if commitments.len() != weights.blocks.len() { return false; }
+	let bases: Vec<BigInt> = commitments.iter().copied().chain([T1, T2]).cloned().collect();
+	let exps: Vec<BigInt> = weights.blocks.iter().cloned().chain([x.clone(), x * x]).collect();
+	let rhs = backend.combine(&backend.multi_exp(&bases, &exps), &backend.commit(&weights.delta(), &BigInt::zero()));
+	ct_eq(&backend.commit(t_hat, tau_x), &rhs, backend.scalar_modulus())
+}
+
+/// Prover messages of `prove_range_relation`, the part `Cuproof`, `AggregatedCuproof` and
+/// `BoundProof` share
+#[allow(non_snake_case)]
+pub(crate) struct RangeRelationProof {
 	pub A: BigInt,
 	pub S: BigInt,
 	pub T1: BigInt,
 	pub T2: BigInt,
-	pub y: BigInt,
-	pub z: BigInt,
-	pub x: BigInt,
+	pub tau_x: BigInt,
+	pub mu: BigInt,
+	pub t_hat: BigInt,
+	pub t0: BigInt,
+	pub t1: BigInt,
+	pub t2: BigInt,
+	pub tau1: BigInt,
+	pub tau2: BigInt,
+	pub ipp_proof: IPPProof,
+	pub challenges: Challenges,
+}
+
+/// Prove that the squares of d in block k sum to the value committed with `blindings[k]`
+/// - params: backend, witness d (length a power of two), `blocks` from `range_blocks`, one
+///   blinding per block, `transcript` mapping (A, S) to y, rng for the nonces
+/// - returns: the prover messages; tau_x = τ1 x + τ2 x^2 + Σ_k z^(B-1+k) blindings[k] opens the
+///   right-hand side of `range_relation_holds`
+/// - usage: nonces are drawn in the order α, ρ, sL, sR, τ1, τ2
+#[allow(non_snake_case)]
+pub(crate) fn prove_range_relation<B: GroupBackend<Element = BigInt>, R: RngCore + ?Sized>(backend: &B, d: &[BigInt], blocks: &[usize], blindings: &[BigInt], transcript: impl FnOnce(&BigInt, &BigInt) -> BigInt, rng: &mut R) -> RangeRelationProof {
+	let dimension = d.len();
+	let alpha = random_bigint_mod_from(rng, backend.scalar_modulus());
+	let rho = random_bigint_mod_from(rng, backend.scalar_modulus());
+	let sL = (0..dimension).map(|_| random_bigint_from(rng, 256)).collect::<Vec<_>>();
+	let sR = (0..dimension).map(|_| random_bigint_from(rng, 256)).collect::<Vec<_>>();
+
+	// Commit A and S to the witness vectors under the IPP generators, so the verifier can
+	// rebuild the IPP statement from them (see `ipp_statement`)
+	let gens = IppGenerators::new(backend, dimension);
+	let A = vector_commit(backend, &gens, d, d, &alpha);
+	let S = vector_commit(backend, &gens, &sL, &sR, &rho);
+
+	// Fiat–Shamir challenges
+	let y = transcript(&A, &S);
+	let z = backend.challenge(&[&y]);
+	let w = RangeWeights::new(backend, &y, &z, blocks, blindings.len());
+
+	// l(x) = f ∘ (d - y + sL x), r(x) = Z (d + y + sR x)
+	let l0 = d.iter().zip(&w.y).zip(&w.f).map(|((di, yi), fi)| fi * (di - yi)).collect::<Vec<_>>();
+	let r0 = d.iter().zip(&w.y).map(|(di, yi)| &w.Z * (di + yi)).collect::<Vec<_>>();
+	let l1 = sL.iter().zip(&w.f).map(|(sLi, fi)| fi * sLi).collect::<Vec<_>>();
+	let r1 = sR.iter().map(|sRi| &w.Z * sRi).collect::<Vec<_>>();
+
+	// Coefficients of t(x) = <l(x), r(x)> = t0 + t1 x + t2 x^2
+	let t0 = inner_product(&l0, &r0);
+	let t1 = inner_product(&l0, &r1) + inner_product(&l1, &r0);
+	let t2 = inner_product(&l1, &r1);
+
+	// Commit T1 = Commit(t1, tau1), T2 = Commit(t2, tau2)
+	let tau1 = random_bigint_mod_from(rng, backend.scalar_modulus());
+	let tau2 = random_bigint_mod_from(rng, backend.scalar_modulus());
+	let T1 = backend.commit(&t1, &tau1);
+	let T2 = backend.commit(&t2, &tau2);
+
+	// Challenge x
+	let x = backend.challenge(&[&T1, &T2]);
+
+	// Evaluate t_hat at x
+	let t_hat = &t0 + &(&t1 * &x) + &(&t2 * &x * &x);
+
+	// Aggregate blinding terms: μ = Z (α + ρ x) (the blinding of (A S^x)^Z) ;
+	// τx = τ2 x^2 + τ1 x + Σ_k z^(B-1+k) γ_k for the block blindings γ_k
+	let mu = &w.Z * (&alpha + &(&rho * &x));
+	let tau_x = &tau2 * &x * &x + &tau1 * &x + w.blocks.iter().zip(blindings).map(|(wk, gk)| wk * gk).sum::<BigInt>();
+
+	// Generate IPP proof for l_vec and r_vec
+	let l_vec = l0.iter().zip(&l1).map(|(l0i, l1i)| l0i + &(l1i * &x)).collect::<Vec<_>>();
+	let r_vec = r0.iter().zip(&r1).map(|(r0i, r1i)| r0i + &(r1i * &x)).collect::<Vec<_>>();
+	let ipp_proof = prove_inner_product(backend, &w.generators(backend, &gens), &l_vec, &r_vec);
+
+	RangeRelationProof {
+		A, S, T1, T2, tau_x, mu, t_hat, t0, t1, t2, tau1, tau2, ipp_proof,
+		challenges: Challenges { y, z, x },
+	}
+}
+
+/// One IPP level fold: P' = L P^x R^(x^2)
+#[allow(non_snake_case)]
+pub(crate) fn fold_ipp_commitment<B: GroupBackend>(backend: &B, P: &B::Element, L: &B::Element, R: &B::Element, x: &BigInt) -> B::Element {
+	backend.combine(&backend.combine(L, &backend.exp(P, x)), &backend.exp(R, &(x * x)))
 }
 
-// Helper function to compute commitment to a value
-fn commit_value(g: &BigInt, h: &BigInt, value: &BigInt, n: &BigInt) -> (BigInt, BigInt) {
-	let r = random_bigint(256);
-	let commitment = pedersen_commit(g, h, value, &r, n);
-	(commitment, r)
+/// One IPP level fold of the generators: g' = g_L^x ∘ g_R, h' = h_L ∘ h_R^x
+pub(crate) fn fold_ipp_generators<B: GroupBackend>(backend: &B, g_vec: &[B::Element], h_vec: &[B::Element], x: &BigInt) -> (Vec<B::Element>, Vec<B::Element>) {
+	let mid = g_vec.len() / 2;
+	let g_new = (0..mid).map(|i| backend.combine(&backend.exp(&g_vec[i], x), &g_vec[mid + i])).collect();
+	let h_new = (0..mid).map(|i| backend.combine(&h_vec[i], &backend.exp(&h_vec[mid + i], x))).collect();
+	(g_new, h_new)
 }
 
-// Full Inner Product Argument implementation
-fn inner_product_argument_recursive(
-	l_vec: &[BigInt], 
-	r_vec: &[BigInt], 
-	g: &BigInt, 
-	h: &BigInt, 
-	n: &BigInt,
-	level: usize
-) -> (BigInt, BigInt, Vec<BigInt>, Vec<BigInt>) {
+/// Inner Product Argument over committed generator vectors
+///
+/// Proves knowledge of l, r with P = g_vec^l h_vec^r u^<l, r>. Each level splits the vectors in
+/// halves and sends
+///   L = g_R^l_L h_L^r_R u^<l_L, r_R>,  R = g_L^l_R h_R^r_L u^<l_R, r_L>
+/// then folds with x = H(P, L, R):
+///   l' = l_L + x l_R,  r' = x r_L + r_R,  g' = g_L^x ∘ g_R,  h' = h_L ∘ h_R^x
+/// which keeps P' = L P^x R^(x^2) = g'^l' h'^r' u^<l', r'>. Bulletproofs folds with x and x^-1,
+/// but the order of Z_n^* is unknown, so exponents have no inverses; this variant needs none.
+/// Returns the final (a, b) and L, R from the top level down. The length must be a power of two.
+#[allow(non_snake_case)]
+fn inner_product_argument_recursive<B: GroupBackend>(
+	l_vec: &[BigInt],
+	r_vec: &[BigInt],
+	g_vec: &[B::Element],
+	h_vec: &[B::Element],
+	u: &B::Element,
+	P: &B::Element,
+	backend: &B,
+) -> (BigInt, BigInt, Vec<B::Element>, Vec<B::Element>) {
 	if l_vec.len() == 1 {
 		return (l_vec[0].clone(), r_vec[0].clone(), vec![], vec![]);
 	}
-	
-	
+
 	let mid = l_vec.len() / 2;
-	let l_left = &l_vec[..mid];
-	let l_right = &l_vec[mid..];
-	let r_left = &r_vec[..mid];  // Fixed: use r_vec instead of l_vec
-	let r_right = &r_vec[mid..]; // Fixed: use r_vec instead of l_vec
-	
+	let (l_left, l_right) = l_vec.split_at(mid);
+	let (r_left, r_right) = r_vec.split_at(mid);
+	let (g_left, g_right) = g_vec.split_at(mid);
+	let (h_left, h_right) = h_vec.split_at(mid);
+
 	let c_L = inner_product(l_left, r_right);
 	let c_R = inner_product(l_right, r_left);
-	
-	// Create commitments to c_L and c_R
-	let r_L = random_bigint(256);
-	let r_R = random_bigint(256);
-	let L = pedersen_commit(g, h, &c_L, &r_L, n);
-	let R = pedersen_commit(g, h, &c_R, &r_R, n);
-	
-	let y = fiat_shamir(&[&L, &R]) % n;
-	
-	let l_new: Vec<BigInt> = l_left.iter().zip(l_right.iter())
-		.map(|(l, r)| l + &(&y * r))
+	let L = backend.multi_exp(&[g_right, h_left, std::slice::from_ref(u)].concat(), &[l_left, r_right, &[c_L]].concat());
+	let R = backend.multi_exp(&[g_left, h_right, std::slice::from_ref(u)].concat(), &[l_right, r_left, &[c_R]].concat());
+
+	let x = backend.challenge(&[P, &L, &R]);
+
+	let l_new: Vec<BigInt> = l_left.iter().zip(l_right)
+		.map(|(l, r)| l + &(&x * r))
 		.collect();
-	let r_new: Vec<BigInt> = r_left.iter().zip(r_right.iter())
-		.map(|(l, r)| r + &(&y * l))
+	let r_new: Vec<BigInt> = r_left.iter().zip(r_right)
+		.map(|(l, r)| &x * l + r)
 		.collect();
-	
-	let (a, b, mut L_vec, mut R_vec) = inner_product_argument_recursive(&l_new, &r_new, g, h, n, level + 1);
-	
-	// Add current level commitments
-	L_vec.push(L);
-	R_vec.push(R);
-	
+	let (g_new, h_new) = fold_ipp_generators(backend, g_vec, h_vec, &x);
+	let P_new = fold_ipp_commitment(backend, P, &L, &R, &x);
+
+	let (a, b, L_rest, R_rest) = inner_product_argument_recursive(&l_new, &r_new, &g_new, &h_new, u, &P_new, backend);
+
+	// Current level first, deeper levels after
+	let L_vec = std::iter::once(L).chain(L_rest).collect();
+	let R_vec = std::iter::once(R).chain(R_rest).collect();
 	(a, b, L_vec, R_vec)
 }
 
+/// Prove an inner product argument for l, r under `gens`
+/// - params: backend, generators of length l.len() (a power of two), witness vectors l and r
+/// - returns: IPPProof; the statement is P = `ipp_commitment(backend, gens, l, r)`
+/// - usage: pair with `verify_inner_product`
+#[allow(non_snake_case)]
+pub fn prove_inner_product<B: GroupBackend<Element = BigInt>>(backend: &B, gens: &IppGenerators<BigInt>, l_vec: &[BigInt], r_vec: &[BigInt]) -> IPPProof {
+	assert!(l_vec.len() == r_vec.len() && l_vec.len() == gens.g_vec.len(), "prove_inner_product: length mismatch");
+	let P = ipp_commitment(backend, gens, l_vec, r_vec);
+	let (a, b, L, R) = inner_product_argument_recursive(l_vec, r_vec, &gens.g_vec, &gens.h_vec, &gens.u, &P, backend);
+	IPPProof { L, R, a, b }
+}
+
+/// Vector length of the interactive protocol's l/r witnesses
+const INTERACTIVE_DIMENSION: usize = 16;
+
+/// Largest (t_hat, mu, tau_x) an honest interactive prover can send
+/// - params: dimension of l/r, modulus n (x, the blindings and the witnesses d_i are below n)
+/// - returns: l_i = d_i + sL_i x < 2^257 n gives t_hat < dimension (2^257 n)^2;
+///   mu = alpha + rho x < 2 n^2; tau_x = tau2 x^2 + tau1 x < 2 n^3
+fn interactive_response_bounds(dimension: usize, n: &BigInt) -> (BigInt, BigInt, BigInt) {
+	let component = n << 257u32;
+	let t_hat = &component * &component * dimension;
+	let mu = n * n * 2u32;
+	let tau_x = n * n * n * 2u32;
+	(t_hat, mu, tau_x)
+}
+
 // Interactive Proof Protocol Implementation
 pub fn interactive_prove_step1(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> (ProverState, BigInt, BigInt) {
+	interactive_prove_step1_with_rng(v, r, a, b, g, h, n, &mut OsRng)
+}
+
+/// Same as `interactive_prove_step1`, drawing every prover nonce (α, ρ, sL, sR, τ1, τ2) from `rng`,
+/// e.g. a seeded StdRng in tests or a hardware RNG. The C_v1/C_v2 blindings are derived from r
+/// (see `CommitmentBlindings`) so step 3 commits to the same values
+#[allow(clippy::too_many_arguments)]
+pub fn interactive_prove_step1_with_rng<R: RngCore + ?Sized>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt, rng: &mut R) -> (ProverState, BigInt, BigInt) {
 	// Fixed optimal dimension for interactive protocol
-	let dimension = 16;
+	let dimension = INTERACTIVE_DIMENSION;
 	
 	// Step 1: Calculate v1 and v2
-	let v1 = 4 * v - 4 * a + 1;
-	let v2 = 4 * b - 4 * v + 1;
+	let (v1, v2) = expected_auxiliary(v, a, b);
 
 	// Step 2: Find six integers d = (d1, d2, d3, d4, d5, d6) using Lagrange's theorem
-	let d1 = find_3_squares(&v1);  // v1 = d1² + d2² + d3²
-	let d2 = find_3_squares(&v2);  // v2 = d4² + d5² + d6²
-	let d_base = [d1, d2].concat(); // length 6
-
-	// Expand d to the fixed dimension by repeating the base pattern
-	let d = (0..dimension)
-		.map(|i| d_base[i % d_base.len()].clone())
-		.collect::<Vec<_>>();
+	// v1 = d1² + d2² + d3², v2 = d4² + d5² + d6²
+	// padded with zeros to the fixed dimension, as in the non-interactive prover
+	let d = padded_range_witness(&v1, &v2, dimension).expect("v must lie in [a, b]");
 
 	// Step 3: Create Pedersen commitment A for values d with random value α
-	let alpha = random_bigint(256);
+	let alpha = random_bigint_mod_from(rng, n);
 	let A = pedersen_commit(g, h, &d.iter().sum::<BigInt>(), &alpha, n);
 
 	// Step 4: Create commitment S using values sL and sR
-	let rho = random_bigint(256);
-	let sL = (0..dimension).map(|_| random_bigint(256)).collect::<Vec<_>>();
-	let sR = (0..dimension).map(|_| random_bigint(256)).collect::<Vec<_>>();
+	let rho = random_bigint_mod_from(rng, n);
+	let sL = (0..dimension).map(|_| random_bigint_from(rng, 256)).collect::<Vec<_>>();
+	let sR = (0..dimension).map(|_| random_bigint_from(rng, 256)).collect::<Vec<_>>();
 	let sum_s = sL.iter().sum::<BigInt>() + sR.iter().sum::<BigInt>();
 	let S = pedersen_commit(g, h, &sum_s, &rho, n);
 
-	// Create commitments to v, v1, v2
-	let (C, _r_v) = commit_value(g, h, v, n);
-	let (C_v1, _r_v1) = commit_value(g, h, &v1, n);
-	let (C_v2, _r_v2) = commit_value(g, h, &v2, n);
+	// Blindings of C, C_v1, C_v2; step 3 commits with exactly these
+	let blindings = CommitmentBlindings::new(r);
 
 	// Calculate l0 and r0 for later use
 	let l0 = d.iter().map(|di| di.clone()).collect::<Vec<_>>();
@@ -166,12 +709,12 @@ pub fn interactive_prove_step1(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g
 		+ r0.iter().zip(&sL).map(|(r0i, sLi)| r0i * sLi).sum::<BigInt>();
 	let t2 = inner_product(&sL, &sR);
 
-	let tau1 = random_bigint(256);
-	let tau2 = random_bigint(256);
+	let tau1 = random_bigint_mod_from(rng, n);
+	let tau2 = random_bigint_mod_from(rng, n);
 
 	let prover_state = ProverState {
 		v: v.clone(), a: a.clone(), b: b.clone(), r: r.clone(),
-		alpha, rho, sL, sR, d, v1, v2, l0, r0, t0, t1, t2, tau1, tau2,
+		alpha, rho, sL, sR, d, v1, v2, l0, r0, t0, t1, t2, tau1, tau2, blindings,
 	};
 
 	(prover_state, A, S)
@@ -201,19 +744,12 @@ pub fn interactive_prove_step3(prover_state: &ProverState, x: &BigInt, g: &BigIn
 	let tau_x = &prover_state.tau2 * x * x + &prover_state.tau1 * x;
 
 	// Generate IPP proof for l_vec and r_vec
-	let (a_final, b_final, L_vec, R_vec) = inner_product_argument_recursive(&l_vec, &r_vec, g, h, n, 0);
-	
-			let ipp_proof = IPPProof {
-			L: L_vec,
-			R: R_vec,
-			a: a_final.clone(),
-			b: b_final.clone(),
-		};
+	let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+	let ipp_proof = prove_inner_product(&backend, &IppGenerators::new(&backend, l_vec.len()), &l_vec, &r_vec);
 
 	// Create final proof
-	let C = pedersen_commit(g, h, &prover_state.v, &prover_state.r, n);
-	let C_v1 = pedersen_commit(g, h, &prover_state.v1, &random_bigint(256), n);
-	let C_v2 = pedersen_commit(g, h, &prover_state.v2, &random_bigint(256), n);
+	let C = pedersen_commit(g, h, &prover_state.v, &prover_state.blindings.r, n);
+	let (C_v1, C_v2) = range_commitments(&backend, &prover_state.v1, &prover_state.v2, &prover_state.blindings);
 
 	let final_proof = Cuproof {
 		A: BigInt::from(0), // Will be set by caller
@@ -234,11 +770,11 @@ pub fn interactive_prove_step3(prover_state: &ProverState, x: &BigInt, g: &BigIn
 		ipp_proof,
 	};
 
-	(t_hat, mu, tau_x, a_final, b_final)
+	(t_hat, mu, tau_x, final_proof.ipp_proof.a.clone(), final_proof.ipp_proof.b.clone())
 }
 
 // Interactive Verification Protocol
-pub fn interactive_verify_step1(g: &BigInt, h: &BigInt, n: &BigInt) -> (VerifierState, BigInt, BigInt) {
+pub fn interactive_verify_step1<'a>(g: &'a BigInt, h: &'a BigInt, n: &'a BigInt) -> (VerifierState<'a>, BigInt, BigInt) {
 	// Step 6: Verifier chooses natural values y', z' and computes y = g^(y'), z = g^(z')
 	let y_prime = random_bigint(256);
 	let z_prime = random_bigint(256);
@@ -246,27 +782,27 @@ pub fn interactive_verify_step1(g: &BigInt, h: &BigInt, n: &BigInt) -> (Verifier
 	let z = g.modpow(&z_prime, n);
 
 	let verifier_state = VerifierState {
-		g: g.clone(), h: h.clone(), n: n.clone(),
-		A: BigInt::from(0), S: BigInt::from(0), T1: BigInt::from(0), T2: BigInt::from(0),
+		g: Cow::Borrowed(g), h: Cow::Borrowed(h), n: Cow::Borrowed(n),
+		A: Cow::Owned(BigInt::from(0)), S: Cow::Owned(BigInt::from(0)), T1: Cow::Owned(BigInt::from(0)), T2: Cow::Owned(BigInt::from(0)),
 		y: y.clone(), z: z.clone(), x: BigInt::from(0),
 	};
 
 	(verifier_state, y, z)
 }
 
-pub fn interactive_verify_step2(verifier_state: &mut VerifierState, A: &BigInt, S: &BigInt) {
+pub fn interactive_verify_step2<'a>(verifier_state: &mut VerifierState<'a>, A: &'a BigInt, S: &'a BigInt) {
 	// Step 5: Verifier receives commitments A and S from Prover
-	verifier_state.A = A.clone();
-	verifier_state.S = S.clone();
+	verifier_state.A = Cow::Borrowed(A);
+	verifier_state.S = Cow::Borrowed(S);
 }
 
-pub fn interactive_verify_step3(verifier_state: &mut VerifierState, T1: &BigInt, T2: &BigInt) {
+pub fn interactive_verify_step3<'a>(verifier_state: &mut VerifierState<'a>, T1: &'a BigInt, T2: &'a BigInt) {
 	// Step 9: Verifier receives T1 and T2 from Prover
-	verifier_state.T1 = T1.clone();
-	verifier_state.T2 = T2.clone();
+	verifier_state.T1 = Cow::Borrowed(T1);
+	verifier_state.T2 = Cow::Borrowed(T2);
 }
 
-pub fn interactive_verify_step4(verifier_state: &mut VerifierState, g: &BigInt, n: &BigInt) -> BigInt {
+pub fn interactive_verify_step4(verifier_state: &mut VerifierState<'_>, g: &BigInt, n: &BigInt) -> BigInt {
 	// Step 10: Verifier chooses natural value x' and computes x = g^(x')
 	let x_prime = random_bigint(256);
 	let x = g.modpow(&x_prime, n);
@@ -274,14 +810,14 @@ pub fn interactive_verify_step4(verifier_state: &mut VerifierState, g: &BigInt,
 	x
 }
 
-pub fn interactive_verify_final(verifier_state: &VerifierState, t_hat: &BigInt, mu: &BigInt, tau_x: &BigInt, a_final: &BigInt, b_final: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+pub fn interactive_verify_final(verifier_state: &VerifierState<'_>, t_hat: &BigInt, mu: &BigInt, tau_x: &BigInt, a_final: &BigInt, b_final: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
 	// Step 12: Verifier performs verification checks
 	
 	// Check 1: Verify that commitments A and S are not zero (basic validation)
-	if verifier_state.A == BigInt::from(0) || verifier_state.S == BigInt::from(0) { return false; }
+	if verifier_state.A.is_zero() || verifier_state.S.is_zero() { return false; }
 	
 	// Check 2: Verify that T1 and T2 are not zero (basic validation)
-	if verifier_state.T1 == BigInt::from(0) || verifier_state.T2 == BigInt::from(0) { return false; }
+	if verifier_state.T1.is_zero() || verifier_state.T2.is_zero() { return false; }
 	
 	// Check 3: Verify that challenges y, z, x are not zero
 	if verifier_state.y == BigInt::from(0) || verifier_state.z == BigInt::from(0) || verifier_state.x == BigInt::from(0) { return false; }
@@ -291,17 +827,13 @@ pub fn interactive_verify_final(verifier_state: &VerifierState, t_hat: &BigInt,
 	if a_final == &BigInt::from(0) || b_final == &BigInt::from(0) { return false; }
 	
 	// Check 5: Verify polynomial relationship t(x) = <l(x), r(x)>
-	// For dimension 64, we expect t_hat to be a reasonable value
 	// This is a simplified check - in a real implementation, we would verify the full polynomial
 	
-	// Check 6: Verify that t_hat is within reasonable bounds
-	// Since t_hat = <l_vec, r_vec> where l_vec and r_vec are 64-dimensional vectors
-	// Each component is typically small (from 3-squares), so t_hat should not be extremely large
-	let max_expected = BigInt::from(1000000u64); // Reasonable upper bound for demo
-	if t_hat > &max_expected { return false; }
-	
-	// Check 7: Verify that mu and tau_x are reasonable
-	if mu > &max_expected || tau_x > &max_expected { return false; }
+	// Check 6: Verify that t_hat, mu and tau_x lie within what an honest prover can produce
+	// for this dimension and modulus (x and the blindings are all below n)
+	let (t_hat_max, mu_max, tau_x_max) = interactive_response_bounds(INTERACTIVE_DIMENSION, n);
+	if t_hat.is_negative() || t_hat > &t_hat_max { return false; }
+	if mu.is_negative() || mu > &mu_max || tau_x.is_negative() || tau_x > &tau_x_max { return false; }
 	
 	// For a complete implementation, we would also verify:
 	// - The commitment relationships for T1 and T2
@@ -311,91 +843,306 @@ pub fn interactive_verify_final(verifier_state: &VerifierState, t_hat: &BigInt,
 	true
 }
 
+/// Fiat–Shamir challenges (y, z, x) of one non-interactive proof
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Challenges {
+	pub y: BigInt,
+	pub z: BigInt,
+	pub x: BigInt,
+}
+
 // Original non-interactive proof (kept for compatibility)
+/// Panics if v is outside [a, b] or dimension cannot hold the witness; `try_cuproof_prove_with_dimension`
+/// returns the `ProveError` instead.
 pub fn cuproof_prove_with_dimension(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt, dimension: usize) -> Cuproof {
-	let v1 = 4 * v - 4 * a + 1;
-	let v2 = 4 * b - 4 * v + 1;
-
-	// Use 3-squares for numbers of the form 4x+1
-	let d1 = find_3_squares(&v1);
-	let d2 = find_3_squares(&v2);
-	let d_base = [d1, d2].concat(); // length 6
-
-	// Expand d to the requested dimension by repeating the base pattern
-	let d = (0..dimension)
-		.map(|i| d_base[i % d_base.len()].clone())
-		.collect::<Vec<_>>();
-
-	// Create commitments to v, v1, v2
-	let (C, _r_v) = commit_value(g, h, v, n);
-	let (C_v1, _r_v1) = commit_value(g, h, &v1, n);
-	let (C_v2, _r_v2) = commit_value(g, h, &v2, n);
-
-	let alpha = random_bigint(256);
-	let rho = random_bigint(256);
-	let sL = (0..dimension).map(|_| random_bigint(256)).collect::<Vec<_>>();
-	let sR = (0..dimension).map(|_| random_bigint(256)).collect::<Vec<_>>();
-
-	// Commit A and S (demo-style, sum-based)
-	let sum_d = d.iter().sum();
-	let A = pedersen_commit(g, h, &sum_d, &alpha, n);
-	let sum_s = sL.iter().sum::<BigInt>() + sR.iter().sum::<BigInt>();
-	let S = pedersen_commit(g, h, &sum_s, &rho, n);
+	try_cuproof_prove_with_dimension(v, r, a, b, g, h, n, dimension).expect("v must lie in [a, b]")
+}
 
-	// Fiat–Shamir challenges
-	let y = fiat_shamir(&[&A, &S, &C, &C_v1, &C_v2]) % n;
-	let z = fiat_shamir(&[&y]) % n;
+/// Same as `cuproof_prove_with_dimension`, returning an error instead of panicking
+#[allow(clippy::too_many_arguments)]
+pub fn try_cuproof_prove_with_dimension(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt, dimension: usize) -> Result<Cuproof, ProveError> {
+	cuproof_prove_with_challenges::<DefaultChallengeHasher, _>(v, r, a, b, g, h, n, dimension, &mut OsRng).map(|(proof, _)| proof)
+}
 
-	// l0 = z*d + y ; r0 = z*d + y
-	let l0 = d.iter().map(|di| &z * di + &y).collect::<Vec<_>>();
-	let r0 = d.iter().map(|di| &z * di + &y).collect::<Vec<_>>();
+/// Same as `cuproof_prove`, but returns an error instead of panicking when v1 or v2 has
+/// no three-square witness (v outside [a, b])
+pub fn cuproof_prove_checked(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<Cuproof, ProveError> {
+	try_cuproof_prove_with_dimension(v, r, a, b, g, h, n, 64)
+}
 
-	// Coefficients of t(x) = <l(x), r(x)> = t0 + t1 x + t2 x^2
-	let t0 = inner_product(&l0, &r0);
-	let t1 = l0.iter().zip(&sR).map(|(l0i, sRi)| l0i * sRi).sum::<BigInt>()
-		+ r0.iter().zip(&sL).map(|(r0i, sLi)| r0i * sLi).sum::<BigInt>();
-	let t2 = inner_product(&sL, &sR);
+/// Public range [0, 2^k - 1] of a k-bit statement
+pub fn bit_range(k: u32) -> (BigInt, BigInt) {
+	(BigInt::zero(), (BigInt::from(1) << k) - 1)
+}
 
-	// Commit T1 = Commit(t1, tau1), T2 = Commit(t2, tau2)
-	let tau1 = random_bigint(256);
-	let tau2 = random_bigint(256);
-	let T1 = pedersen_commit(g, h, &t1, &tau1, n);
-	let T2 = pedersen_commit(g, h, &t2, &tau2, n);
+/// Prove that v fits in k bits, i.e. 0 <= v < 2^k (the classic Bulletproofs statement)
+/// - params: v, r blinding of C, k bit width (>= 1), g/h/n public parameters
+/// - returns: Cuproof for [0, 2^k - 1]; V2Decomposition if v >= 2^k, InvalidRange if k = 0
+/// - usage: verify with `verify_bits(proof, k, ..)`; with a = 0, v1 = 4v + 1 needs no shift
+pub fn prove_bits(v: &BigInt, r: &BigInt, k: u32, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<Cuproof, ProveError> {
+	if k == 0 { return Err(ProveError::InvalidRange); }
+	if !value_fits_bits(v, k) {
+		let e = SquaresError::Negative;
+		return Err(if v.is_negative() { ProveError::V1Decomposition(e) } else { ProveError::V2Decomposition(e) });
+	}
+	let (a, b) = bit_range(k);
+	cuproof_prove_checked(v, r, &a, &b, g, h, n)
+}
 
-	// Challenge x
-	let x = fiat_shamir(&[&T1, &T2]) % n;
+/// Same as `cuproof_prove`, drawing every prover nonce (α, ρ, sL, sR, τ1, τ2) from `rng`. With a seeded RNG and a fixed r the proof is reproducible;
+/// never reuse a seed for two different statements, since that reuses the nonces.
+///
+/// Panics if v is outside [a, b]; `try_cuproof_prove_with_rng` returns the `ProveError` instead.
+#[allow(clippy::too_many_arguments)]
+pub fn cuproof_prove_with_rng<R: RngCore + ?Sized>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt, rng: &mut R) -> Cuproof {
+	try_cuproof_prove_with_rng(v, r, a, b, g, h, n, rng).expect("v must lie in [a, b]")
+}
 
-	// Evaluate t_hat at x
-	let t_hat = &t0 + &(&t1 * &x) + &(&t2 * &x * &x);
+/// Same as `cuproof_prove_with_rng`, returning an error instead of panicking
+#[allow(clippy::too_many_arguments)]
+pub fn try_cuproof_prove_with_rng<R: RngCore + ?Sized>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt, rng: &mut R) -> Result<Cuproof, ProveError> {
+	cuproof_prove_with_challenges::<DefaultChallengeHasher, R>(v, r, a, b, g, h, n, 64, rng).map(|(proof, _)| proof)
+}
+
+/// Same as `cuproof_prove`, deriving every Fiat–Shamir challenge with `H` instead of SHA-256.
+/// Verify with `cuproof_verify_with_hasher::<H>`.
+///
+/// Panics if v is outside [a, b]; `try_cuproof_prove_with_hasher` returns the `ProveError` instead.
+pub fn cuproof_prove_with_hasher<H: ChallengeHasher>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Cuproof {
+	try_cuproof_prove_with_hasher::<H>(v, r, a, b, g, h, n).expect("v must lie in [a, b]")
+}
 
-	// Aggregate blinding terms (demo-style): μ = α + ρ x ; τx = τ2 x^2 + τ1 x
-	let mu = &alpha + &(&rho * &x);
-	let tau_x = &tau2 * &x * &x + &tau1 * &x;
+/// Same as `cuproof_prove_with_hasher`, returning an error instead of panicking
+pub fn try_cuproof_prove_with_hasher<H: ChallengeHasher>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<Cuproof, ProveError> {
+	cuproof_prove_with_challenges::<H, _>(v, r, a, b, g, h, n, 64, &mut OsRng).map(|(proof, _)| proof)
+}
 
-	// Generate IPP proof for l_vec and r_vec
-	let l_vec = l0.iter().zip(&sL).map(|(l0i, sLi)| l0i + &(sLi * &x)).collect::<Vec<_>>();
-	let r_vec = r0.iter().zip(&sR).map(|(r0i, sRi)| r0i + &(sRi * &x)).collect::<Vec<_>>();
-	
-	let (a_final, b_final, L_vec, R_vec) = inner_product_argument_recursive(&l_vec, &r_vec, g, h, n, 0);
-	
-	let ipp_proof = IPPProof {
-		L: L_vec,
-		R: R_vec,
-		a: a_final,
-		b: b_final,
+/// Same as `cuproof_prove`, with every commitment and challenge computed by `backend`
+///
+/// Panics if v is outside [a, b]; `try_cuproof_prove_with_backend` returns the `ProveError` instead.
+pub fn cuproof_prove_with_backend<B: GroupBackend<Element = BigInt>>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, backend: &B) -> Cuproof {
+	try_cuproof_prove_with_backend(v, r, a, b, backend).expect("v must lie in [a, b]")
+}
+
+/// Same as `cuproof_prove_with_backend`, returning an error instead of panicking
+pub fn try_cuproof_prove_with_backend<B: GroupBackend<Element = BigInt>>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, backend: &B) -> Result<Cuproof, ProveError> {
+	prove_with_backend(v, r, a, b, backend, 64, &mut OsRng).map(|(proof, _)| proof)
+}
+
+/// Same as `cuproof_prove_with_dimension`, also returning the transcript challenges so a
+/// caller can verify without recomputing Fiat–Shamir
+pub(crate) fn cuproof_prove_with_challenges<H: ChallengeHasher, R: RngCore + ?Sized>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt, dimension: usize, rng: &mut R) -> Result<(Cuproof, Challenges), ProveError> {
+	prove_with_backend(v, r, a, b, &RsaBackend::<H>::new(g, h, n), dimension, rng)
+}
+
+/// Non-interactive prover over any group backend whose elements are BigInts
+fn prove_with_backend<B: GroupBackend<Element = BigInt>, R: RngCore + ?Sized>(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, backend: &B, dimension: usize, rng: &mut R) -> Result<(Cuproof, Challenges), ProveError> {
+	// Out-of-range values fail before any witness search, with the side that went negative
+	if !value_in_range(v, a, b) {
+		let e = SquaresError::Negative;
+		return Err(if v < a { ProveError::V1Decomposition(e) } else { ProveError::V2Decomposition(e) });
+	}
+	let (v1, v2) = expected_auxiliary(v, a, b);
+
+	// Use 3-squares for numbers of the form 4x+1, padded with zeros to the requested dimension
+	let d = padded_range_witness(&v1, &v2, dimension)?;
+	Ok(prove_committed(backend, v, &v1, &v2, &d, r, rng))
+}
+
+/// Proof that d's blocks open C_v1 = Commit(v1, 4r) and C_v2 = Commit(v2, -4r), for any v1, v2, d
+///
+/// `prove_with_backend` passes the auxiliary values of v and their witness; anything else builds
+/// a proof the verifier must reject, which is what the soundness tests need.
+#[allow(non_snake_case)]
+pub(crate) fn prove_committed<B: GroupBackend<Element = BigInt>, R: RngCore + ?Sized>(backend: &B, v: &BigInt, v1: &BigInt, v2: &BigInt, d: &[BigInt], r: &BigInt, rng: &mut R) -> (Cuproof, Challenges) {
+	// Create commitments to v, v1, v2 (C uses the caller's blinding r so it can be opened).
+	// C_v1, C_v2 are derived from C so the verifier can bind them to [a, b]:
+	// C_v1 = C^4 g^(1-4a) and C_v1 C_v2 = g^(4(b-a)+2), see `range_binding_holds`
+	let blindings = CommitmentBlindings::new(r);
+	let C = backend.commit(v, &blindings.r);
+	let (C_v1, C_v2) = range_commitments(backend, v1, v2, &blindings);
+
+	// Block 0 (the squares of v1) opens C_v1, block 1 (the squares of v2 and the padding) C_v2
+	let blocks = range_blocks(1, d.len(), d.len());
+	let p = prove_range_relation(backend, d, &blocks, &[blindings.r_v1.clone(), blindings.r_v2.clone()],
+		|A, S| backend.challenge(&[A, S, &C, &C_v1, &C_v2]), rng);
+
+	let proof = Cuproof {
+		A: p.A, S: p.S, T1: p.T1, T2: p.T2, tau_x: p.tau_x, mu: p.mu, t_hat: p.t_hat, C, C_v1, C_v2,
+		t0: p.t0, t1: p.t1, t2: p.t2, tau1: p.tau1, tau2: p.tau2, ipp_proof: p.ipp_proof,
 	};
+	(proof, p.challenges)
+}
 
-	Cuproof {
-		A, S, T1, T2, tau_x, mu, t_hat, C, C_v1, C_v2, t0, t1, t2, tau1, tau2, ipp_proof,
+/// Prover RNG seeded from a secret key and the full statement (RFC 6979-style)
+///
+/// The seed is SHA-256 over a domain tag and (secret_key, v, a, b, g, h, n), so the same
+/// inputs always give the same nonces and a different statement under the same key gives
+/// unrelated ones. Keep the key secret: anyone who knows it can recompute α, ρ, τ1, τ2.
+pub fn deterministic_nonce_rng(secret_key: &BigInt, v: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> StdRng {
+	let mut hasher = Sha256::new();
+	hasher.update(b"cuproof-deterministic-nonce");
+	for x in [secret_key, v, a, b, g, h, n] {
+		let (_sign, bytes) = x.to_bytes_be();
+		hasher.update((bytes.len() as u64).to_be_bytes());
+		hasher.update(&bytes);
+	}
+	StdRng::from_seed(hasher.finalize().into())
+}
+
+/// Blinding r of C that `ProofBuilder::deterministic` derives when no `blinding` is set
+/// - params: same as `deterministic_nonce_rng`
+/// - returns: the first 256-bit draw of that RNG
+/// - usage: recover r to open C = g^v h^r later
+pub fn deterministic_blinding(secret_key: &BigInt, v: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> BigInt {
+	random_bigint_from(&mut deterministic_nonce_rng(secret_key, v, a, b, g, h, n), 256)
+}
+
+/// Named-field alternative to the positional `cuproof_prove` arguments
+///
+/// ```ignore
+/// let proof = ProofBuilder::new()
+///     .value(&v).bounds(1..=100).params(&g, &h, &n).blinding(&r)
+///     .build()?;
+/// ```
+/// value, range (via `range` or `bounds`), params and blinding are required;
+/// dimension defaults to 64. With `deterministic(key)` the blinding is optional and
+/// every nonce comes from `deterministic_nonce_rng` instead of `OsRng`.
+#[derive(Clone, Debug, Default)]
+pub struct ProofBuilder {
+	value: Option<BigInt>,
+	range: Option<(BigInt, BigInt)>,
+	params: Option<(BigInt, BigInt, BigInt)>,
+	blinding: Option<BigInt>,
+	dimension: Option<usize>,
+	deterministic_key: Option<BigInt>,
+	range_error: bool,
+}
+
+impl ProofBuilder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn value(mut self, v: &BigInt) -> Self {
+		self.value = Some(v.clone());
+		self
+	}
+
+	/// Inclusive range [a, b]
+	pub fn range(mut self, a: &BigInt, b: &BigInt) -> Self {
+		self.range = Some((a.clone(), b.clone()));
+		self.range_error = false;
+		self
+	}
+
+	/// Range from Rust range syntax, e.g. `1..=100` or `1..101`; unbounded ends are rejected at `build()`
+	pub fn bounds<T: Clone + Into<BigInt>, B: RangeBounds<T>>(mut self, bounds: B) -> Self {
+		let start = match bounds.start_bound() {
+			Bound::Included(x) => Some(x.clone().into()),
+			Bound::Excluded(x) => Some(x.clone().into() + 1),
+			Bound::Unbounded => None,
+		};
+		let end = match bounds.end_bound() {
+			Bound::Included(x) => Some(x.clone().into()),
+			Bound::Excluded(x) => Some(x.clone().into() - 1),
+			Bound::Unbounded => None,
+		};
+		match (start, end) {
+			(Some(a), Some(b)) => { self.range = Some((a, b)); self.range_error = false; }
+			_ => { self.range = None; self.range_error = true; }
+		}
+		self
+	}
+
+	pub fn params(mut self, g: &BigInt, h: &BigInt, n: &BigInt) -> Self {
+		self.params = Some((g.clone(), h.clone(), n.clone()));
+		self
+	}
+
+	/// Blinding r of C = g^v h^r; keep it to open C later
+	pub fn blinding(mut self, r: &BigInt) -> Self {
+		self.blinding = Some(r.clone());
+		self
+	}
+
+	pub fn dimension(mut self, d: usize) -> Self {
+		self.dimension = Some(d);
+		self
+	}
+
+	/// Derive every nonce (and the blinding, unless set) from `secret_key` and the statement,
+	/// so the same inputs always build the same proof; see `deterministic_nonce_rng`
+	pub fn deterministic(mut self, secret_key: &BigInt) -> Self {
+		self.deterministic_key = Some(secret_key.clone());
+		self
+	}
+
+	pub fn build(&self) -> Result<Cuproof, ProveError> {
+		let v = self.value.as_ref().ok_or(ProveError::MissingField("value"))?;
+		if self.range_error { return Err(ProveError::InvalidRange); }
+		let (a, b) = self.range.as_ref().ok_or(ProveError::MissingField("range"))?;
+		let (g, h, n) = self.params.as_ref().ok_or(ProveError::MissingField("params"))?;
+		if self.deterministic_key.is_none() && self.blinding.is_none() { return Err(ProveError::MissingField("blinding")); }
+		let dimension = self.dimension.unwrap_or(64);
+		if a >= b { return Err(ProveError::InvalidRange); }
+		if !dimension.is_power_of_two() { return Err(ProveError::InvalidDimension(dimension)); }
+		let result = match &self.deterministic_key {
+			Some(key) => {
+				let mut rng = deterministic_nonce_rng(key, v, a, b, g, h, n);
+				// First draw is the blinding, matching `deterministic_blinding`
+				let derived = random_bigint_from(&mut rng, 256);
+				let r = self.blinding.as_ref().unwrap_or(&derived);
+				cuproof_prove_with_challenges::<DefaultChallengeHasher, _>(v, r, a, b, g, h, n, dimension, &mut rng)
+			}
+			None => {
+				let r = self.blinding.as_ref().expect("checked above");
+				cuproof_prove_with_challenges::<DefaultChallengeHasher, _>(v, r, a, b, g, h, n, dimension, &mut OsRng)
+			}
+		};
+		result.map(|(proof, _)| proof)
 	}
 }
 
 // Backward-compatible wrapper that defaults to larger dimension for IPP
+/// Panics if v is outside [a, b]; `cuproof_prove_checked` returns the `ProveError` instead.
 pub fn cuproof_prove(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Cuproof {
 	// Use larger dimension to ensure enough recursion levels for IPP
 	cuproof_prove_with_dimension(v, r, a, b, g, h, n, 64) // Reduced from 1024 to 64
 }
 
+/// Endless stream of proofs under (g, h, n), built one `cuproof_prove` call per `next()`
+///
+/// ```ignore
+/// let proofs = ProofStream::new(&g, &h, &n, |i| (BigInt::from(i), random_bigint(256), a.clone(), b.clone()));
+/// for proof in proofs.take(3) { /* ... */ }
+/// ```
+/// The closure receives the step index and returns (v, r, a, b); like `cuproof_prove`, `next()`
+/// panics if v is outside [a, b]. Bound the stream with `take` or another adapter.
+pub struct ProofStream<F> {
+	g: BigInt,
+	h: BigInt,
+	n: BigInt,
+	statement: F,
+	step: usize,
+}
+
+impl<F: FnMut(usize) -> (BigInt, BigInt, BigInt, BigInt)> ProofStream<F> {
+	pub fn new(g: &BigInt, h: &BigInt, n: &BigInt, statement: F) -> Self {
+		ProofStream { g: g.clone(), h: h.clone(), n: n.clone(), statement, step: 0 }
+	}
+}
+
+impl<F: FnMut(usize) -> (BigInt, BigInt, BigInt, BigInt)> Iterator for ProofStream<F> {
+	type Item = Cuproof;
+
+	fn next(&mut self) -> Option<Cuproof> {
+		let (v, r, a, b) = (self.statement)(self.step);
+		self.step += 1;
+		Some(cuproof_prove(&v, &r, &a, &b, &self.g, &self.h, &self.n))
+	}
+}
+
 fn bigint_size_bytes(x: &BigInt) -> usize {
 	let (_sign, bytes) = x.to_bytes_be();
 	bytes.len()
@@ -428,6 +1175,17 @@ pub fn proof_size_bytes(proof: &Cuproof) -> usize {
 	sum
 }
 
+/// Same count as `proof_size_bytes` over the fields a `MinimalCuproof` keeps
+pub fn minimal_proof_size_bytes(proof: &MinimalCuproof) -> usize {
+	[&proof.A, &proof.S, &proof.tau_x, &proof.mu, &proof.C, &proof.C_v1, &proof.C_v2,
+		&proof.t0, &proof.t1, &proof.t2, &proof.tau1, &proof.tau2, &proof.ipp_proof.a, &proof.ipp_proof.b]
+		.into_iter()
+		.chain(&proof.ipp_proof.L)
+		.chain(&proof.ipp_proof.R)
+		.map(bigint_size_bytes)
+		.sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -452,6 +1210,256 @@ mod tests {
         assert_eq!(proof.ipp_proof.L.len(), proof.ipp_proof.R.len());
         assert!(proof.ipp_proof.L.len() > 0);
     }
+
+    // Purpose: ipp_depth reports log2 of the dimension the proof was built at
+    // Params: small demo range, dimension 64
+    // Output: depth 6, matching dimension() = 64
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    fn ipp_depth_matches_dimension() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove_with_dimension(&v, &random_bigint(128), &a, &b, &g, &h, &n, 64);
+        assert_eq!(proof.ipp_depth(), 6);
+        assert_eq!(proof.dimension(), Some(64));
+    }
+
+    // Purpose: ProofBuilder builds the same statement as cuproof_prove and names missing fields
+    // Params: small demo range via range() and bounds(), random r
+    // Output: verifying proof; MissingField / InvalidRange / InvalidDimension errors
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    fn proof_builder_complete_and_missing_fields() {
+        use crate::verify::cuproof_verify_with_range;
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let r = random_bigint(128);
+
+        let proof = ProofBuilder::new().value(&v).bounds(1..=100).params(&g, &h, &n).blinding(&r).build().unwrap();
+        assert!(cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b));
+        assert_eq!(proof.C, pedersen_commit(&g, &h, &v, &r, &n));
+
+        let full = ProofBuilder::new().value(&v).range(&a, &b).params(&g, &h, &n).blinding(&r);
+        assert!(full.build().is_ok());
+        assert_eq!(ProofBuilder::new().range(&a, &b).params(&g, &h, &n).blinding(&r).build().unwrap_err(), ProveError::MissingField("value"));
+        assert_eq!(ProofBuilder::new().value(&v).params(&g, &h, &n).blinding(&r).build().unwrap_err(), ProveError::MissingField("range"));
+        assert_eq!(ProofBuilder::new().value(&v).range(&a, &b).blinding(&r).build().unwrap_err(), ProveError::MissingField("params"));
+        assert_eq!(ProofBuilder::new().value(&v).range(&a, &b).params(&g, &h, &n).build().unwrap_err(), ProveError::MissingField("blinding"));
+
+        assert_eq!(full.clone().range(&b, &a).build().unwrap_err(), ProveError::InvalidRange);
+        assert_eq!(full.clone().bounds(1..).build().unwrap_err(), ProveError::InvalidRange);
+        assert_eq!(full.clone().dimension(48).build().unwrap_err(), ProveError::InvalidDimension(48));
+        assert!(matches!(full.value(&BigInt::from(0)).build(), Err(ProveError::V1Decomposition(_))));
+    }
+
+    // Purpose: deterministic mode rebuilds the identical proof from the same inputs
+    // Params: small demo range, fixed secret keys, no explicit blinding
+    // Output: equal proofs that both verify; C opens with deterministic_blinding; another key differs
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    fn deterministic_proofs_are_reproducible() {
+        use crate::verify::cuproof_verify_with_range;
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let key = BigInt::from(0xc0ffee);
+        let builder = ProofBuilder::new().value(&v).range(&a, &b).params(&g, &h, &n).deterministic(&key);
+
+        let first = builder.build().unwrap();
+        let second = builder.build().unwrap();
+        assert_eq!(first, second);
+        assert!(cuproof_verify_with_range(&first, &g, &h, &n, &a, &b));
+        assert!(cuproof_verify_with_range(&second, &g, &h, &n, &a, &b));
+
+        let r = deterministic_blinding(&key, &v, &a, &b, &g, &h, &n);
+        assert_eq!(first.C, pedersen_commit(&g, &h, &v, &r, &n));
+
+        let other = builder.clone().deterministic(&BigInt::from(1)).build().unwrap();
+        assert_ne!(other, first);
+    }
+
+    // Purpose: an injected seeded RNG makes the non-interactive and interactive provers reproducible
+    // Params: fast_test_setup params, StdRng seeds 7 and 8, fixed blinding
+    // Output: identical proofs/states for the same seed, different ones for another seed
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    fn seeded_rng_proofs_are_reproducible() {
+        use crate::verify::cuproof_verify;
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v, r) = (BigInt::from(1), BigInt::from(100), BigInt::from(42), BigInt::from(0xbeef));
+        let prove = |seed| cuproof_prove_with_rng(&v, &r, &a, &b, &g, &h, &n, &mut StdRng::seed_from_u64(seed));
+
+        let first = prove(7);
+        assert_eq!(prove(7), first);
+        assert!(cuproof_verify(&first, &g, &h, &n));
+        assert_ne!(prove(8), first);
+
+        let step1 = |seed| interactive_prove_step1_with_rng(&v, &r, &a, &b, &g, &h, &n, &mut StdRng::seed_from_u64(seed));
+        let ((s1, A1, S1), (s2, A2, S2)) = (step1(7), step1(7));
+        assert_eq!((A1, S1), (A2, S2));
+        assert_eq!((&s1.sL, &s1.sR, &s1.tau1, &s1.tau2), (&s2.sL, &s2.sR, &s2.tau1, &s2.tau2));
+        assert_ne!(step1(8).0.sL, s1.sL);
+    }
+
+    // Purpose: C, C_v1, C_v2 open under one coherent set of blindings in both provers
+    // Params: fast_test_setup params, v = 42 in [1, 100], random r
+    // Output: r_v1 = 4r and r_v2 = -4r; the commitments open to (v, v1, v2) under them and satisfy
+    //         the verifier's linear relations; the interactive state carries the same blindings
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    fn commitment_blindings_follow_v1_v2_combination() {
+        let (g, h, n) = fast_test_setup();
+        let (v, a, b, r) = (BigInt::from(42), BigInt::from(1), BigInt::from(100), random_bigint(128));
+        let blindings = CommitmentBlindings::new(&r);
+        assert!(blindings.is_consistent());
+        assert_eq!(&blindings.r_v1 + &blindings.r_v2, BigInt::zero());
+
+        let proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
+        let (v1, v2) = expected_auxiliary(&v, &a, &b);
+        assert_eq!(proof.C, pedersen_commit(&g, &h, &v, &blindings.r, &n));
+        assert_eq!(proof.C_v1, pedersen_commit(&g, &h, &v1, &blindings.r_v1, &n));
+        assert_eq!(proof.C_v2, pedersen_commit(&g, &h, &v2, &blindings.r_v2, &n));
+        assert!(range_binding_holds(&proof.C, &proof.C_v1, &proof.C_v2, &a, &b, &g, &n));
+
+        let (state, _, _) = interactive_prove_step1(&v, &r, &a, &b, &g, &h, &n);
+        assert_eq!(state.blindings, blindings);
+
+        let skewed = CommitmentBlindings { r_v2: random_bigint(128), ..blindings };
+        assert!(!skewed.is_consistent());
+    }
+
+    // Purpose: a 32-bit interactive transcript verifies even though t_hat is far above 1,000,000
+    // Params: fast_test_setup params, v = 3_000_000_000 in [0, 2^32 - 1]
+    // Output: t_hat/mu/tau_x exceed the old fixed bound; interactive_verify_final accepts
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    #[allow(non_snake_case)]
+    fn interactive_large_range_verifies() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(0), BigInt::from(u32::MAX), BigInt::from(3_000_000_000u64));
+        let (prover, A, S) = interactive_prove_step1(&v, &random_bigint(128), &a, &b, &g, &h, &n);
+        let (mut verifier, y, z) = interactive_verify_step1(&g, &h, &n);
+        interactive_verify_step2(&mut verifier, &A, &S);
+        let (T1, T2) = interactive_prove_step2(&prover, &y, &z, &g, &h, &n);
+        interactive_verify_step3(&mut verifier, &T1, &T2);
+        let x = interactive_verify_step4(&mut verifier, &g, &n);
+        let (t_hat, mu, tau_x, a_final, b_final) = interactive_prove_step3(&prover, &x, &g, &h, &n);
+
+        let old_bound = BigInt::from(1_000_000u64);
+        assert!(t_hat > old_bound && mu > old_bound && tau_x > old_bound);
+        assert!(interactive_verify_final(&verifier, &t_hat, &mu, &tau_x, &a_final, &b_final, &g, &h, &n));
+
+        // Responses beyond what any honest prover could produce are still rejected
+        let (t_hat_max, _, _) = interactive_response_bounds(INTERACTIVE_DIMENSION, &n);
+        assert!(!interactive_verify_final(&verifier, &(t_hat_max + 1), &mu, &tau_x, &a_final, &b_final, &g, &h, &n));
+    }
+
+    // Purpose: the prover uses the balanced witness and still verifies
+    // Params: fast_test_setup params, v = 7 in [0, 7] (v1 = 29 = 2^2 + 0^2 + 5^2 = 4^2 + 3^2 + 2^2)
+    // Output: largest d_i 4 instead of 5; both witnesses have squares summing to v1 + v2, so the
+    //         magnitude of t0 is essentially unchanged; the interactive transcript and a Cuproof verify
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    #[allow(non_snake_case)]
+    fn prover_uses_balanced_witness() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(0), BigInt::from(7), BigInt::from(7));
+        let (v1, v2) = expected_auxiliary(&v, &a, &b);
+        let naive = [find_3_squares(&v1).unwrap(), find_3_squares(&v2).unwrap()].concat();
+        let naive_d = (0..INTERACTIVE_DIMENSION).map(|i| naive[i % naive.len()].clone()).collect::<Vec<_>>();
+
+        let (prover, A, S) = interactive_prove_step1(&v, &random_bigint(128), &a, &b, &g, &h, &n);
+        assert_eq!(prover.d[..3], [4, 3, 2].map(BigInt::from));
+        assert!(prover.d.iter().max() < naive_d.iter().max());
+        assert_eq!(inner_product(&prover.d[..6], &prover.d[..6]), &v1 + &v2);
+        assert_eq!(inner_product(&naive, &naive), &v1 + &v2);
+
+        let (mut verifier, y, z) = interactive_verify_step1(&g, &h, &n);
+        interactive_verify_step2(&mut verifier, &A, &S);
+        let (T1, T2) = interactive_prove_step2(&prover, &y, &z, &g, &h, &n);
+        interactive_verify_step3(&mut verifier, &T1, &T2);
+        let x = interactive_verify_step4(&mut verifier, &g, &n);
+        let (t_hat, mu, tau_x, a_final, b_final) = interactive_prove_step3(&prover, &x, &g, &h, &n);
+        assert!(interactive_verify_final(&verifier, &t_hat, &mu, &tau_x, &a_final, &b_final, &g, &h, &n));
+
+        let proof = cuproof_prove(&v, &random_bigint(128), &a, &b, &g, &h, &n);
+        assert!(crate::verify::cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b));
+    }
+
+    // Purpose: A commits to the zero-padded witness, and the verifier holds each block of it to
+    //         its commitment
+    // Params: fast_test_setup params, v = 42 in [1, 100], seeded StdRng replayed for alpha; the
+    //         six squares repeated over all 64 coordinates as a rejected alternative
+    // Output: <d, d> = v1 + v2 with d[6..] zero; A = h^alpha g_vec^d h_vec^d; the repeated witness
+    //         fails THatCommitmentMismatch; dimension 4 is rejected; the interactive prover's d is
+    //         the same zero-padded witness
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    fn committed_witness_is_padded_not_repeated() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let (v1, v2) = expected_auxiliary(&v, &a, &b);
+        let d = padded_range_witness(&v1, &v2, 64).unwrap();
+        assert_eq!(d.len(), 64);
+        assert!(d[6..].iter().all(Zero::is_zero));
+        assert_eq!(inner_product(&d, &d), &v1 + &v2);
+
+        let backend = RsaBackend::<DefaultChallengeHasher>::new(&g, &h, &n);
+        let (proof, _) = prove_with_backend(&v, &random_bigint(128), &a, &b, &backend, 64, &mut StdRng::seed_from_u64(7)).unwrap();
+        let alpha = random_bigint_mod_from(&mut StdRng::seed_from_u64(7), backend.scalar_modulus());
+        assert_eq!(proof.A, vector_commit(&backend, &IppGenerators::new(&backend, 64), &d, &d, &alpha));
+        assert!(crate::verify::cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b));
+
+        let repeated: Vec<BigInt> = (0..64).map(|i| d[i % 6].clone()).collect();
+        let (proof, _) = prove_committed(&backend, &v, &v1, &v2, &repeated, &random_bigint(128), &mut OsRng);
+        assert_eq!(crate::verify::cuproof_verify_with_range_detailed(&proof, &g, &h, &n, &a, &b), Err(crate::verify::VerifyError::THatCommitmentMismatch));
+
+        assert_eq!(prove_with_backend(&v, &BigInt::from(1), &a, &b, &backend, 4, &mut OsRng).unwrap_err(), ProveError::DimensionTooSmall(4));
+
+        // The interactive prover pads the same way
+        let (state, _, _) = interactive_prove_step1_with_rng(&v, &random_bigint(128), &a, &b, &g, &h, &n, &mut OsRng);
+        assert_eq!(state.d, padded_range_witness(&v1, &v2, INTERACTIVE_DIMENSION).unwrap());
+    }
+
+    // Purpose: ProofStream proves lazily, one statement per step
+    // Params: fast_test_setup params, v = 10 + step in [1, 100], fresh random r per step
+    // Output: take(3) calls the closure three times and yields three distinct verifying proofs
+    // Usage: `cargo test -- src::range_proof` or `cargo test`
+    #[test]
+    fn proof_stream_take_yields_distinct_verifying_proofs() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b) = (BigInt::from(1), BigInt::from(100));
+        let mut calls = 0;
+        let proofs = ProofStream::new(&g, &h, &n, |i| {
+            calls += 1;
+            (BigInt::from(10 + i), random_bigint(128), a.clone(), b.clone())
+        }).take(3).collect::<Vec<_>>();
+        assert_eq!((calls, proofs.len()), (3, 3));
+        assert!(proofs[0] != proofs[1] && proofs[1] != proofs[2] && proofs[0] != proofs[2]);
+        assert!(proofs.iter().all(|p| crate::verify::cuproof_verify_with_range(p, &g, &h, &n, &a, &b)));
+    }
+
+    // Purpose: ProverState wipes its secrets and can be dropped safely under the zeroize feature
+    // Params: interactive step-1 prover state on a small demo range
+    // Output: secret fields read back as zero/empty after zeroize; drop does not panic
+    // Usage: `cargo test --features zeroize -- src::range_proof`
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn prover_state_zeroize_and_drop() {
+        use num_traits::Zero;
+        use zeroize::Zeroize;
+        let (g, h, n) = fast_test_setup();
+        let (state, _, _) = interactive_prove_step1(&BigInt::from(42), &random_bigint(128), &BigInt::from(1), &BigInt::from(100), &g, &h, &n);
+
+        let mut wiped = state.clone();
+        wiped.zeroize();
+        assert!(wiped.r.is_zero() && wiped.alpha.is_zero() && wiped.rho.is_zero());
+        assert!(wiped.tau1.is_zero() && wiped.tau2.is_zero());
+        assert!(wiped.sL.is_empty() && wiped.sR.is_empty());
+        assert_eq!(wiped.a, state.a);
+
+        drop(wiped);
+        drop(state);
+    }
 }
 
 // Inner Product Argument (simplified version - kept for reference)
@@ -469,7 +1477,7 @@ pub fn inner_product_argument(l_vec: &[BigInt], r_vec: &[BigInt], g: &BigInt, h:
 	let c_L = inner_product(l_left, r_right);
 	let c_R = inner_product(l_right, l_left);
 	
-	let y = fiat_shamir(&[&c_L, &c_R]) % n;
+	let y = fiat_shamir(&[&c_L, &c_R]) % challenge_modulus(n);
 	
 	let l_new: Vec<BigInt> = l_left.iter().zip(l_right.iter())
 		.map(|(l, r)| l + &(&y * r))
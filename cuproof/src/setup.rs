@@ -1,31 +1,40 @@
 use num_bigint::{BigInt, RandBigInt, Sign, BigUint};
 use num_traits::{Signed, Zero, One};
 use num_integer::Integer;
-use rand::rngs::OsRng;
+use crate::fiat_shamir::{fiat_shamir_with, ChallengeHasher, DefaultChallengeHasher};
+use rand::{RngCore, SeedableRng};
+use rand::rngs::{OsRng, StdRng};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::time::Instant;
 
-fn miller_rabin(n: &BigUint, k: u32) -> bool {
+/// Miller–Rabin rounds used by `probable_prime` and the setups (error probability <= 4^-16)
+pub const MILLER_RABIN_ROUNDS: u32 = 16;
+
+const SMALL_PRIMES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+pub(crate) fn miller_rabin(n: &BigUint, k: u32) -> bool {
     if *n < BigUint::from(2u32) { return false; }
     // small primes quick check
-    for p in [2u32,3,5,7,11,13,17,19,23,29,31,37] {
+    for p in SMALL_PRIMES {
         let p_b = BigUint::from(p);
         if &p_b == n { return true; }
         if n % &p_b == BigUint::zero() { return false; }
     }
+    // No factor <= 37, so anything below 41^2 is prime: deterministic, no random witnesses needed
+    if *n < BigUint::from(41u32 * 41) { return true; }
 
     // write n-1 = d * 2^r
     let one = BigUint::one();
-    let n_minus_one = n - &one;
-    let mut d = n_minus_one.clone();
+    let mut d = n - &one;
     let mut r = 0u32;
     while &d % 2u32 == BigUint::zero() { d >>= 1; r += 1; }
 
     let mut rng = OsRng;
-    'witness: for _ in 0..k {
+    for _ in 0..k {
         // pick random a in [2, n-2]
         let two = BigUint::from(2u32);
         let n_minus_two = n - &two;
-        if n_minus_two <= two { return true; }
-        use rand::RngCore;
         // sample a by rejection using bytes length
         let mut a;
         loop {
@@ -36,45 +45,159 @@ fn miller_rabin(n: &BigUint, k: u32) -> bool {
             if a >= two && a <= n_minus_two { break; }
         }
 
-        // x = a^d mod n
-        let mut x = a.modpow(&d, n);
-        if x == one || x == n_minus_one { continue 'witness; }
-        for _ in 0..(r-1) {
-            x = x.modpow(&two, n);
-            if x == n_minus_one { continue 'witness; }
-        }
-        return false;
+        if !strong_probable_prime(n, &a, &d, r) { return false; }
     }
     true
 }
 
-fn generate_probable_prime(bits: usize) -> BigUint {
-    let mut rng = OsRng;
+/// One Miller–Rabin round: whether odd n passes for witness a, where n - 1 = d * 2^r
+fn strong_probable_prime(n: &BigUint, a: &BigUint, d: &BigUint, r: u32) -> bool {
+    let one = BigUint::one();
+    let n_minus_one = n - &one;
+    // x = a^d mod n
+    let mut x = a.modpow(d, n);
+    if x == one || x == n_minus_one { return true; }
+    for _ in 0..(r-1) {
+        x = x.modpow(&BigUint::from(2u32), n);
+        if x == n_minus_one { return true; }
+    }
+    false
+}
+
+/// Miller–Rabin with the fixed witnesses `SMALL_PRIMES` instead of random ones
+///
+/// Exact below 3.3 * 10^24 and a probable-prime test above, but always the same answer for
+/// the same n, so two parties deriving a prime from shared data agree on it.
+pub(crate) fn miller_rabin_deterministic(n: &BigUint) -> bool {
+    if *n < BigUint::from(2u32) { return false; }
+    for p in SMALL_PRIMES {
+        let p_b = BigUint::from(p);
+        if &p_b == n { return true; }
+        if n % &p_b == BigUint::zero() { return false; }
+    }
+    let mut d = n - BigUint::one();
+    let mut r = 0u32;
+    while &d % 2u32 == BigUint::zero() { d >>= 1; r += 1; }
+    SMALL_PRIMES.iter().all(|&p| strong_probable_prime(n, &BigUint::from(p), &d, r))
+}
+
+/// Random `bits`-bit probable prime with the default `MILLER_RABIN_ROUNDS`
+pub fn probable_prime(bits: usize) -> BigUint {
+    generate_probable_prime(bits, MILLER_RABIN_ROUNDS)
+}
+
+/// Random `bits`-bit probable prime (top bit set) passing `rounds` Miller–Rabin rounds
+/// - params: bits size, rounds witnesses per candidate (more rounds: lower error, slower)
+/// - returns: odd BigUint with exactly `bits` bits
+/// - usage: `probable_prime` for the default; fewer rounds only for tests
+pub fn generate_probable_prime(bits: usize, rounds: u32) -> BigUint {
+    generate_probable_prime_from(&mut OsRng, bits, rounds)
+}
+
+/// Same as `generate_probable_prime`, drawing the candidates from `rng`
+/// - usage: a seeded rng gives a reproducible prime (see `setup_from_seed`)
+pub fn generate_probable_prime_from<R: RngCore + ?Sized>(rng: &mut R, bits: usize, rounds: u32) -> BigUint {
     loop {
-        // ensure top bit set and odd
-        let high = BigUint::one() << (bits.saturating_sub(1) as u32);
-        let lower = BigUint::from_bytes_be(&{
-            let mut buf = vec![0u8; bits.saturating_sub(1) / 8 + 1];
-            use rand::RngCore; rng.fill_bytes(&mut buf); buf
-        });
-        let mut cand = high.clone() + (lower % &high);
-        if &cand % 2u32 == BigUint::zero() { cand += BigUint::one(); }
-        if miller_rabin(&cand, 16) { return cand; }
+        let cand = prime_candidate(rng, bits);
+        if miller_rabin(&cand, rounds) { return cand; }
     }
 }
 
-pub fn trusted_setup(bits: usize) -> (BigInt, BigInt, BigInt) {
+/// Same as `generate_probable_prime`, giving up once `deadline` has passed
+/// - returns: None if no prime was found in time; the clock is checked before each candidate,
+///   so a single Miller–Rabin test may run past the deadline
+pub fn generate_probable_prime_until(bits: usize, rounds: u32, deadline: Instant) -> Option<BigUint> {
     let mut rng = OsRng;
+    while Instant::now() < deadline {
+        let cand = prime_candidate(&mut rng, bits);
+        if miller_rabin(&cand, rounds) { return Some(cand); }
+    }
+    None
+}
+
+/// Random odd `bits`-bit candidate with the top bit set
+fn prime_candidate<R: RngCore + ?Sized>(rng: &mut R, bits: usize) -> BigUint {
+    let high = BigUint::one() << (bits.saturating_sub(1) as u32);
+    let lower = BigUint::from_bytes_be(&{
+        let mut buf = vec![0u8; bits.saturating_sub(1) / 8 + 1];
+        rng.fill_bytes(&mut buf); buf
+    });
+    let mut cand = high.clone() + (lower % &high);
+    if &cand % 2u32 == BigUint::zero() { cand += BigUint::one(); }
+    cand
+}
+
+/// Safe prime p = 2q + 1 of `bits` bits, with q also prime
+/// - params: bits size of p (>= 3)
+/// - returns: p such that p and (p - 1) / 2 both pass Miller–Rabin
+/// - usage: RSA moduli from safe primes have no small subgroups besides {±1}; much slower than
+///   `generate_probable_prime` since only about 1 in ln(2^bits) primes q gives a prime 2q + 1
+pub fn generate_safe_prime(bits: usize) -> BigUint {
+    loop {
+        let q = probable_prime(bits - 1);
+        let p = (&q << 1u32) + BigUint::one();
+        if miller_rabin(&p, MILLER_RABIN_ROUNDS) { return p; }
+    }
+}
+
+/// Two distinct primes of `prime_bits` bits, safe primes if `safe`
+fn prime_pair(prime_bits: usize, safe: bool) -> (BigUint, BigUint) {
+    let generate = |bits| if safe { generate_safe_prime(bits) } else { probable_prime(bits) };
+    let p = generate(prime_bits);
+    let mut q = generate(prime_bits);
+    while q == p { q = generate(prime_bits); }
+    (p, q)
+}
 
+pub fn trusted_setup(_bits: usize) -> (BigInt, BigInt, BigInt) {
     // RSA-style modulus n = p * q where p and q are 1024-bit primes
     // For 2048-bit modulus, we need 1024-bit primes
-    let prime_bits = 1024; // Fixed: always generate 1024-bit primes
-    let p = generate_probable_prime(prime_bits);
-    let mut q = generate_probable_prime(prime_bits);
-    while q == p { q = generate_probable_prime(prime_bits); }
-    let n_u = &p * &q;
-    let n = BigInt::from_biguint(Sign::Plus, n_u.clone());
+    setup_with_prime_bits(1024, false) // Fixed: always generate 1024-bit primes
+}
+
+/// RSA setup with n = p * q for two safe primes (p = 2p' + 1, q = 2q' + 1)
+/// - params: bits modulus size; p and q have `bits / 2` bits each, so n has bits - 1 or bits bits
+/// - usage: the CLI's `setup trusted-safe` passes 2048, the size of `trusted_setup`
+pub fn trusted_setup_safe(bits: usize) -> (BigInt, BigInt, BigInt) {
+    setup_with_prime_bits(bits / 2, true)
+}
+
+/// 256-bit modulus (two 128-bit primes) matching the uint256 arithmetic of the on-chain verifier
+pub fn setup_256() -> (BigInt, BigInt, BigInt) {
+    setup_with_prime_bits(128, false)
+}
 
+/// Setup paired with `DefaultChallengeHasher`: `trusted_setup` (2048-bit n), or `setup_256`
+/// with the `keccak` feature
+pub fn default_setup() -> (BigInt, BigInt, BigInt) {
+    if cfg!(feature = "keccak") { setup_256() } else { trusted_setup(2048) }
+}
+
+/// RSA setup with n = p * q for two `prime_bits`-bit primes and random g, h in Z_n^*
+/// - params: prime_bits size of each prime, safe whether p and q must be safe primes
+/// - returns: (g, h, n) with g != h, both coprime to n
+/// - usage: `trusted_setup` is (1024, false); pass safe = true to avoid small subgroups
+pub fn setup_with_prime_bits(prime_bits: usize, safe: bool) -> (BigInt, BigInt, BigInt) {
+    let (p, q) = prime_pair(prime_bits, safe);
+    random_generators(BigInt::from_biguint(Sign::Plus, &p * &q))
+}
+
+/// Same parameters as `trusted_setup`, but with n of `bits` bits and a bound on the running time
+/// - params: bits modulus size (two `bits / 2`-bit primes), deadline after which to give up
+/// - returns: None if prime generation is still running at the deadline, otherwise (g, h, n)
+///   drawn exactly like `setup_with_prime_bits(bits / 2, false)`
+/// - usage: services that must not hang a request on a 2048-bit setup
+pub fn trusted_setup_with_deadline(bits: usize, deadline: Instant) -> Option<(BigInt, BigInt, BigInt)> {
+    let prime_bits = bits / 2;
+    let p = generate_probable_prime_until(prime_bits, MILLER_RABIN_ROUNDS, deadline)?;
+    let mut q = generate_probable_prime_until(prime_bits, MILLER_RABIN_ROUNDS, deadline)?;
+    while q == p { q = generate_probable_prime_until(prime_bits, MILLER_RABIN_ROUNDS, deadline)?; }
+    Some(random_generators(BigInt::from_biguint(Sign::Plus, &p * &q)))
+}
+
+/// (g, h, n) with g != h drawn uniformly from Z_n^*
+fn random_generators(n: BigInt) -> (BigInt, BigInt, BigInt) {
+    let mut rng = OsRng;
     // choose g, h uniformly in Z_n^* (co-prime with n)
     let two = BigInt::from(2u32);
     let one = BigInt::one();
@@ -99,9 +222,9 @@ pub fn fast_test_setup() -> (BigInt, BigInt, BigInt) {
 
     // Use smaller primes for fast testing: 256-bit primes -> 512-bit modulus
     let prime_bits = 256; // Much faster than 1024-bit
-    let p = generate_probable_prime(prime_bits);
-    let mut q = generate_probable_prime(prime_bits);
-    while q == p { q = generate_probable_prime(prime_bits); }
+    let p = probable_prime(prime_bits);
+    let mut q = probable_prime(prime_bits);
+    while q == p { q = probable_prime(prime_bits); }
     let n_u = &p * &q;
     let n = BigInt::from_biguint(Sign::Plus, n_u.clone());
 
@@ -122,11 +245,328 @@ pub fn fast_test_setup() -> (BigInt, BigInt, BigInt) {
     (g, h, n)
 }
 
+/// Derive h from g by hashing into Z_n^* ("nothing up my sleeve")
+/// - params: g generator, n RSA modulus
+/// - returns: h = H(g, n, counter) expanded to n.bits() + 128 bits and reduced mod n,
+///   for the first counter giving gcd(h, n) = 1, h >= 2 and h != g
+/// - usage: nobody (including the setup party) knows log_g(h), which Pedersen binding needs
+pub fn derive_h(g: &BigInt, n: &BigInt) -> BigInt {
+    derive_h_with::<DefaultChallengeHasher>(g, n)
+}
+
+/// Same as `derive_h`, hashing with `H` instead of the feature-selected default
+/// - usage: values that must not change with the `keccak` feature, e.g. the IPP generators of
+///   an `RsaBackend<H>`
+pub fn derive_h_with<H: ChallengeHasher>(g: &BigInt, n: &BigInt) -> BigInt {
+    let target_bits = n.bits() + 128;
+    let mut counter = 0u64;
+    loop {
+        // Expand the 256-bit Fiat–Shamir hash with a block index until it covers n
+        let mut wide = BigInt::zero();
+        let mut block = 0u64;
+        while wide.bits() < target_bits {
+            let digest = fiat_shamir_with::<H>(&[g, n, &BigInt::from(counter), &BigInt::from(block)]);
+            wide = (wide << 256) + digest;
+            block += 1;
+        }
+        let h = wide % n;
+        if h >= BigInt::from(2u32) && &h != g && h.gcd(n).is_one() { return h; }
+        counter += 1;
+    }
+}
+
+/// n = p * q for two distinct random primes of `prime_bits` bits and a random g in Z_n^*
+fn modulus_and_generator(prime_bits: usize) -> (BigInt, BigInt) {
+    let mut rng = OsRng;
+    let (p, q) = prime_pair(prime_bits, false);
+    let n = BigInt::from_biguint(Sign::Plus, &p * &q);
+    let two = BigInt::from(2u32);
+    loop {
+        let g = rng.gen_bigint_range(&two, &n);
+        if g.gcd(&n).is_one() { return (n, g); }
+    }
+}
+
+/// Same as `trusted_setup`, with h = `derive_h(g, n)` instead of an independent random h
+pub fn trusted_setup_derived_h(_bits: usize) -> (BigInt, BigInt, BigInt) {
+    let (n, g) = modulus_and_generator(1024);
+    let h = derive_h(&g, &n);
+    (g, h, n)
+}
+
+/// Same as `fast_test_setup`, with h = `derive_h(g, n)` instead of an independent random h
+pub fn fast_test_setup_derived_h() -> (BigInt, BigInt, BigInt) {
+    let (n, g) = modulus_and_generator(256);
+    let h = derive_h(&g, &n);
+    (g, h, n)
+}
+
+/// Same as `setup_256`, with h = `derive_h(g, n)` instead of an independent random h
+pub fn setup_256_derived_h() -> (BigInt, BigInt, BigInt) {
+    let (n, g) = modulus_and_generator(128);
+    let h = derive_h(&g, &n);
+    (g, h, n)
+}
+
+/// Smallest modulus accepted by `validate_params`: the size fast_test_setup (two 256-bit primes) can produce,
+/// or `setup_256` (two 128-bit primes) with the `keccak` feature
+#[cfg(not(feature = "keccak"))]
+pub const MIN_MODULUS_BITS: u64 = 511;
+#[cfg(feature = "keccak")]
+pub const MIN_MODULUS_BITS: u64 = 255;
+
+/// Security level of a modulus, from the setup function that produces that size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamClass {
+    /// ~512-bit n from `fast_test_setup`: development and tests only
+    Fast,
+    /// ~256-bit n from `setup_256`, sized for the on-chain verifier
+    Evm256,
+    /// ~2048-bit n from `trusted_setup`
+    Trusted,
+    /// Any other size
+    Unknown,
+}
+
+/// Classify a modulus by its bit size
+/// - params: n RSA modulus, e.g. from a loaded params file
+/// - returns: the class whose two k-bit primes give n (2k - 1 or 2k bits), Unknown otherwise
+/// - usage: refuse a `Fast` modulus in production; says nothing about how n was generated,
+///   only `validate_params` checks its structure
+pub fn classify_params(n: &BigInt) -> ParamClass {
+    match n.bits() {
+        255..=256 => ParamClass::Evm256,
+        511..=512 => ParamClass::Fast,
+        2047..=2048 => ParamClass::Trusted,
+        _ => ParamClass::Unknown,
+    }
+}
+
+/// Why a (g, h, n) triple was rejected by `validate_params`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// n has fewer than `min` bits
+    ModulusTooSmall { bits: u64, min: u64 },
+    /// n is even, so it cannot be a product of two odd primes
+    EvenModulus,
+    /// g or h is not in [2, n)
+    GeneratorOutOfRange,
+    /// gcd(g, n) != 1
+    GNotCoprime,
+    /// gcd(h, n) != 1
+    HNotCoprime,
+    /// g == h
+    GeneratorsEqual,
+    /// `combine_params` got no contributions
+    NoContributions,
+    /// Contributions to `combine_params` use different moduli
+    ModulusMismatch,
+    /// n passes Miller–Rabin, so it is not an RSA modulus
+    PrimeModulus,
+    /// n has a small prime factor
+    SmallFactor(u32),
+    /// n = p^2
+    SquareModulus,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::ModulusTooSmall { bits, min } => write!(f, "modulus has {} bits, need at least {}", bits, min),
+            ParamError::EvenModulus => write!(f, "modulus is even"),
+            ParamError::GeneratorOutOfRange => write!(f, "g and h must lie in [2, n)"),
+            ParamError::GNotCoprime => write!(f, "gcd(g, n) != 1"),
+            ParamError::HNotCoprime => write!(f, "gcd(h, n) != 1"),
+            ParamError::GeneratorsEqual => write!(f, "g and h must differ"),
+            ParamError::NoContributions => write!(f, "no parameter contributions to combine"),
+            ParamError::ModulusMismatch => write!(f, "contributions use different moduli"),
+            ParamError::PrimeModulus => write!(f, "modulus is prime, expected a product of two primes"),
+            ParamError::SmallFactor(p) => write!(f, "modulus is divisible by {}", p),
+            ParamError::SquareModulus => write!(f, "modulus is a perfect square"),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Check the invariants the setup functions guarantee for (g, h, n)
+/// - params: g, h generators and n RSA modulus
+/// - returns: Ok(()) or the first violated invariant
+/// - usage: reject corrupt or hand-edited parameter files before proving/verifying
+pub fn validate_params(g: &BigInt, h: &BigInt, n: &BigInt) -> Result<(), ParamError> {
+    if n.bits() < MIN_MODULUS_BITS {
+        return Err(ParamError::ModulusTooSmall { bits: n.bits(), min: MIN_MODULUS_BITS });
+    }
+    if n.is_even() { return Err(ParamError::EvenModulus); }
+    let two = BigInt::from(2u32);
+    if g < &two || g >= n || h < &two || h >= n { return Err(ParamError::GeneratorOutOfRange); }
+    if !g.gcd(n).is_one() { return Err(ParamError::GNotCoprime); }
+    if !h.gcd(n).is_one() { return Err(ParamError::HNotCoprime); }
+    if g == h { return Err(ParamError::GeneratorsEqual); }
+    Ok(())
+}
+
+/// Reproducible RSA setup: the same seed always gives the same (g, h, n)
+/// - params: seed arbitrary bytes, prime_bits size of each of the two primes
+/// - returns: (g, h, n) with p, q and g drawn from a StdRng seeded with SHA-256 of the seed
+///   and h = `derive_h(g, n)`
+/// - usage: test vectors and cross-implementation checks only; anyone with the seed can
+///   recompute p and q, so never use seeded parameters for real proofs
+pub fn setup_from_seed(seed: &[u8], prime_bits: usize) -> (BigInt, BigInt, BigInt) {
+    let mut hasher = Sha256::new();
+    hasher.update(b"cuproof-setup-seed");
+    hasher.update(seed);
+    let mut rng = StdRng::from_seed(hasher.finalize().into());
+
+    let p = generate_probable_prime_from(&mut rng, prime_bits, MILLER_RABIN_ROUNDS);
+    let mut q = generate_probable_prime_from(&mut rng, prime_bits, MILLER_RABIN_ROUNDS);
+    while q == p { q = generate_probable_prime_from(&mut rng, prime_bits, MILLER_RABIN_ROUNDS); }
+    let n = BigInt::from_biguint(Sign::Plus, &p * &q);
+    let two = BigInt::from(2u32);
+    let g = loop {
+        let g = rng.gen_bigint_range(&two, &n);
+        if g.gcd(&n).is_one() { break g; }
+    };
+    let h = derive_h(&g, &n);
+    (g, h, n)
+}
+
+/// Build parameters for an existing RSA modulus, e.g. from an earlier ceremony
+/// - params: n modulus whose factorization the caller does not know
+/// - returns: (g, h, n) with random g in Z_n^* and h = `derive_h(g, n)`, validated with `validate_params`
+/// - usage: reuse a modulus without generating primes; cheap checks reject even, prime,
+///   square or smooth-looking n, but cannot prove n has exactly two prime factors
+pub fn setup_from_modulus(n: &BigInt) -> Result<(BigInt, BigInt, BigInt), ParamError> {
+    if n.bits() < MIN_MODULUS_BITS {
+        return Err(ParamError::ModulusTooSmall { bits: n.bits(), min: MIN_MODULUS_BITS });
+    }
+    if n.is_even() { return Err(ParamError::EvenModulus); }
+    let n_u = n.to_biguint().ok_or(ParamError::EvenModulus)?;
+    if let Some(p) = SMALL_PRIMES.into_iter().find(|&p| (&n_u % p).is_zero()) {
+        return Err(ParamError::SmallFactor(p));
+    }
+    if miller_rabin(&n_u, MILLER_RABIN_ROUNDS) { return Err(ParamError::PrimeModulus); }
+    let root = n_u.sqrt();
+    if &root * &root == n_u { return Err(ParamError::SquareModulus); }
+
+    let mut rng = OsRng;
+    let two = BigInt::from(2u32);
+    let g = loop {
+        let g = rng.gen_bigint_range(&two, n);
+        if g.gcd(n).is_one() { break g; }
+    };
+    let h = derive_h(&g, n);
+    validate_params(&g, &h, n)?;
+    Ok((g, h, n.clone()))
+}
+
+/// Combine per-participant (g_i, h_i, n) contributions of a distributed setup
+/// - params: contributions sharing the same modulus n; each must pass `validate_params`
+/// - returns: (prod g_i mod n, derive_h(g, n), n), validated with `validate_params`
+/// - usage: building block for a multi-party ceremony. h is hashed from the combined g rather
+///   than multiplied from the h_i, which the last participant could pick after seeing the
+///   others' to know log_g(h); the h_i are only validated
+pub fn combine_params(params: &[(BigInt, BigInt, BigInt)]) -> Result<(BigInt, BigInt, BigInt), ParamError> {
+    let (_, _, n) = params.first().ok_or(ParamError::NoContributions)?;
+    let mut g = BigInt::one();
+    for (g_i, h_i, n_i) in params {
+        if n_i != n { return Err(ParamError::ModulusMismatch); }
+        validate_params(g_i, h_i, n_i)?;
+        g = g * g_i % n;
+    }
+    let h = derive_h(&g, n);
+    validate_params(&g, &h, n)?;
+    Ok((g, h, n.clone()))
+}
+
+/// True if two parameter sets live in the same group (same modulus n), whatever their generators
+/// - params: p1, p2 as (g, h, n)
+/// - usage: with `params_identical`, tells a modulus mismatch apart from a generator mismatch
+///   when a proof made under one parameter file fails under another
+pub fn params_same_group(p1: &(BigInt, BigInt, BigInt), p2: &(BigInt, BigInt, BigInt)) -> bool {
+    p1.2 == p2.2
+}
+
+/// True if two parameter sets agree on g, h and n
+pub fn params_identical(p1: &(BigInt, BigInt, BigInt), p2: &(BigInt, BigInt, BigInt)) -> bool {
+    p1 == p2
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use num_traits::Zero;
 
+    // Purpose: Miller–Rabin rejects small and Carmichael composites and accepts known primes
+    // Params: composites 1, 4, 6, 9, 341 (base-2 pseudoprime), 561, 1105 (Carmichael), 1681 = 41^2;
+    //         primes 2, 3, 37, 41, 1019, 2^61 - 1
+    // Output: boolean assertions, with 1 and 16 rounds; generated primes have the requested size
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn miller_rabin_known_composites_and_primes() {
+        for rounds in [1, MILLER_RABIN_ROUNDS] {
+            for c in [0u64, 1, 4, 6, 9, 341, 561, 1105, 1681] {
+                assert!(!miller_rabin(&BigUint::from(c), rounds), "{} accepted", c);
+            }
+            for p in [2u64, 3, 37, 41, 1019, (1 << 61) - 1] {
+                assert!(miller_rabin(&BigUint::from(p), rounds), "{} rejected", p);
+            }
+        }
+        let p = generate_probable_prime(48, 4);
+        assert_eq!(p.bits(), 48);
+        assert!(miller_rabin(&p, 32));
+    }
+
+    // Purpose: setup_from_modulus accepts a product of two primes and rejects non-RSA moduli
+    // Params: n = p * q for two 256-bit primes; even, prime, square and 3-divisible moduli
+    // Output: valid (g, h) for p * q; the matching ParamError otherwise
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn setup_from_known_modulus() {
+        let p = probable_prime(256);
+        let mut q = probable_prime(256);
+        while q == p { q = probable_prime(256); }
+        let n = BigInt::from_biguint(Sign::Plus, &p * &q);
+        let (g, h, n2) = setup_from_modulus(&n).unwrap();
+        assert_eq!(n2, n);
+        assert_eq!(validate_params(&g, &h, &n), Ok(()));
+        assert_eq!(h, derive_h(&g, &n));
+
+        let prime = BigInt::from_biguint(Sign::Plus, probable_prime(520));
+        let square = BigInt::from_biguint(Sign::Plus, &p * &p);
+        assert_eq!(setup_from_modulus(&(&n * 2)), Err(ParamError::EvenModulus));
+        assert_eq!(setup_from_modulus(&(&n * 3)), Err(ParamError::SmallFactor(3)));
+        assert_eq!(setup_from_modulus(&prime), Err(ParamError::PrimeModulus));
+        assert_eq!(setup_from_modulus(&square), Err(ParamError::SquareModulus));
+    }
+
+    // Purpose: safe primes are primes p with (p - 1) / 2 also prime, and safe setups multiply two of them
+    // Params: 64-bit safe prime; 64-bit safe-prime setup; trusted_setup_safe(160)
+    // Output: Miller–Rabin assertions on p and (p - 1) / 2; coprime, distinct generators; a
+    //         159- or 160-bit modulus from trusted_setup_safe
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn safe_prime_and_safe_setup() {
+        let p = generate_safe_prime(64);
+        assert_eq!(p.bits(), 64);
+        assert!(miller_rabin(&p, 32));
+        assert!(miller_rabin(&((&p - 1u32) >> 1u32), 32));
+
+        let (p, q) = prime_pair(64, true);
+        assert_ne!(p, q);
+        for x in [&p, &q] {
+            assert!(miller_rabin(&((x - 1u32) >> 1u32), 32));
+        }
+
+        let (g, h, n) = setup_with_prime_bits(64, true);
+        assert!(n.bits() == 127 || n.bits() == 128);
+        assert!(g.gcd(&n).is_one() && h.gcd(&n).is_one() && g != h);
+
+        // trusted_setup_safe sizes n by its argument
+        let (_, _, n) = trusted_setup_safe(160);
+        assert!(n.bits() == 159 || n.bits() == 160);
+    }
+
     // Purpose: ensure generated (g,h) are in Z*_n, non-equal, and gcd(g,n)=gcd(h,n)=1
     // Params: none
     // Output: assertions on coprimality and distinctness
@@ -139,4 +579,143 @@ mod tests {
         assert_ne!(g, h);
         assert!(!n.is_zero());
     }
+
+    // Purpose: validate_params accepts setup output and rejects each hand-crafted bad triple
+    // Params: fast_test_setup params, then one corrupted field per case
+    // Output: Ok for valid params, the matching ParamError otherwise
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn validate_params_rejects_bad_params() {
+        let (g, h, n) = fast_test_setup();
+        assert_eq!(validate_params(&g, &h, &n), Ok(()));
+
+        let small = BigInt::from(3233); // 61 * 53
+        assert!(matches!(validate_params(&BigInt::from(2), &BigInt::from(3), &small), Err(ParamError::ModulusTooSmall { .. })));
+        assert_eq!(validate_params(&g, &h, &(&n + 1)), Err(ParamError::EvenModulus));
+        assert_eq!(validate_params(&BigInt::from(1), &h, &n), Err(ParamError::GeneratorOutOfRange));
+        assert_eq!(validate_params(&g, &(&n + 2), &n), Err(ParamError::GeneratorOutOfRange));
+
+        // A modulus with a known small factor p makes p itself a non-unit
+        let p = BigInt::from(65537);
+        let n_p = &n * &p;
+        let (two, three) = (BigInt::from(2), BigInt::from(3));
+        assert_eq!(validate_params(&p, &three, &n_p), Err(ParamError::GNotCoprime));
+        assert_eq!(validate_params(&two, &p, &n_p), Err(ParamError::HNotCoprime));
+        assert_eq!(validate_params(&g, &g, &n), Err(ParamError::GeneratorsEqual));
+    }
+
+    // Purpose: combining two contributions over one modulus yields valid params; mixed moduli are rejected
+    // Params: two (g, h) pairs drawn for the same fast_test_setup modulus
+    // Output: g is the product of the g_i and h = derive_h(g, n), whatever the h_i; validate_params
+    //         passes on the result; ModulusMismatch / NoContributions otherwise
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn combine_params_two_contributions() {
+        let (g1, h1, n) = fast_test_setup();
+        let mut rng = OsRng;
+        let second = loop {
+            let g2 = rng.gen_bigint_range(&BigInt::from(2u32), &n);
+            let h2 = rng.gen_bigint_range(&BigInt::from(2u32), &n);
+            if validate_params(&g2, &h2, &n).is_ok() { break (g2, h2, n.clone()); }
+        };
+        let (g, h, n_out) = combine_params(&[(g1.clone(), h1.clone(), n.clone()), second.clone()]).unwrap();
+        assert_eq!(n_out, n);
+        assert_eq!(validate_params(&g, &h, &n_out), Ok(()));
+        assert_eq!(g, &g1 * &second.0 % &n);
+        assert_eq!(h, derive_h(&g, &n));
+        // A last h_i chosen after seeing h1 no longer steers h: the old product would be g^5
+        use crate::commitment::{mod_exp, mod_inverse};
+        let steered = mod_exp(&g, &BigInt::from(5), &n) * mod_inverse(&h1, &n).unwrap() % &n;
+        let (_, h_steered, _) = combine_params(&[(g1.clone(), h1.clone(), n.clone()), (second.0.clone(), steered, n.clone())]).unwrap();
+        assert_eq!(h_steered, h);
+        assert_ne!(h, mod_exp(&g, &BigInt::from(5), &n));
+
+        let (g3, h3, n3) = fast_test_setup();
+        assert_eq!(combine_params(&[(g1, h1, n), (g3, h3, n3)]), Err(ParamError::ModulusMismatch));
+        assert_eq!(combine_params(&[]), Err(ParamError::NoContributions));
+    }
+
+    // Purpose: derive_h is a deterministic function of (g, n) landing in Z_n^* and distinct from g
+    // Params: fast_test_setup params; the derived-h setup variant
+    // Output: equality/coprimality assertions and validate_params on the derived setup
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn derive_h_deterministic_and_coprime() {
+        let (g, _h, n) = fast_test_setup();
+        let h1 = derive_h(&g, &n);
+        let h2 = derive_h(&g, &n);
+        assert_eq!(h1, h2);
+        assert!(h1.gcd(&n).is_one());
+        assert_ne!(h1, g);
+        assert_ne!(derive_h(&(&g + 1u32), &n), h1);
+
+        let (g, h, n) = fast_test_setup_derived_h();
+        assert_eq!(h, derive_h(&g, &n));
+        assert_eq!(validate_params(&g, &h, &n), Ok(()));
+
+        // 256-bit moduli only pass validate_params with the `keccak` feature
+        let (g, h, n) = setup_256_derived_h();
+        assert_eq!(h, derive_h(&g, &n));
+        assert!(n.bits() >= 255 && h.gcd(&n).is_one());
+    }
+
+    // Purpose: setup_from_seed is reproducible per seed and yields valid parameters
+    // Params: two fixed seeds, 256-bit primes
+    // Output: equality/inequality assertions and validate_params on the seeded setup
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn setup_from_seed_reproducible() {
+        let first = setup_from_seed(b"vectors", 256);
+        assert_eq!(setup_from_seed(b"vectors", 256), first);
+        let (g, h, n) = &first;
+        assert!(n.bits() >= MIN_MODULUS_BITS);
+        assert_eq!(*h, derive_h(g, n));
+        assert_eq!(validate_params(g, h, n), Ok(()));
+        assert_ne!(setup_from_seed(b"other", 256).2, first.2);
+    }
+
+    // Purpose: same-group and identical comparisons separate generator from modulus mismatches
+    // Params: fast_test_setup params, the same n with a re-derived g/h, an independent setup
+    // Output: boolean assertions on params_same_group / params_identical
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn compare_param_sets() {
+        let p1 = fast_test_setup();
+        let g2 = &p1.0 * &p1.0 % &p1.2;
+        let p2 = (g2.clone(), derive_h(&g2, &p1.2), p1.2.clone());
+        let p3 = setup_from_seed(b"compare_param_sets", 256);
+
+        assert!(params_same_group(&p1, &p1.clone()) && params_identical(&p1, &p1.clone()));
+        assert!(params_same_group(&p1, &p2) && !params_identical(&p1, &p2));
+        assert!(!params_same_group(&p1, &p3) && !params_identical(&p1, &p3));
+    }
+
+    // Purpose: the deadline aborts a slow setup and does not affect a fast one
+    // Params: 2048-bit modulus with an already-expired deadline; 512-bit modulus with 60 s
+    // Output: None for the first; params passing validate_params for the second
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn setup_with_deadline() {
+        use std::time::Duration;
+        assert!(trusted_setup_with_deadline(2048, Instant::now()).is_none());
+        let (g, h, n) = trusted_setup_with_deadline(512, Instant::now() + Duration::from_secs(60)).unwrap();
+        assert_eq!(validate_params(&g, &h, &n), Ok(()));
+    }
+
+    // Purpose: each setup function's modulus is classified as its security level
+    // Params: moduli from fast_test_setup, setup_256, trusted_setup and setup_with_prime_bits(512)
+    // Output: Fast, Evm256, Trusted, and Unknown for the 1024-bit and tiny moduli
+    // Usage: `cargo test -- src::setup` or `cargo test`
+    #[test]
+    fn classify_params_by_modulus_size() {
+        assert_eq!(classify_params(&fast_test_setup().2), ParamClass::Fast);
+        assert_eq!(classify_params(&setup_256().2), ParamClass::Evm256);
+        assert_eq!(classify_params(&trusted_setup(2048).2), ParamClass::Trusted);
+        assert_eq!(classify_params(&setup_with_prime_bits(512, false).2), ParamClass::Unknown);
+        assert_eq!(classify_params(&BigInt::from(3233)), ParamClass::Unknown);
+        // Both sizes a product of two 128-bit primes can have
+        assert_eq!(classify_params(&(BigInt::one() << 254)), ParamClass::Evm256);
+        assert_eq!(classify_params(&((BigInt::one() << 256) - 1)), ParamClass::Evm256);
+        assert_eq!(classify_params(&(BigInt::one() << 256)), ParamClass::Unknown);
+    }
 }
@@ -0,0 +1,86 @@
+use crate::{util::*, commitment::*, fiat_shamir::*};
+use num_bigint::BigInt;
+use num_traits::Signed;
+
+/// Proof that commitments C_i = Commit(v_i, r_i) satisfy sum v_i = total for a public total
+///
+/// By the homomorphism P = prod C_i = g^total h^R with R = sum r_i, so the prover shows
+/// knowledge of R with P / g^total = h^R (Schnorr proof base h):
+/// - T = h^k for a random nonce k
+/// - c = H(C_1, ..., C_k, total, T)
+/// - s = k + c R, computed over the integers since the group order is unknown
+///
+/// The verifier checks h^s = T (P / g^total)^c in the inverse-free form h^s g^(c total) = T P^c.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumProof {
+    pub T: BigInt,
+    pub s: BigInt,
+}
+
+#[allow(non_snake_case)]
+fn sum_challenge(commitments: &[BigInt], total: &BigInt, T: &BigInt) -> BigInt {
+    let mut inputs: Vec<&BigInt> = commitments.iter().collect();
+    inputs.push(total);
+    inputs.push(T);
+    fiat_shamir_length_prefixed(&inputs)
+}
+
+/// Prove that the values behind Commit(v_i, r_i) add up to `total`
+/// - params: values (v_i, r_i) pairs, total public sum, g/h/n public parameters
+/// - returns: SumProof (the verifier recomputes nothing secret; it needs only the C_i)
+/// - usage: solvency-style statements together with a range proof per commitment
+#[allow(non_snake_case)]
+pub fn prove_sum(values: &[(BigInt, BigInt)], total: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> SumProof {
+    let commitments: Vec<BigInt> = values.iter().map(|(v, r)| pedersen_commit(g, h, v, r, n)).collect();
+    let R: BigInt = values.iter().map(|(_, r)| r).sum();
+    let (T, mut s) = schnorr_responses(&[R], |k| mod_exp(h, &k[0], n), |T| sum_challenge(&commitments, total, T));
+    SumProof { T, s: s.remove(0) }
+}
+
+/// Verify a SumProof that the committed values add up to `total`
+/// - params: commitments C_i, total, proof, g/h/n public parameters
+/// - returns: true if prod C_i opens to `total` (with the prover knowing the summed blinding)
+/// - usage: pair with `prove_sum`; an empty commitment list is rejected
+pub fn verify_sum(commitments: &[BigInt], total: &BigInt, proof: &SumProof, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+    if commitments.is_empty() || proof.s.is_negative() { return false; }
+    if &proof.T % n == BigInt::from(0) { return false; }
+    let product = commitments.iter().fold(BigInt::from(1), |acc, c| acc * c % n);
+    let c = sum_challenge(commitments, total, &proof.T);
+    let lhs = mod_exp(h, &proof.s, n) * mod_exp(g, &(&c * total), n) % n;
+    let rhs = &proof.T * mod_exp(&product, &c, n) % n;
+    ct_eq(&lhs, &rhs, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup::fast_test_setup;
+
+    // Purpose: committed values verify against their true total and fail against any other
+    // Params: fast_test_setup params, three values with random 256-bit blindings
+    // Output: boolean assertions on verify_sum
+    // Usage: `cargo test -- src::sum` or `cargo test`
+    #[test]
+    fn sum_matching_and_mismatched_totals() {
+        let (g, h, n) = fast_test_setup();
+        let values: Vec<(BigInt, BigInt)> = [10, 25, 7].iter()
+            .map(|&v| (BigInt::from(v), random_bigint(256)))
+            .collect();
+        let commitments: Vec<BigInt> = values.iter().map(|(v, r)| pedersen_commit(&g, &h, v, r, &n)).collect();
+        let total = BigInt::from(42);
+
+        let proof = prove_sum(&values, &total, &g, &h, &n);
+        assert!(verify_sum(&commitments, &total, &proof, &g, &h, &n));
+
+        // Wrong public total
+        assert!(!verify_sum(&commitments, &BigInt::from(43), &proof, &g, &h, &n));
+
+        // A prover claiming a wrong total cannot produce a verifying proof either
+        let lying = prove_sum(&values, &BigInt::from(41), &g, &h, &n);
+        assert!(!verify_sum(&commitments, &BigInt::from(41), &lying, &g, &h, &n));
+
+        // Dropping a commitment breaks the sum
+        assert!(!verify_sum(&commitments[..2], &total, &proof, &g, &h, &n));
+    }
+}
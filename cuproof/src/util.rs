@@ -1,18 +1,77 @@
 use num_bigint::{BigInt, RandBigInt};
-use num_traits::Signed;
+use num_traits::{Signed, Zero};
+use rand::RngCore;
 use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use subtle::ConstantTimeEq;
 use std::fs;
 use std::io::{self, Write};
 use std::path::Path;
 use crate::range_proof::Cuproof;
+use crate::setup::validate_params;
 
 pub fn random_bigint(bits: usize) -> BigInt {
-    let mut rng = OsRng;
+    random_bigint_from(&mut OsRng, bits)
+}
+
+/// Same as `random_bigint`, drawing from the given RNG
+/// - params: rng any RngCore (e.g. a seeded StdRng), bits upper bound on the bit length
+/// - returns: non-negative BigInt with at most `bits` bits
+/// - usage: reproducible proofs from a seeded RNG
+pub fn random_bigint_from<R: RngCore + ?Sized>(rng: &mut R, bits: usize) -> BigInt {
     rng.gen_bigint(bits as u64).abs()
 }
 
+/// Uniform random BigInt in [0, n) via rejection sampling
+/// - params: n exclusive upper bound (must be positive)
+/// - returns: BigInt r with 0 <= r < n
+/// - usage: blinding factors that are already reduced modulo the group
+pub fn random_bigint_mod(n: &BigInt) -> BigInt {
+    random_bigint_mod_from(&mut OsRng, n)
+}
+
+/// Same as `random_bigint_mod`, drawing from the given RNG
+pub fn random_bigint_mod_from<R: RngCore + ?Sized>(rng: &mut R, n: &BigInt) -> BigInt {
+    assert!(n.is_positive(), "random_bigint_mod: n must be positive");
+    let bits = n.bits();
+    loop {
+        // Sampling exactly n.bits() bits accepts with probability > 1/2
+        let r = BigInt::from(rng.gen_biguint(bits));
+        if &r < n { return r; }
+    }
+}
+
+/// <a, b> over the common prefix of a and b
+/// - usage: the hot loop of the prover at large dimensions. Each product is written into one
+///   scratch value whose buffer is reused, then added to the accumulator in place, so the sum
+///   allocates only when a product or the total outgrows what was already allocated
 pub fn inner_product(a: &[BigInt], b: &[BigInt]) -> BigInt {
-    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+    let (mut acc, mut scratch) = (BigInt::zero(), BigInt::zero());
+    for (x, y) in a.iter().zip(b) {
+        scratch.clone_from(x);
+        scratch *= y;
+        acc += &scratch;
+    }
+    acc
+}
+
+/// Compare two BigInts in constant time for values below the modulus
+/// - params: a, b values to compare (typically commitments reduced mod n); n the modulus
+/// - returns: true iff a == b
+/// - usage: verifier equality checks; both sides are laid out as the same number of 64-bit limbs
+///   as n (wider only for values that exceed n) and compared with `subtle`, so the running time
+///   depends on the modulus, not on the operands
+pub fn ct_eq(a: &BigInt, b: &BigInt, n: &BigInt) -> bool {
+    let limbs = |x: &BigInt| x.magnitude().iter_u64_digits().len();
+    let width = (n.magnitude().iter_u64_digits().len()).max(limbs(a)).max(limbs(b));
+    let fixed = |x: &BigInt| {
+        let mut out = vec![0u64; width];
+        for (slot, digit) in out.iter_mut().zip(x.magnitude().iter_u64_digits()) { *slot = digit; }
+        out
+    };
+    let signs_eq = (a.sign() as u8).ct_eq(&(b.sign() as u8));
+    bool::from(signs_eq & fixed(a).as_slice().ct_eq(fixed(b).as_slice()))
 }
 
 /// Convert BigInt to hex string (two's complement positive representation)
@@ -24,13 +83,57 @@ pub fn bigint_to_hex(x: &BigInt) -> String {
     hex::encode(bytes)
 }
 
-/// Parse BigInt from hex string (no 0x prefix)
-/// - params: s hex string
-/// - returns: BigInt parsed as positive number
-/// - usage: load BigInt values from text files
+/// Same as `bigint_to_hex` with a leading '-' for negative values
+/// - usage: integer scalars of a proof (t0, t1, ...), which the verifier checks over the integers
+fn bigint_to_signed_hex(x: &BigInt) -> String {
+    if x.is_negative() { format!("-{}", bigint_to_hex(x)) } else { bigint_to_hex(x) }
+}
+
+/// Whether a <= v <= b
+/// - params: v value, a/b inclusive bounds
+/// - returns: true exactly when v1 = 4v-4a+1 and v2 = 4b-4v+1 are both positive
+/// - usage: prover input validation and test setup, instead of comparing bounds inline
+pub fn value_in_range(v: &BigInt, a: &BigInt, b: &BigInt) -> bool {
+    a <= v && v <= b
+}
+
+/// Whether 0 <= v < 2^bits
+/// - params: v value, bits width (any u32; no shift, so bits = 64 and above cannot overflow)
+/// - returns: false for negative v
+/// - usage: k-bit statements such as `prove_bits`
+pub fn value_fits_bits(v: &BigInt, bits: u32) -> bool {
+    !v.is_negative() && v.bits() <= u64::from(bits)
+}
+
+/// Map x into [0, n), e.g. a group element that came out negative
+/// - params: x any BigInt, n positive modulus
+/// - returns: the representative of x mod n in [0, n)
+/// - usage: clean group elements before `save_proof`; integer scalars (t0, t_hat, ...) are checked
+///   over the integers by the verifier and must not be reduced this way
+pub fn canonicalize_scalar(x: &BigInt, n: &BigInt) -> BigInt {
+    ((x % n) + n) % n
+}
+
+/// Parse BigInt from hex string, leniently
+/// - params: s hex string; optional 0x/0X prefix, `_` separators, whitespace and odd length are accepted
+/// - returns: BigInt parsed as positive number (0 on invalid input)
+/// - usage: CLI arguments; file loading uses the strict parser instead
 pub fn hex_to_bigint(s: &str) -> BigInt {
-    let bytes = hex::decode(s.trim()).unwrap_or_default();
-    BigInt::from_bytes_be(num_bigint::Sign::Plus, &bytes)
+    try_hex_to_bigint(s).unwrap_or_default()
+}
+
+/// Same lenient syntax as `hex_to_bigint`, but None for empty input or a non-hex digit
+/// - usage: secrets such as `prove --blinding`, where a silent 0 would be unsafe
+pub fn try_hex_to_bigint(s: &str) -> Option<BigInt> {
+    let trimmed = s.trim();
+    let trimmed = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")).unwrap_or(trimmed);
+    // Drop `_` separators and any internal whitespace pasted from other tools
+    let mut cleaned: String = trimmed.chars().filter(|c| *c != '_' && !c.is_whitespace()).collect();
+    if cleaned.is_empty() { return None; }
+    // Odd length: left-pad one zero nibble so "abc" reads as 0x0abc
+    if cleaned.len() % 2 == 1 { cleaned.insert(0, '0'); }
+    let bytes = hex::decode(cleaned).ok()?;
+    Some(BigInt::from_bytes_be(num_bigint::Sign::Plus, &bytes))
 }
 
 /// Strictly parse BigInt from hex string
@@ -45,6 +148,17 @@ fn hex_to_bigint_strict(s: &str) -> io::Result<BigInt> {
     Ok(BigInt::from_bytes_be(num_bigint::Sign::Plus, &bytes))
 }
 
+/// Same as `hex_to_bigint_strict`, also accepting the leading '-' of `bigint_to_signed_hex`
+/// (its digits may be odd in number, see `bigint_to_hex_fixed`)
+fn signed_hex_to_bigint_strict(s: &str) -> io::Result<BigInt> {
+    let t = s.trim();
+    match t.strip_prefix('-') {
+        Some(magnitude) if magnitude.len() % 2 == 1 => hex_to_bigint_strict(&format!("0{}", magnitude)).map(|x| -x),
+        Some(magnitude) => hex_to_bigint_strict(magnitude).map(|x| -x),
+        None => hex_to_bigint_strict(t),
+    }
+}
+
 /// Write all lines to a file, creating parent dirs if needed
 /// - params: path, lines
 /// - returns: io::Result
@@ -59,13 +173,30 @@ fn write_lines(path: &str, lines: &[String]) -> io::Result<()> {
     Ok(())
 }
 
-/// Read all lines from a UTF-8 text file
-/// - params: path
+/// Split file contents into owned lines
+/// - params: data UTF-8 text
 /// - returns: Vec of lines
-/// - usage: helper for loading params and proofs
-fn read_lines(path: &str) -> io::Result<Vec<String>> {
-    let content = fs::read_to_string(path)?;
-    Ok(content.lines().map(|s| s.to_string()).collect())
+/// - usage: helper for parsing params and proofs
+fn split_lines(data: &str) -> Vec<String> {
+    data.lines().map(|s| s.to_string()).collect()
+}
+
+/// Reject proofs holding a negative group element before they reach the hex format
+/// - params: proof
+/// - returns: InvalidInput naming the first negative group element
+/// - usage: group elements are written as magnitudes, so a negative one would reload flipped;
+///   `save_proof` calls this first. Integer scalars keep their sign (t0 includes δ < 0)
+pub fn check_signs(proof: &Cuproof) -> io::Result<()> {
+    let named = [("A", &proof.A), ("S", &proof.S), ("T1", &proof.T1), ("T2", &proof.T2),
+        ("C", &proof.C), ("C_v1", &proof.C_v1), ("C_v2", &proof.C_v2)];
+    let negative = named.into_iter().find(|(_, x)| x.is_negative()).map(|(name, _)| name)
+        .or_else(|| proof.ipp_proof.L.iter().any(Signed::is_negative).then_some("ipp_L"))
+        .or_else(|| proof.ipp_proof.R.iter().any(Signed::is_negative).then_some("ipp_R"));
+    match negative {
+        Some(name) => Err(io::Error::new(io::ErrorKind::InvalidInput,
+            format!("{} is negative and the hex format drops the sign of group elements; reduce it with canonicalize_scalar", name))),
+        None => Ok(()),
+    }
 }
 
 /// Save public parameters (g, h, n) to a file as hex per line
@@ -83,56 +214,350 @@ pub fn save_params(path: &str, g: &BigInt, h: &BigInt, n: &BigInt) -> io::Result
 
 /// Load public parameters (g, h, n) from a file
 /// - params: path
-/// - returns: (g, h, n)
+/// - returns: (g, h, n), or InvalidData if they fail `validate_params`
 /// - usage: restore parameters for proving and verifying
 pub fn load_params(path: &str) -> io::Result<(BigInt, BigInt, BigInt)> {
-    let lines = read_lines(path)?;
+    parse_params(&fs::read_to_string(path)?)
+}
+
+/// Parse public parameters (g, h, n) from the text written by `save_params`
+/// - params: data file contents, e.g. from `include_str!` or a WASM host
+/// - returns: (g, h, n), or InvalidData if malformed or they fail `validate_params`
+/// - usage: parameters baked into a binary or received without a filesystem
+pub fn parse_params(data: &str) -> io::Result<(BigInt, BigInt, BigInt)> {
+    let lines = split_lines(data);
     if lines.len() < 3 { return Err(io::Error::new(io::ErrorKind::InvalidData, "params file too short")); }
     let g = hex_to_bigint_strict(&lines[0])?;
     let h = hex_to_bigint_strict(&lines[1])?;
     let n = hex_to_bigint_strict(&lines[2])?;
+    validate_params(&g, &h, &n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
     Ok((g, h, n))
 }
 
+/// How a parameter set was generated, as recorded by `save_params_json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupMode {
+    /// `fast_test_setup`
+    Fast,
+    /// `setup_256`, the default setup with the `keccak` feature
+    Bits256,
+    /// `trusted_setup` and its variants
+    Trusted,
+}
+
+impl SetupMode {
+    /// Name stored in the JSON file: "fast", "256" or "trusted"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SetupMode::Fast => "fast",
+            SetupMode::Bits256 => "256",
+            SetupMode::Trusted => "trusted",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "fast" => Some(SetupMode::Fast),
+            "256" => Some(SetupMode::Bits256),
+            "trusted" => Some(SetupMode::Trusted),
+            _ => None,
+        }
+    }
+}
+
+/// Metadata stored next to (g, h, n) by `save_params_json`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamMetadata {
+    /// Bit length of n
+    pub bits: u64,
+    pub mode: SetupMode,
+    /// Creation time as ISO 8601 UTC, e.g. "2024-05-01T12:00:00Z"
+    pub created: String,
+}
+
+impl ParamMetadata {
+    /// Metadata for modulus n generated now with `mode`
+    pub fn new(n: &BigInt, mode: SetupMode) -> Self {
+        ParamMetadata { bits: n.bits(), mode, created: iso8601_utc(std::time::SystemTime::now()) }
+    }
+}
+
+/// Format a time as ISO 8601 UTC with second precision (civil-from-days, no time zone database)
+fn iso8601_utc(t: std::time::SystemTime) -> String {
+    let secs = t.duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, rem) = ((secs / 86400) as i64, secs % 86400);
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of each 400-year era
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, rem / 3600, rem / 60 % 60, rem % 60)
+}
+
+/// Save public parameters (g, h, n) as JSON together with their metadata
+/// - params: path, g, h, n, metadata (usually `ParamMetadata::new(n, mode)`)
+/// - returns: io::Result; InvalidInput if metadata.bits is not the bit length of n
+/// - usage: self-describing parameter files; read back with `load_params_json`
+pub fn save_params_json(path: &str, g: &BigInt, h: &BigInt, n: &BigInt, metadata: &ParamMetadata) -> io::Result<()> {
+    if metadata.bits != n.bits() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+            format!("metadata says {} bits but n has {}", metadata.bits, n.bits())));
+    }
+    let lines = vec![
+        "{".to_string(),
+        format!("  \"bits\": {},", metadata.bits),
+        format!("  \"mode\": \"{}\",", metadata.mode.as_str()),
+        format!("  \"created\": \"{}\",", metadata.created),
+        format!("  \"g\": \"{}\",", bigint_to_hex(g)),
+        format!("  \"h\": \"{}\",", bigint_to_hex(h)),
+        format!("  \"n\": \"{}\"", bigint_to_hex(n)),
+        "}".to_string(),
+    ];
+    write_lines(path, &lines)
+}
+
+/// Load public parameters and metadata written by `save_params_json`
+/// - params: path
+/// - returns: ((g, h, n), metadata), or InvalidData if a field is missing or malformed, the
+///   recorded bit length does not match n, or the parameters fail `validate_params`
+/// - usage: check `metadata.bits` / `metadata.mode` before mixing parameter sets
+pub fn load_params_json(path: &str) -> io::Result<((BigInt, BigInt, BigInt), ParamMetadata)> {
+    let data = fs::read_to_string(path)?;
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    // Value after `"key":` up to the next comma, newline or closing brace, without quotes
+    let field = |key: &str| -> io::Result<&str> {
+        let tag = format!("\"{}\":", key);
+        let start = data.find(&tag).ok_or_else(|| invalid(format!("missing key {}", key)))? + tag.len();
+        let rest = &data[start..];
+        let end = rest.find([',', '\n', '}']).unwrap_or(rest.len());
+        Ok(rest[..end].trim().trim_matches('"'))
+    };
+    let bits = field("bits")?.parse::<u64>().map_err(|_| invalid("bits is not an integer".to_string()))?;
+    let mode = SetupMode::parse(field("mode")?).ok_or_else(|| invalid("unknown setup mode".to_string()))?;
+    let created = field("created")?.to_string();
+    let g = hex_to_bigint_strict(field("g")?)?;
+    let h = hex_to_bigint_strict(field("h")?)?;
+    let n = hex_to_bigint_strict(field("n")?)?;
+    if bits != n.bits() {
+        return Err(invalid(format!("file says {} bits but n has {}", bits, n.bits())));
+    }
+    validate_params(&g, &h, &n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(((g, h, n), ParamMetadata { bits, mode, created }))
+}
+
+/// Prefix of the trailing checksum line `save_proof` writes
+const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// First line of every file `save_proof` / `save_proof_fixed` writes
+///
+/// A file opening with it must end in a checksum line. Files without it predate the header and
+/// only load through `parse_proof_legacy` / `load_proof_legacy`.
+const PROOF_FILE_HEADER: &str = "cuproof-proof v2";
+
+/// A proof file whose trailing checksum does not match its content
+///
+/// Returned by `load_proof` / `parse_proof` inside an InvalidData `io::Error`, so callers can
+/// tell a corrupted file (`e.get_ref()` downcasts to this) from a proof that fails verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    /// Hex SHA-256 recorded in the file
+    pub expected: String,
+    /// Hex SHA-256 of the content actually read
+    pub actual: String,
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "proof file checksum mismatch (file corrupted): recorded {}, computed {}", self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
+/// Hex SHA-256 of proof lines joined with '\n', the bytes `save_proof` writes before the checksum
+fn lines_checksum(lines: &[String]) -> String {
+    hex::encode(Sha256::digest(lines.join("\n").as_bytes()))
+}
+
+/// `body` framed as a current proof file: header line first, checksum of everything above it last
+fn framed_proof_lines(body: Vec<String>) -> Vec<String> {
+    let mut lines = vec![PROOF_FILE_HEADER.to_string()];
+    lines.extend(body);
+    lines.push(format!("{}{}", CHECKSUM_PREFIX, lines_checksum(&lines)));
+    lines
+}
+
+/// Pop a trailing checksum line off `lines` and check it against the lines above it
+/// - returns: Ok(true) if the line was there and matches, Ok(false) if there was none, InvalidData
+///   wrapping `ChecksumMismatch` if it disagrees
+fn strip_checksum(lines: &mut Vec<String>) -> io::Result<bool> {
+    let Some(expected) = lines.last().and_then(|l| l.trim().strip_prefix(CHECKSUM_PREFIX)).map(str::to_string) else {
+        return Ok(false);
+    };
+    lines.pop();
+    let actual = lines_checksum(lines);
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, ChecksumMismatch { expected, actual }));
+    }
+    Ok(true)
+}
+
 /// Save Cuproof to a file (simple line-based hex format)
 /// - params: path, proof
 /// - returns: io::Result
-/// - usage: send proof file to verifier
+/// - usage: send proof file to verifier; a `cuproof-proof v2` header line opens the file and a last
+///   `sha256:<hex>` line checksums the lines above it
 pub fn save_proof(path: &str, proof: &Cuproof) -> io::Result<()> {
+    check_signs(proof)?;
+    write_lines(path, &framed_proof_lines(proof_lines(proof, |x| Ok(bigint_to_hex(x)), |x| Ok(bigint_to_signed_hex(x)))?))
+}
+
+/// Lines of the `save_proof` format, with group elements rendered by `element` and the
+/// remaining scalars by `scalar`
+fn proof_lines(proof: &Cuproof, element: impl Fn(&BigInt) -> io::Result<String>, scalar: impl Fn(&BigInt) -> io::Result<String>) -> io::Result<Vec<String>> {
     let mut lines = Vec::new();
     // Scalars
-    lines.push(bigint_to_hex(&proof.A));
-    lines.push(bigint_to_hex(&proof.S));
-    lines.push(bigint_to_hex(&proof.T1));
-    lines.push(bigint_to_hex(&proof.T2));
-    lines.push(bigint_to_hex(&proof.tau_x));
-    lines.push(bigint_to_hex(&proof.mu));
-    lines.push(bigint_to_hex(&proof.t_hat));
-    lines.push(bigint_to_hex(&proof.C));
-    lines.push(bigint_to_hex(&proof.C_v1));
-    lines.push(bigint_to_hex(&proof.C_v2));
-    lines.push(bigint_to_hex(&proof.t0));
-    lines.push(bigint_to_hex(&proof.t1));
-    lines.push(bigint_to_hex(&proof.t2));
-    lines.push(bigint_to_hex(&proof.tau1));
-    lines.push(bigint_to_hex(&proof.tau2));
+    lines.push(element(&proof.A)?);
+    lines.push(element(&proof.S)?);
+    lines.push(element(&proof.T1)?);
+    lines.push(element(&proof.T2)?);
+    lines.push(scalar(&proof.tau_x)?);
+    lines.push(scalar(&proof.mu)?);
+    lines.push(scalar(&proof.t_hat)?);
+    lines.push(element(&proof.C)?);
+    lines.push(element(&proof.C_v1)?);
+    lines.push(element(&proof.C_v2)?);
+    lines.push(scalar(&proof.t0)?);
+    lines.push(scalar(&proof.t1)?);
+    lines.push(scalar(&proof.t2)?);
+    lines.push(scalar(&proof.tau1)?);
+    lines.push(scalar(&proof.tau2)?);
     // IPP vectors sizes
     lines.push(proof.ipp_proof.L.len().to_string());
-    for x in &proof.ipp_proof.L { lines.push(bigint_to_hex(x)); }
+    for x in &proof.ipp_proof.L { lines.push(element(x)?); }
     lines.push(proof.ipp_proof.R.len().to_string());
-    for x in &proof.ipp_proof.R { lines.push(bigint_to_hex(x)); }
+    for x in &proof.ipp_proof.R { lines.push(element(x)?); }
     // IPP scalars
-    lines.push(bigint_to_hex(&proof.ipp_proof.a));
-    lines.push(bigint_to_hex(&proof.ipp_proof.b));
-    write_lines(path, &lines)
+    lines.push(scalar(&proof.ipp_proof.a)?);
+    lines.push(scalar(&proof.ipp_proof.b)?);
+    Ok(lines)
+}
+
+/// Byte widths of `save_proof_fixed` values for modulus n and an IPP of `levels` levels
+/// - returns: (group element width, scalar width)
+/// - usage: group elements are below n; scalars such as tau_x, mu, t_hat and the IPP a, b also
+///   pick up a factor below the 256-bit challenge bound per challenge they are multiplied by,
+///   at most levels + 3 of them, so they get 32 extra bytes for each
+fn fixed_widths(n: &BigInt, levels: usize) -> (usize, usize) {
+    let element = (n.bits() as usize).div_ceil(8);
+    (element, element + 32 * (levels + 3))
+}
+
+/// Hex of x left-padded with zeros to exactly `width` bytes; a negative x spends the first
+/// character on its '-', so the line length does not reveal the sign
+fn bigint_to_hex_fixed(x: &BigInt, width: usize) -> io::Result<String> {
+    let hex = bigint_to_hex(x);
+    let digits = 2 * width - usize::from(x.is_negative());
+    if hex.len() > digits {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("value of {} bits does not fit in {} bytes", x.bits(), width)));
+    }
+    let sign = if x.is_negative() { "-" } else { "" };
+    Ok(format!("{}{:0>w$}", sign, hex, w = digits))
+}
+
+/// Save a proof in the `save_proof` layout with every value zero-padded to a width fixed by n
+/// - params: path, proof, n modulus of the parameters the proof is for
+/// - returns: InvalidInput if a group element is negative or a value is wider than its slot
+///   (see `fixed_widths`)
+/// - usage: all proofs of one dimension under the same n give files of identical size, so the
+///   file length no longer reveals value magnitudes; read back with `load_proof_fixed`
+pub fn save_proof_fixed(path: &str, proof: &Cuproof, n: &BigInt) -> io::Result<()> {
+    check_signs(proof)?;
+    write_lines(path, &fixed_proof_lines(proof, n)?)
+}
+
+/// Lines of a `save_proof_fixed` file, header and checksum included
+fn fixed_proof_lines(proof: &Cuproof, n: &BigInt) -> io::Result<Vec<String>> {
+    let (element, scalar) = fixed_widths(n, proof.ipp_proof.L.len());
+    Ok(framed_proof_lines(proof_lines(proof, |x| bigint_to_hex_fixed(x, element), |x| bigint_to_hex_fixed(x, scalar))?))
+}
+
+/// Load a proof written by `save_proof_fixed` for modulus n
+/// - params: path, n modulus the file was written for
+/// - returns: Cuproof, or InvalidData if the file is malformed or any value is not padded to
+///   the width n prescribes
+/// - usage: inverse of `save_proof_fixed`; `load_proof` also reads these files, without the width check
+pub fn load_proof_fixed(path: &str, n: &BigInt) -> io::Result<Cuproof> {
+    let data = fs::read_to_string(path)?;
+    let proof = parse_proof(&data)?;
+    let expected = fixed_proof_lines(&proof, n)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    if expected != split_lines(&data) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "values are not padded to the fixed widths for this modulus"));
+    }
+    Ok(proof)
 }
 
 /// Load Cuproof from a file written by save_proof
 /// - params: path
-/// - returns: Cuproof
+/// - returns: Cuproof, or InvalidData wrapping `ChecksumMismatch` if the checksum line disagrees
 /// - usage: verifier loads file to verify
 pub fn load_proof(path: &str) -> io::Result<Cuproof> {
-    let lines = read_lines(path)?;
+    parse_proof(&fs::read_to_string(path)?)
+}
+
+/// Parse a Cuproof from the text written by `save_proof` or `save_proof_fixed`
+/// - params: data file contents, which must open with the `cuproof-proof v2` header and end in
+///   the checksum line
+/// - returns: Cuproof, InvalidData wrapping `ChecksumMismatch` if the checksum line disagrees
+///   (checked before any field is parsed), or InvalidData if the header or checksum line is
+///   missing or the file is malformed
+/// - usage: verify proofs received in memory (network, WASM) without touching the filesystem;
+///   files from before the header was introduced need `parse_proof_legacy`
+pub fn parse_proof(data: &str) -> io::Result<Cuproof> {
+    let mut lines = split_lines(data);
+    if lines.first().map(|l| l.trim()) != Some(PROOF_FILE_HEADER) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            format!("missing \"{}\" header; load files from older versions with load_proof_legacy", PROOF_FILE_HEADER)));
+    }
+    if !strip_checksum(&mut lines)? {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "missing checksum line"));
+    }
+    parse_proof_lines(&lines[1..])
+}
+
+/// Load a Cuproof from a file written before the `cuproof-proof v2` header, see `parse_proof_legacy`
+/// - params: path
+/// - returns: Cuproof, or the errors of `parse_proof_legacy`
+/// - usage: explicit opt-in for old files, e.g. `cuproof verify ... --legacy-proof`
+pub fn load_proof_legacy(path: &str) -> io::Result<Cuproof> {
+    parse_proof_legacy(&fs::read_to_string(path)?)
+}
+
+/// Parse a proof file written before the `cuproof-proof v2` header
+/// - params: data file contents without the header; a trailing checksum line is checked if
+///   present, but a file without one is accepted unchecked
+/// - returns: Cuproof, InvalidData wrapping `ChecksumMismatch` if the checksum line disagrees,
+///   or InvalidData if malformed; current files (with the header) go through `parse_proof`
+/// - usage: only for files known to predate the header, since a truncated file that lost its
+///   checksum line passes without any integrity check
+pub fn parse_proof_legacy(data: &str) -> io::Result<Cuproof> {
+    let mut lines = split_lines(data);
+    if lines.first().map(|l| l.trim()) == Some(PROOF_FILE_HEADER) {
+        return parse_proof(data);
+    }
+    strip_checksum(&mut lines)?;
+    parse_proof_lines(&lines)
+}
+
+/// Fields of a proof file, header and checksum line already removed
+#[allow(non_snake_case)]
+fn parse_proof_lines(lines: &[String]) -> io::Result<Cuproof> {
     let mut i = 0usize;
     let take = |i: &mut usize| -> io::Result<String> {
         let s = lines.get(*i).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unexpected end of file"))?.clone();
@@ -145,32 +570,35 @@ pub fn load_proof(path: &str) -> io::Result<Cuproof> {
     let S = hex_to_bigint_strict(&take(&mut i)?)?;
     let T1 = hex_to_bigint_strict(&take(&mut i)?)?;
     let T2 = hex_to_bigint_strict(&take(&mut i)?)?;
-    let tau_x = hex_to_bigint_strict(&take(&mut i)?)?;
-    let mu = hex_to_bigint_strict(&take(&mut i)?)?;
-    let t_hat = hex_to_bigint_strict(&take(&mut i)?)?;
+    let tau_x = signed_hex_to_bigint_strict(&take(&mut i)?)?;
+    let mu = signed_hex_to_bigint_strict(&take(&mut i)?)?;
+    let t_hat = signed_hex_to_bigint_strict(&take(&mut i)?)?;
     let C = hex_to_bigint_strict(&take(&mut i)?)?;
     let C_v1 = hex_to_bigint_strict(&take(&mut i)?)?;
     let C_v2 = hex_to_bigint_strict(&take(&mut i)?)?;
-    let t0 = hex_to_bigint_strict(&take(&mut i)?)?;
-    let t1 = hex_to_bigint_strict(&take(&mut i)?)?;
-    let t2 = hex_to_bigint_strict(&take(&mut i)?)?;
-    let tau1 = hex_to_bigint_strict(&take(&mut i)?)?;
-    let tau2 = hex_to_bigint_strict(&take(&mut i)?)?;
+    let t0 = signed_hex_to_bigint_strict(&take(&mut i)?)?;
+    let t1 = signed_hex_to_bigint_strict(&take(&mut i)?)?;
+    let t2 = signed_hex_to_bigint_strict(&take(&mut i)?)?;
+    let tau1 = signed_hex_to_bigint_strict(&take(&mut i)?)?;
+    let tau2 = signed_hex_to_bigint_strict(&take(&mut i)?)?;
 
     // IPP vectors sizes
     let l_len: usize = take(&mut i)?.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid L length"))?;
     if l_len == 0 { return Err(io::Error::new(io::ErrorKind::InvalidData, "L length must be > 0")); }
+    // Each entry takes one line, so a length beyond the file is malformed (and must not drive an allocation)
+    if l_len > lines.len() - i { return Err(io::Error::new(io::ErrorKind::InvalidData, "L length exceeds file")); }
     let mut L_vec = Vec::with_capacity(l_len);
     for _ in 0..l_len { L_vec.push(hex_to_bigint_strict(&take(&mut i)?)?); }
     let r_len: usize = take(&mut i)?.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid R length"))?;
     if r_len == 0 { return Err(io::Error::new(io::ErrorKind::InvalidData, "R length must be > 0")); }
     if r_len != l_len { return Err(io::Error::new(io::ErrorKind::InvalidData, "L and R length mismatch")); }
+    if r_len > lines.len() - i { return Err(io::Error::new(io::ErrorKind::InvalidData, "R length exceeds file")); }
     let mut R_vec = Vec::with_capacity(r_len);
     for _ in 0..r_len { R_vec.push(hex_to_bigint_strict(&take(&mut i)?)?); }
 
     // IPP scalars
-    let a = hex_to_bigint_strict(&take(&mut i)?)?;
-    let b = hex_to_bigint_strict(&take(&mut i)?)?;
+    let a = signed_hex_to_bigint_strict(&take(&mut i)?)?;
+    let b = signed_hex_to_bigint_strict(&take(&mut i)?)?;
     let zero = BigInt::from(0);
     if A == zero || S == zero || T1 == zero || T2 == zero { return Err(io::Error::new(io::ErrorKind::InvalidData, "zero scalar in header")); }
 
@@ -178,6 +606,151 @@ pub fn load_proof(path: &str) -> io::Result<Cuproof> {
     Ok(Cuproof { A, S, T1, T2, tau_x, mu, t_hat, C, C_v1, C_v2, t0, t1, t2, tau1, tau2, ipp_proof })
 }
 
+/// Version tag leading every binary proof encoding
+const PROOF_BYTES_VERSION: u8 = 1;
+
+fn push_bigint(out: &mut Vec<u8>, x: &BigInt) {
+    let (sign, bytes) = x.to_bytes_be();
+    out.push(u8::from(sign == num_bigint::Sign::Minus));
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(&bytes);
+}
+
+/// Encode a Cuproof as bytes: a version byte, then every BigInt as sign byte, u32 length and
+/// big-endian magnitude, with the IPP vectors prefixed by their u32 length
+/// - params: proof
+/// - returns: byte vector; unlike the hex file format it keeps the sign of every scalar
+/// - usage: compact transport, e.g. through `proof_to_base64`
+pub fn proof_to_bytes(proof: &Cuproof) -> Vec<u8> {
+    let mut out = vec![PROOF_BYTES_VERSION];
+    for x in [&proof.A, &proof.S, &proof.T1, &proof.T2, &proof.tau_x, &proof.mu, &proof.t_hat,
+              &proof.C, &proof.C_v1, &proof.C_v2, &proof.t0, &proof.t1, &proof.t2, &proof.tau1, &proof.tau2] {
+        push_bigint(&mut out, x);
+    }
+    for v in [&proof.ipp_proof.L, &proof.ipp_proof.R] {
+        out.extend_from_slice(&(v.len() as u32).to_be_bytes());
+        for x in v { push_bigint(&mut out, x); }
+    }
+    push_bigint(&mut out, &proof.ipp_proof.a);
+    push_bigint(&mut out, &proof.ipp_proof.b);
+    out
+}
+
+/// Cursor over an encoded proof; every read fails with InvalidData instead of panicking
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|&e| e <= self.data.len())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unexpected end of proof bytes"))?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_u32(&mut self) -> io::Result<usize> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    }
+
+    fn read_bigint(&mut self) -> io::Result<BigInt> {
+        let sign = match self.take(1)?[0] {
+            0 => num_bigint::Sign::Plus,
+            1 => num_bigint::Sign::Minus,
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid sign byte")),
+        };
+        let len = self.read_u32()?;
+        Ok(BigInt::from_bytes_be(sign, self.take(len)?))
+    }
+
+    fn read_vec(&mut self) -> io::Result<Vec<BigInt>> {
+        let len = self.read_u32()?;
+        // Every entry takes at least five bytes, so a larger count is malformed (and must not drive an allocation)
+        if len > (self.data.len() - self.pos) / 5 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "IPP vector length exceeds input"));
+        }
+        (0..len).map(|_| self.read_bigint()).collect()
+    }
+}
+
+/// Decode a Cuproof written by `proof_to_bytes`
+/// - params: data encoded bytes
+/// - returns: Cuproof, or InvalidData on a wrong version, truncation or trailing bytes
+/// - usage: inverse of `proof_to_bytes`
+#[allow(non_snake_case)]
+pub fn proof_from_bytes(data: &[u8]) -> io::Result<Cuproof> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    let mut r = ByteReader { data, pos: 0 };
+    if r.take(1)?[0] != PROOF_BYTES_VERSION { return Err(invalid("unsupported proof encoding version")); }
+    let scalars = (0..15).map(|_| r.read_bigint()).collect::<io::Result<Vec<_>>>()?;
+    let L = r.read_vec()?;
+    let R = r.read_vec()?;
+    let a = r.read_bigint()?;
+    let b = r.read_bigint()?;
+    if r.pos != data.len() { return Err(invalid("trailing bytes after proof")); }
+    if L.len() != R.len() { return Err(invalid("L and R length mismatch")); }
+
+    let mut s = scalars.into_iter();
+    let mut next = || s.next().unwrap();
+    Ok(Cuproof {
+        A: next(), S: next(), T1: next(), T2: next(), tau_x: next(), mu: next(), t_hat: next(),
+        C: next(), C_v1: next(), C_v2: next(), t0: next(), t1: next(), t2: next(), tau1: next(), tau2: next(),
+        ipp_proof: crate::range_proof::IPPProof { L, R, a, b },
+    })
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode a proof as one line of standard base64 (RFC 4648, padded) over `proof_to_bytes`
+/// - params: proof
+/// - returns: single-line string without whitespace
+/// - usage: paste proofs into chat, URLs (after percent-encoding), JSON APIs or QR codes
+pub fn proof_to_base64(proof: &Cuproof) -> String {
+    let bytes = proof_to_bytes(proof);
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (u32::from(chunk[0]) << 16)
+            | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8)
+            | u32::from(*chunk.get(2).unwrap_or(&0));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decode a proof written by `proof_to_base64`
+/// - params: s base64 text; surrounding whitespace is ignored
+/// - returns: Cuproof, or InvalidData on bad base64 or a malformed encoding
+/// - usage: inverse of `proof_to_base64`
+pub fn proof_from_base64(s: &str) -> io::Result<Cuproof> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid base64");
+    let t = s.trim().as_bytes();
+    if !t.len().is_multiple_of(4) { return Err(invalid()); }
+    let mut bytes = Vec::with_capacity(t.len() / 4 * 3);
+    for (ci, chunk) in t.chunks(4).enumerate() {
+        let last = ci == t.len() / 4 - 1;
+        let pad = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if pad > 2 || (pad > 0 && !last) { return Err(invalid()); }
+        let mut n = 0u32;
+        for &c in &chunk[..4 - pad] {
+            let digit = BASE64_ALPHABET.iter().position(|&a| a == c).ok_or_else(invalid)?;
+            n = (n << 6) | digit as u32;
+        }
+        n <<= 6 * pad as u32;
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        bytes.extend_from_slice(&decoded[..3 - pad]);
+    }
+    proof_from_bytes(&bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,4 +772,343 @@ mod tests {
         let ip = inner_product(&a, &b);
         assert_eq!(ip, BigInt::from(32)); // 1*4 + 2*5 + 3*6
     }
+
+    // Purpose: the limb-accumulating inner_product matches the plain zip-multiply-sum
+    // Params: 1024 signed entries of up to 512 bits with zeros, vectors of unequal length, empty input
+    // Output: identical BigInt to `x * y` summed per element in every case
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn inner_product_matches_naive_sum() {
+        let naive = |a: &[BigInt], b: &[BigInt]| a.iter().zip(b).map(|(x, y)| x * y).sum::<BigInt>();
+        let signed = |i: usize| {
+            let x = random_bigint(1 + i % 512);
+            match i % 5 { 0 => BigInt::zero(), 1 | 2 => -x, _ => x }
+        };
+        let a: Vec<BigInt> = (0..1024).map(signed).collect();
+        let b: Vec<BigInt> = (0..1024).map(|i| signed(i * 7 + 3)).collect();
+        assert_eq!(inner_product(&a, &b), naive(&a, &b));
+        assert_eq!(inner_product(&a[..10], &b), naive(&a[..10], &b));
+        assert_eq!(inner_product(&a, &a), naive(&a, &a));
+        let max = vec![(BigInt::from(1) << 256) - 1; 64];
+        assert_eq!(inner_product(&max, &max), naive(&max, &max));
+        assert_eq!(inner_product(&[], &b), BigInt::zero());
+    }
+
+    // Purpose: ct_eq agrees with == on random, equal, sign-flipped and differently sized values
+    // Params: 1,000 random 256-bit pairs under a 512-bit modulus plus edge cases, including
+    //         values wider than the modulus
+    // Output: assert_eq! between ct_eq and ==
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn ct_eq_matches_eq() {
+        let n = (BigInt::from(1) << 512) - 1;
+        for _ in 0..1_000 {
+            let a = random_bigint(256);
+            let b = if random_bigint(1) == BigInt::from(0) { a.clone() } else { random_bigint(256) };
+            assert_eq!(ct_eq(&a, &b, &n), a == b);
+            assert_eq!(ct_eq(&a, &-&a, &n), a == BigInt::from(0));
+        }
+        assert!(ct_eq(&BigInt::from(0), &BigInt::from(0), &n));
+        assert!(!ct_eq(&BigInt::from(1), &BigInt::from(256), &n));
+        assert!(!ct_eq(&BigInt::from(0x0102), &BigInt::from(0x02), &n));
+        let wide = BigInt::from(1) << 1024;
+        assert!(ct_eq(&wide, &wide, &n));
+        assert!(!ct_eq(&wide, &BigInt::from(0), &n));
+        assert!(!ct_eq(&BigInt::from(0), &(&wide + 1), &BigInt::from(7)));
+    }
+
+    // Purpose: params and proofs parse from in-memory strings, no filesystem access
+    // Params: fast-setup params and a SHA-256 proof for v = 42 in [1, 100], embedded with include_str!
+    // Output: parsed proof verifies; truncated input is InvalidData
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn parse_params_and_proof_from_str() {
+        use crate::fiat_shamir::Sha256Hasher;
+        use crate::range_proof::range_binding_holds;
+        use crate::verify::cuproof_verify_with_hasher;
+        const PARAMS: &str = include_str!("../tests/fixtures/params_fast.txt");
+        const PROOF: &str = include_str!("../tests/fixtures/proof_fast.txt");
+        let (g, h, n) = parse_params(PARAMS).unwrap();
+        let proof = parse_proof(PROOF).unwrap();
+        // The fixture was produced with SHA-256 challenges, whichever hasher feature is enabled
+        assert!(cuproof_verify_with_hasher::<Sha256Hasher>(&proof, &g, &h, &n).is_ok());
+        assert!(range_binding_holds(&proof.C, &proof.C_v1, &proof.C_v2, &BigInt::from(1), &BigInt::from(100), &g, &n));
+
+        let truncated = &PROOF[..PROOF.len() / 2];
+        assert_eq!(parse_proof(truncated).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(parse_params("").unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    // Purpose: malformed proof files error instead of panicking (regression set for fuzz_load_proof)
+    // Params: every line-prefix of the fixture proof and of its header-less legacy body, a huge
+    //         L length, non-numeric lengths
+    // Output: Err(InvalidData) for each malformed input
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn parse_proof_rejects_malformed_without_panic() {
+        const PROOF: &str = include_str!("../tests/fixtures/proof_fast.txt");
+        let lines: Vec<&str> = PROOF.lines().collect();
+        let body = &lines[1..lines.len() - 1];
+        for k in 0..lines.len() {
+            assert!(parse_proof(&lines[..k].join("\n")).is_err(), "prefix of {} lines parsed", k);
+        }
+        for k in 0..body.len() {
+            assert!(parse_proof_legacy(&body[..k].join("\n")).is_err(), "legacy prefix of {} lines parsed", k);
+        }
+        for l_len in ["18446744073709551615", "-1", "x", "0"] {
+            let mut bad = body.to_vec();
+            bad[15] = l_len;
+            assert_eq!(parse_proof_legacy(&bad.join("\n")).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    // Purpose: the checksum line is mandatory for current files; header-less files need the legacy path
+    // Params: fixture proof, the same file without its checksum line, and its legacy body
+    // Output: parse_proof rejects the file missing its checksum and the legacy body;
+    //         parse_proof_legacy reads the legacy body and the current file
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn checksum_required_unless_legacy() {
+        const PROOF: &str = include_str!("../tests/fixtures/proof_fast.txt");
+        let proof = parse_proof(PROOF).unwrap();
+        let lines: Vec<&str> = PROOF.lines().collect();
+
+        let unchecked = lines[..lines.len() - 1].join("\n");
+        assert_eq!(parse_proof(&unchecked).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        // The header promises a checksum, so the legacy path does not waive it either
+        assert!(parse_proof_legacy(&unchecked).is_err());
+
+        let legacy = lines[1..lines.len() - 1].join("\n");
+        assert_eq!(parse_proof(&legacy).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(parse_proof_legacy(&legacy).unwrap(), proof);
+        assert_eq!(parse_proof_legacy(PROOF).unwrap(), proof);
+    }
+
+    // Purpose: lenient hex parsing accepts separators, padding whitespace and odd length
+    // Params: "0x12_34", " 1234 ", "abc", "12 34"
+    // Output: equality assertions; the strict parser still rejects separators; invalid input is
+    //         0 for hex_to_bigint and None for try_hex_to_bigint
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn hex_to_bigint_lenient_inputs() {
+        assert_eq!(hex_to_bigint("0x12_34"), BigInt::from(0x1234));
+        assert_eq!(hex_to_bigint(" 1234 "), BigInt::from(0x1234));
+        assert_eq!(hex_to_bigint("abc"), BigInt::from(0xabc));
+        assert_eq!(hex_to_bigint("12 34"), BigInt::from(0x1234));
+        assert_eq!(hex_to_bigint("0XFF"), BigInt::from(0xff));
+        assert!(hex_to_bigint_strict("12_34").is_err());
+        // the fallible variant rejects what the lenient one maps to 0
+        assert_eq!(try_hex_to_bigint("0x12_34"), Some(BigInt::from(0x1234)));
+        assert_eq!(try_hex_to_bigint("00"), Some(BigInt::zero()));
+        for bad in ["xyz", "12g4", "", "0x", " _ "] {
+            assert_eq!(hex_to_bigint(bad), BigInt::zero());
+            assert_eq!(try_hex_to_bigint(bad), None, "{:?}", bad);
+        }
+    }
+
+    // Purpose: random_bigint_mod stays in [0, n) and its low bit is unbiased
+    // Params: 10,000 samples modulo the fast test modulus and a small modulus
+    // Output: range assertions and a parity count within ~6 sigma of 5,000
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn random_bigint_mod_bounded_and_unbiased() {
+        use crate::setup::fast_test_setup;
+        use num_integer::Integer;
+        let (_g, _h, n) = fast_test_setup();
+        for modulus in [n, BigInt::from(1000)] {
+            let mut odd = 0usize;
+            for _ in 0..10_000 {
+                let r = random_bigint_mod(&modulus);
+                assert!(r >= BigInt::from(0) && r < modulus);
+                if r.is_odd() { odd += 1; }
+            }
+            assert!((4_700..=5_300).contains(&odd), "low bit biased: {} odd of 10000", odd);
+        }
+    }
+
+    // Purpose: proofs compare equal to their clones and survive a save/load round-trip unchanged
+    // Params: small demo range, proof written to the system temp dir
+    // Output: assert_eq! on whole proofs (needs Debug + PartialEq)
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn proof_clone_and_roundtrip_equal() {
+        use crate::setup::fast_test_setup;
+        use crate::range_proof::cuproof_prove;
+        let (g, h, n) = fast_test_setup();
+        let proof = cuproof_prove(&BigInt::from(42), &random_bigint(128), &BigInt::from(1), &BigInt::from(100), &g, &h, &n);
+        assert_eq!(proof.clone(), proof);
+
+        let path = std::env::temp_dir().join(format!("cuproof_roundtrip_src_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_proof(path, &proof).unwrap();
+        let loaded = load_proof(path).unwrap();
+        let _ = fs::remove_file(path);
+        assert_eq!(loaded, proof);
+    }
+
+    // Purpose: a bit-flipped proof file is reported as corrupted, not as an invalid proof
+    // Params: fixture proof saved with save_proof, one hex character of A flipped
+    // Output: load_proof fails with ChecksumMismatch; the same flip read as a legacy file without
+    //         header and checksum still parses and only then fails verification
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn flipped_hex_char_fails_checksum() {
+        use crate::verify::cuproof_verify;
+        let (g, h, n) = parse_params(include_str!("../tests/fixtures/params_fast.txt")).unwrap();
+        let proof = parse_proof(include_str!("../tests/fixtures/proof_fast.txt")).unwrap();
+        let path = std::env::temp_dir().join(format!("cuproof_checksum_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_proof(path, &proof).unwrap();
+        assert_eq!(load_proof(path).unwrap(), proof);
+
+        let mut lines = split_lines(&fs::read_to_string(path).unwrap());
+        assert!(lines.last().unwrap().starts_with(CHECKSUM_PREFIX));
+        let last = lines[1].pop().unwrap();
+        lines[1].push(if last == '0' { '1' } else { '0' });
+        fs::write(path, lines.join("\n")).unwrap();
+        let err = load_proof(path).unwrap_err();
+        let _ = fs::remove_file(path);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mismatch = err.get_ref().and_then(|e| e.downcast_ref::<ChecksumMismatch>()).expect("checksum error");
+        assert_ne!(mismatch.expected, mismatch.actual);
+
+        let unchecked = parse_proof_legacy(&lines[1..lines.len() - 1].join("\n")).unwrap();
+        assert!(!cuproof_verify(&unchecked, &g, &h, &n));
+    }
+
+    // Purpose: fixed-width files have one size per modulus and dimension and round-trip unchanged
+    // Params: fast_test_setup params, v = 1 in [1, 100] and v = 10^6 in [1, 2^40]
+    // Output: equal file lengths; both reload equal to the proofs; the plain save_proof file and a
+    //         modulus of another size are rejected by load_proof_fixed
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn fixed_size_proofs_have_equal_length() {
+        use crate::setup::fast_test_setup;
+        use crate::range_proof::cuproof_prove;
+        let (g, h, n) = fast_test_setup();
+        let small = cuproof_prove(&BigInt::from(1), &random_bigint(128), &BigInt::from(1), &BigInt::from(100), &g, &h, &n);
+        let large = cuproof_prove(&BigInt::from(1_000_000), &random_bigint(256), &BigInt::from(1), &(BigInt::from(1) << 40u32), &g, &h, &n);
+        let path = |name: &str| std::env::temp_dir().join(format!("cuproof_fixed_{}_{}.txt", name, std::process::id()));
+        let (small_path, large_path, plain_path) = (path("small"), path("large"), path("plain"));
+        let (small_s, large_s, plain_s) = (small_path.to_str().unwrap(), large_path.to_str().unwrap(), plain_path.to_str().unwrap());
+
+        save_proof_fixed(small_s, &small, &n).unwrap();
+        save_proof_fixed(large_s, &large, &n).unwrap();
+        save_proof(plain_s, &small).unwrap();
+        assert_eq!(fs::metadata(small_s).unwrap().len(), fs::metadata(large_s).unwrap().len());
+        assert_eq!(load_proof_fixed(small_s, &n).unwrap(), small);
+        assert_eq!(load_proof_fixed(large_s, &n).unwrap(), large);
+        assert_eq!(load_proof(small_s).unwrap(), small);
+        assert_eq!(load_proof_fixed(plain_s, &n).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(load_proof_fixed(small_s, &(&n << 64u32)).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        for p in [small_s, large_s, plain_s] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    // Purpose: base64 blob is one line, round-trips to an identical proof, and still verifies
+    // Params: fast_test_setup params, range [1, 100], v = 42
+    // Output: equality, byte-identical re-encoding, verify pass; bad input rejected
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn proof_base64_roundtrip_and_verify() {
+        use crate::setup::fast_test_setup;
+        use crate::range_proof::cuproof_prove;
+        use crate::verify::cuproof_verify_with_range;
+        let (g, h, n) = fast_test_setup();
+        let (a, b) = (BigInt::from(1), BigInt::from(100));
+        let proof = cuproof_prove(&BigInt::from(42), &random_bigint(256), &a, &b, &g, &h, &n);
+
+        let blob = proof_to_base64(&proof);
+        assert!(!blob.chars().any(char::is_whitespace));
+        let decoded = proof_from_base64(&blob).unwrap();
+        assert_eq!(decoded, proof);
+        assert_eq!(proof_to_bytes(&decoded), proof_to_bytes(&proof));
+        assert!(cuproof_verify_with_range(&decoded, &g, &h, &n, &a, &b));
+
+        assert!(proof_from_base64(&blob[..blob.len() - 4]).is_err());
+        assert!(proof_from_base64(&format!("{}AAAA", blob)).is_err());
+        assert!(proof_from_base64("not base64!").is_err());
+    }
+
+    // Purpose: a negative value is never written with its sign silently dropped
+    // Params: fixture proof (t0 < 0) with A replaced by A - n (same residue, negative)
+    // Output: save_proof fails with InvalidInput; canonicalize_scalar restores A and the saved
+    //         proof reloads unchanged, t0 keeping its sign, and still verifies
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn negative_values_are_canonicalized_or_rejected() {
+        use crate::fiat_shamir::Sha256Hasher;
+        use crate::verify::cuproof_verify_with_hasher;
+        let (g, h, n) = parse_params(include_str!("../tests/fixtures/params_fast.txt")).unwrap();
+        let proof = parse_proof(include_str!("../tests/fixtures/proof_fast.txt")).unwrap();
+        let path = std::env::temp_dir().join(format!("cuproof_negative_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let mut negative = proof.clone();
+        negative.A = &proof.A - &n;
+        assert_eq!(save_proof(path, &negative).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert!(!Path::new(path).exists());
+
+        negative.A = canonicalize_scalar(&negative.A, &n);
+        assert_eq!(negative.A, proof.A);
+        save_proof(path, &negative).unwrap();
+        let loaded = load_proof(path).unwrap();
+        let _ = fs::remove_file(path);
+        assert_eq!(loaded, proof);
+        assert!(loaded.t0.is_negative());
+        assert!(cuproof_verify_with_hasher::<Sha256Hasher>(&loaded, &g, &h, &n).is_ok());
+        assert_eq!(canonicalize_scalar(&BigInt::from(-1), &n), &n - 1);
+    }
+
+    // Purpose: JSON parameter files round-trip (g, h, n) together with their metadata
+    // Params: fast_test_setup params, mode "fast", a fixed and a current timestamp
+    // Output: identical params and metadata after reload; a bit length that disagrees with n is
+    //         rejected on save and on load
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn params_json_round_trip_keeps_metadata() {
+        use crate::setup::fast_test_setup;
+        let (g, h, n) = fast_test_setup();
+        let path = std::env::temp_dir().join(format!("cuproof_params_json_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let meta = ParamMetadata::new(&n, SetupMode::Fast);
+        assert_eq!(meta.bits, n.bits());
+        assert!(meta.created.ends_with('Z') && meta.created.len() == 20);
+        save_params_json(path, &g, &h, &n, &meta).unwrap();
+        let (params, loaded) = load_params_json(path).unwrap();
+        assert_eq!(params, (g.clone(), h.clone(), n.clone()));
+        assert_eq!(loaded, meta);
+
+        assert_eq!(iso8601_utc(std::time::UNIX_EPOCH + std::time::Duration::from_secs(951_782_400)), "2000-02-29T00:00:00Z");
+
+        let wrong = ParamMetadata { bits: 256, ..meta };
+        assert_eq!(save_params_json(path, &g, &h, &n, &wrong).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        let text = fs::read_to_string(path).unwrap().replace(&format!("\"bits\": {}", n.bits()), "\"bits\": 256");
+        fs::write(path, text).unwrap();
+        assert_eq!(load_params_json(path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let _ = fs::remove_file(path);
+    }
+
+    // Purpose: the shared bound helpers get the boundary values right, including 64-bit widths
+    // Params: [10, 20] with v = 9..=21; 2^64 - 1 and 2^64 against 64 bits; widths 0, 1, 65, u32::MAX
+    // Output: inclusive range membership; 2^64 - 1 fits 64 bits, 2^64 does not, negatives never fit
+    // Usage: `cargo test -- src::util` or `cargo test`
+    #[test]
+    fn range_and_bit_width_boundaries() {
+        let (a, b) = (BigInt::from(10), BigInt::from(20));
+        assert!(!value_in_range(&BigInt::from(9), &a, &b));
+        assert!(value_in_range(&a, &a, &b) && value_in_range(&b, &a, &b));
+        assert!(!value_in_range(&BigInt::from(21), &a, &b));
+
+        let two_64 = BigInt::from(1) << 64;
+        assert!(value_fits_bits(&(&two_64 - 1), 64));
+        assert!(!value_fits_bits(&two_64, 64));
+        assert!(value_fits_bits(&two_64, 65));
+        assert!(value_fits_bits(&BigInt::from(0), 0) && !value_fits_bits(&BigInt::from(1), 0));
+        assert!(value_fits_bits(&BigInt::from(1), 1) && !value_fits_bits(&BigInt::from(2), 1));
+        assert!(value_fits_bits(&two_64, u32::MAX));
+        assert!(!value_fits_bits(&BigInt::from(-1), 64));
+    }
 }
@@ -1,60 +1,435 @@
 use crate::{util::*, fiat_shamir::*, commitment::*};
-use crate::range_proof::Cuproof;
+use crate::backend::{GroupBackend, RsaBackend};
+use crate::range_proof::{Cuproof, MinimalCuproof, VerifierProof, Challenges, IPPProof, RangeWeights, bit_range, IppGenerators, ProveError, DEFAULT_DIMENSION, fold_ipp_commitment, fold_ipp_generators, cuproof_prove_with_challenges, expected_auxiliary, range_binding_holds, range_blocks, range_relation_holds, ipp_statement};
 use num_bigint::BigInt;
+use num_traits::Signed;
+use std::borrow::Cow;
+use std::fmt;
 
+/// Reason a proof was rejected, one variant per verifier check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+	/// A Fiat–Shamir challenge reduced to zero
+	ZeroChallenge,
+	/// T1 is not Commit(t1, tau1)
+	T1Mismatch,
+	/// T2 is not Commit(t2, tau2)
+	T2Mismatch,
+	/// t_hat != t0 + t1 x + t2 x^2
+	THatInconsistent,
+	/// g^t_hat h^tau_x != C_v1^z C_v2^(z^2) g^δ T1^x T2^(x^2): t_hat and tau_x do not open
+	/// the block-weighted commitments, i.e. the witness squares do not sum to v1 and v2
+	THatCommitmentMismatch,
+	/// The IPP does not open (A S^x)^Z g_vec^(-Z y) h_vec^(Z y) u^t_hat h^(-mu): t_hat is not
+	/// <l, r> for the vectors committed in A and S
+	THatIppMismatch,
+	/// IPP L and R vectors have different lengths
+	IppLengthMismatch,
+	/// IPP recursion depth does not match the proof dimension
+	IppLevelMismatch { expected: usize, found: usize },
+	/// The proof was built at dimension `found` (see `Cuproof::dimension`), the verifier assumed `expected`
+	DimensionMismatch { expected: usize, found: usize },
+	/// A commitment is zero modulo n
+	ZeroCommitment,
+	/// Two of C, C_v1, C_v2 are equal
+	DuplicateCommitment,
+	/// The public range is empty or degenerate (a >= b)
+	InvalidRange,
+	/// C_v1, C_v2 are not derived from C and the public range [a, b]
+	RangeBindingMismatch,
+	/// The claimed proof dimension is not a non-zero power of two
+	InvalidDimension(usize),
+	/// `prove_and_verify` could not build the proof (v outside [a, b])
+	Prove(ProveError),
+	/// A group element (named field, or ipp_L / ipp_R entry) is not in [1, n)
+	ElementOutOfRange(&'static str),
+}
+
+impl fmt::Display for VerifyError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			VerifyError::IppLevelMismatch { expected, found } =>
+				write!(f, "IppLevelMismatch (expected {} levels, found {})", expected, found),
+			VerifyError::DimensionMismatch { expected, found } =>
+				write!(f, "DimensionMismatch (verifier expects dimension {}, proof has {})", expected, found),
+			VerifyError::Prove(e) => write!(f, "Prove ({})", e),
+			other => write!(f, "{:?}", other),
+		}
+	}
+}
+
+impl std::error::Error for VerifyError {}
+
+impl Cuproof {
+	/// Cheap pre-check that every group element lies in [1, n)
+	/// - params: n modulus of the parameters the proof is verified against
+	/// - returns: ElementOutOfRange naming the first offending field
+	/// - usage: call before verifying untrusted proofs; covers A, S, T1, T2, C, C_v1, C_v2 and
+	///   the IPP L/R entries only, since scalars such as t0, t_hat, mu and tau_x are integers
+	///   that may legitimately exceed n
+	pub fn check_ranges(&self, n: &BigInt) -> Result<(), VerifyError> {
+		let in_range = |x: &BigInt| x.is_positive() && x < n;
+		let named = [("A", &self.A), ("S", &self.S), ("T1", &self.T1), ("T2", &self.T2),
+			("C", &self.C), ("C_v1", &self.C_v1), ("C_v2", &self.C_v2)];
+		for (name, x) in named {
+			if !in_range(x) { return Err(VerifyError::ElementOutOfRange(name)); }
+		}
+		if !self.ipp_proof.L.iter().all(in_range) { return Err(VerifyError::ElementOutOfRange("ipp_L")); }
+		if !self.ipp_proof.R.iter().all(in_range) { return Err(VerifyError::ElementOutOfRange("ipp_R")); }
+		Ok(())
+	}
+}
+
+/// Structural verification only: challenges, T1/T2 openings, the t_hat polynomial, the IPP
+/// opening of t_hat and its binding to C_v1, C_v2
+/// - returns: true if the proof is well-formed for (g, h, n); says nothing about which range it is for
+/// - usage: callers that only hold the proof and params; add the range later with `cuproof_verify_with_range`
 pub fn cuproof_verify(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
-	// 1. Fiat–Shamir
-	let y = fiat_shamir(&[&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2]) % n;
-	if y == BigInt::from(0) { return false; }
-	let z = fiat_shamir(&[&y]) % n;
-	if z == BigInt::from(0) { return false; }
-	let x = fiat_shamir(&[&proof.T1, &proof.T2]) % n;
-	if x == BigInt::from(0) { return false; }
-
-	// 2. Check T1, T2 commitments
-	if pedersen_commit(g, h, &proof.t1, &proof.tau1, n) != proof.T1 { return false; }
-	if pedersen_commit(g, h, &proof.t2, &proof.tau2, n) != proof.T2 { return false; }
-
-	// 3. Verify t_hat consistency: t_hat ?= t0 + t1 x + t2 x^2
-	let rhs_t = &proof.t0 + &(&proof.t1 * &x) + &(&proof.t2 * &x * &x);
-	if proof.t_hat != rhs_t { return false; }
-
-	// 4. Verify commitment consistency for t_hat
-	let lhs = pedersen_commit(g, h, &proof.t_hat, &proof.tau_x, n);
-	// Construct a commitment to rhs_t using tau_x (already provided)
-	let rhs = pedersen_commit(g, h, &rhs_t, &proof.tau_x, n);
-	if lhs != rhs { return false; }
-
-	// 5. Verify IPP proof (simplified verification)
-	// In a full implementation, this would verify the recursive structure
-	if proof.ipp_proof.L.len() != proof.ipp_proof.R.len() { return false; }
+	cuproof_verify_detailed(proof, g, h, n).is_ok()
+}
+
+/// Same as `cuproof_verify`, optionally reusing a `GeneratorContext` built for (g, h, n)
+/// so that batches of verifications amortize the fixed-base precomputation.
+pub fn cuproof_verify_with_context(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt, ctx: Option<&GeneratorContext>) -> bool {
+	let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+	derive_challenges(proof, &backend)
+		.and_then(|ch| verify_with_challenges(proof, g, h, n, ctx, &backend, &ch, DEFAULT_DIMENSION))
+		.is_ok()
+}
+
+/// Same result as `cuproof_verify(proof, &ctx.g, &ctx.h, &ctx.n)`, taking the parameters and
+/// the precomputed tables from `ctx`; build the context once and reuse it across a batch
+pub fn cuproof_verify_ctx(proof: &Cuproof, ctx: &GeneratorContext) -> bool {
+	derive_challenges(proof, ctx)
+		.and_then(|ch| verify_with_challenges(proof, &ctx.g, &ctx.h, &ctx.n, Some(ctx), ctx, &ch, DEFAULT_DIMENSION))
+		.is_ok()
+}
+
+/// Same checks as `cuproof_verify`, but reports which one failed
+pub fn cuproof_verify_detailed(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<(), VerifyError> {
+	cuproof_verify_with_hasher::<DefaultChallengeHasher>(proof, g, h, n)
+}
+
+/// Same checks as `cuproof_verify_detailed`, recomputing the challenges with `H`;
+/// pair with `cuproof_prove_with_hasher::<H>`
+pub fn cuproof_verify_with_hasher<H: ChallengeHasher>(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<(), VerifyError> {
+	let backend = RsaBackend::<H>::new(g, h, n);
+	let ch = derive_challenges(proof, &backend)?;
+	verify_with_challenges(proof, g, h, n, None, &backend, &ch, DEFAULT_DIMENSION)
+}
+
+/// Run the algebraic checks of `cuproof_verify_detailed` with externally supplied challenges
+/// - params: proof, g/h/n public parameters, y/z/x challenges (e.g. from a reference implementation)
+/// - returns: true if the checks pass; the Fiat–Shamir recomputation is skipped entirely
+/// - usage: interop debugging; if this accepts the reference challenges but `cuproof_verify`
+///   rejects, the mismatch is in challenge derivation rather than in the algebra.
+///   x enters the polynomial check, and all three the IPP statement; the IPP's own fold
+///   challenges are still hashed with the default hasher
+pub fn cuproof_verify_with_challenges(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt, y: &BigInt, z: &BigInt, x: &BigInt) -> bool {
+	let ch = Challenges { y: y.clone(), z: z.clone(), x: x.clone() };
+	let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+	verify_with_challenges(proof, g, h, n, None, &backend, &ch, DEFAULT_DIMENSION).is_ok()
+}
+
+/// Everything `cuproof_verify_trace` recomputed, for auditing a verification
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyTrace {
+	/// Fiat–Shamir challenges: y = H(A, S, C, C_v1, C_v2), z = H(y), x = H(T1, T2)
+	pub y: BigInt,
+	pub z: BigInt,
+	pub x: BigInt,
+	/// Commit(t1, tau1) and Commit(t2, tau2), compared with the proof's T1 and T2
+	pub T1: BigInt,
+	pub T2: BigInt,
+	/// t0 + t1 x + t2 x^2, compared with t_hat
+	pub t_poly: BigInt,
+	/// Commit(t_hat, tau_x) and C_v1^z C_v2^(z^2) g^δ T1^x T2^(x^2), compared with each other
+	pub t_hat_commitment: BigInt,
+	pub t_poly_commitment: BigInt,
+	/// IPP statement (A S^x)^Z g_vec^(-Z y) h_vec^(Z y) u^t_hat h^(-mu); None if the IPP has the
+	/// wrong shape
+	pub ipp_statement: Option<BigInt>,
+	/// Every check in verification order, named after the `VerifyError` it raises, and whether it passed
+	pub checks: Vec<(&'static str, bool)>,
+}
+
+/// Run every check of `cuproof_verify` without stopping at the first failure, and record
+/// the challenges, recomputed commitments and per-check results
+/// - returns: (valid, trace); valid is what `cuproof_verify` returns
+/// - usage: audits; `cuproof_verify_detailed` is enough to learn why a proof failed
+#[allow(non_snake_case)]
+pub fn cuproof_verify_trace(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt) -> (bool, VerifyTrace) {
+	let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+	let zero = BigInt::from(0);
+	let y = backend.challenge(&[&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2]);
+	let z = backend.challenge(&[&y]);
+	let x = backend.challenge(&[&proof.T1, &proof.T2]);
+	let mut checks = vec![("ZeroChallenge", y != zero && z != zero && x != zero)];
+
+	let T1 = pedersen_commit(g, h, &proof.t1, &proof.tau1, n);
+	let T2 = pedersen_commit(g, h, &proof.t2, &proof.tau2, n);
+	checks.push(("T1Mismatch", ct_eq(&T1, &proof.T1, n)));
+	checks.push(("T2Mismatch", ct_eq(&T2, &proof.T2, n)));
+
+	let t_poly = &proof.t0 + &(&proof.t1 * &x) + &(&proof.t2 * &x * &x);
+	checks.push(("THatInconsistent", proof.t_hat == t_poly));
+
+	let lengths_match = proof.ipp_proof.L.len() == proof.ipp_proof.R.len();
+	let levels_match = expected_ipp_levels(DEFAULT_DIMENSION) == Ok(proof.ipp_proof.L.len());
+	checks.push(("IppLengthMismatch", lengths_match));
+	checks.push(("DimensionMismatch", levels_match));
+
+	checks.push(("ZeroCommitment", [&proof.A, &proof.S, &proof.T1, &proof.T2, &proof.C, &proof.C_v1, &proof.C_v2]
+		.iter().all(|c| *c % n != zero)));
+	checks.push(("DuplicateCommitment", proof.C != proof.C_v1 && proof.C != proof.C_v2 && proof.C_v1 != proof.C_v2));
+
+	let weights = range_weights(&backend, &y, &z, DEFAULT_DIMENSION);
+	let ipp_statement = (lengths_match && levels_match).then(|| {
+		let gens = IppGenerators::new(&backend, DEFAULT_DIMENSION);
+		let P = ipp_statement(&backend, &gens, &proof.A, &proof.S, &proof.t_hat, &proof.mu, &x, &weights);
+		checks.push(("THatIppMismatch", verify_inner_product(&backend, &weights.generators(&backend, &gens), &P, &proof.ipp_proof)));
+		P
+	});
+	if ipp_statement.is_none() { checks.push(("THatIppMismatch", false)); }
+
+	let t_hat_commitment = pedersen_commit(g, h, &proof.t_hat, &proof.tau_x, n);
+	let t_poly_commitment = multi_mod_exp(&[proof.C_v1.clone(), proof.C_v2.clone(), proof.T1.clone(), proof.T2.clone(), g.clone()],
+		&[weights.blocks[0].clone(), weights.blocks[1].clone(), x.clone(), &x * &x, weights.delta()], n);
+	checks.push(("THatCommitmentMismatch", ct_eq(&t_hat_commitment, &t_poly_commitment, n)));
+
+	let valid = checks.iter().all(|(_, passed)| *passed);
+	(valid, VerifyTrace { y, z, x, T1, T2, t_poly, t_hat_commitment, t_poly_commitment, ipp_statement, checks })
+}
+
+/// Recompute the Fiat–Shamir challenges (y, z, x) from the proof transcript
+fn derive_challenges<B: GroupBackend<Element = BigInt>>(proof: &Cuproof, backend: &B) -> Result<Challenges, VerifyError> {
+	transcript_challenges(backend, [&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2], [&proof.T1, &proof.T2])
+}
+
+/// y = H(A, S, C, C_v1, C_v2), z = H(y), x = H(T1, T2), rejecting any zero challenge
+fn transcript_challenges<B: GroupBackend<Element = BigInt>>(backend: &B, commitments: [&BigInt; 5], t: [&BigInt; 2]) -> Result<Challenges, VerifyError> {
+	let y = backend.challenge(&commitments);
+	if y == BigInt::from(0) { return Err(VerifyError::ZeroChallenge); }
+	let z = backend.challenge(&[&y]);
+	if z == BigInt::from(0) { return Err(VerifyError::ZeroChallenge); }
+	let x = backend.challenge(&t);
+	if x == BigInt::from(0) { return Err(VerifyError::ZeroChallenge); }
+	Ok(Challenges { y, z, x })
+}
+
+/// Algebraic checks of the verifier, given already-derived challenges; `backend` must hash
+/// the IPP fold challenges like the prover's
+#[allow(clippy::too_many_arguments)]
+fn verify_with_challenges<B: GroupBackend<Element = BigInt>>(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt, ctx: Option<&GeneratorContext>, backend: &B, ch: &Challenges, dimension: usize) -> Result<(), VerifyError> {
+	let ctx = ctx.filter(|c| c.matches(g, h, n));
+	let commit = |m: &BigInt, r: &BigInt| match ctx {
+		Some(c) => c.commit(m, r),
+		None => pedersen_commit(g, h, m, r, n),
+	};
+	if ch.y == BigInt::from(0) || ch.z == BigInt::from(0) || ch.x == BigInt::from(0) { return Err(VerifyError::ZeroChallenge); }
+
+	// 2. The prover's openings must match T1, T2
+	if !ct_eq(&commit(&proof.t1, &proof.tau1), &proof.T1, n) { return Err(VerifyError::T1Mismatch); }
+	if !ct_eq(&commit(&proof.t2, &proof.tau2), &proof.T2, n) { return Err(VerifyError::T2Mismatch); }
+
+	verify_verifier_with_challenges(&proof.to_verifier(), n, backend, ch, dimension)?;
+
+	// 8. The openings must evaluate to t_hat: t_hat ?= t0 + t1 x + t2 x^2
+	let x = &ch.x;
+	let rhs_t = &proof.t0 + &(&proof.t1 * x) + &(&proof.t2 * x * x);
+	if proof.t_hat != rhs_t { return Err(VerifyError::THatInconsistent); }
+
+	Ok(())
+}
+
+/// Weights of the single-value relation: block 0 opens C_v1, block 1 C_v2 (see `range_blocks`)
+fn range_weights<B: GroupBackend<Element = BigInt>>(backend: &B, y: &BigInt, z: &BigInt, dimension: usize) -> RangeWeights {
+	RangeWeights::new(backend, y, z, &range_blocks(1, dimension, dimension), 2)
+}
+
+/// The checks of `verify_with_challenges` that read only `VerifierProof` fields: the IPP binds
+/// t_hat to A and S, and the tau_x relation binds it to C_v1, C_v2, T1 and T2
+fn verify_verifier_with_challenges<B: GroupBackend<Element = BigInt>>(proof: &VerifierProof, n: &BigInt, backend: &B, ch: &Challenges, dimension: usize) -> Result<(), VerifyError> {
+	if ch.y == BigInt::from(0) || ch.z == BigInt::from(0) || ch.x == BigInt::from(0) { return Err(VerifyError::ZeroChallenge); }
+
+	// 3. Verify IPP proof structure
+	if proof.ipp_proof.L.len() != proof.ipp_proof.R.len() { return Err(VerifyError::IppLengthMismatch); }
 	
 	// Check that we have the expected number of recursion levels
-	// For dimension 64, we expect log2(64) = 6 levels
-	let expected_levels = (64.0_f64).log2().ceil() as usize;
-	if proof.ipp_proof.L.len() != expected_levels { return false; }
-
-	// 6. Basic sanity: commitments must be within modulus and non-zero
-	if &proof.A % n == BigInt::from(0) { return false; }
-	if &proof.S % n == BigInt::from(0) { return false; }
-	if &proof.T1 % n == BigInt::from(0) { return false; }
-	if &proof.T2 % n == BigInt::from(0) { return false; }
-	if &proof.C % n == BigInt::from(0) { return false; }
-	if &proof.C_v1 % n == BigInt::from(0) { return false; }
-	if &proof.C_v2 % n == BigInt::from(0) { return false; }
-
-	// 7. Verify that C_v1 and C_v2 are consistent with C in a coarse way
+	// For dimension 64, we expect log2(64) = 6 levels (integer-exact, see `expected_ipp_levels`)
+	let expected_levels = expected_ipp_levels(dimension)?;
+	if proof.ipp_proof.L.len() != expected_levels {
+		return Err(match proof.dimension() {
+			Some(found) => VerifyError::DimensionMismatch { expected: dimension, found },
+			None => VerifyError::IppLevelMismatch { expected: expected_levels, found: proof.ipp_proof.L.len() },
+		});
+	}
+
+	// 4. Basic sanity: commitments must be within modulus and non-zero
+	for c in [&proof.A, &proof.S, &proof.T1, &proof.T2, &proof.C, &proof.C_v1, &proof.C_v2] {
+		if c % n == BigInt::from(0) { return Err(VerifyError::ZeroCommitment); }
+	}
+
+	// 5. Verify that C_v1 and C_v2 are consistent with C in a coarse way
 	// Note: In a rigorous design, we would prove relations for v1, v2.
 	// Here we at least ensure they are not trivially equal or zero modulo n.
-	if &proof.C == &proof.C_v1 { return false; }
-	if &proof.C == &proof.C_v2 { return false; }
-	if &proof.C_v1 == &proof.C_v2 { return false; }
+	if proof.C == proof.C_v1 || proof.C == proof.C_v2 || proof.C_v1 == proof.C_v2 {
+		return Err(VerifyError::DuplicateCommitment);
+	}
+
+	// 6. The IPP must open P = g'^l h_vec^r u^t_hat rebuilt from A and S, so t_hat is the
+	// inner product of the committed vectors whatever t0, t1, t2 the prover sent
+	let weights = range_weights(backend, &ch.y, &ch.z, dimension);
+	let gens = IppGenerators::new(backend, dimension);
+	let P = ipp_statement(backend, &gens, &proof.A, &proof.S, &proof.t_hat, &proof.mu, &ch.x, &weights);
+	if !verify_inner_product(backend, &weights.generators(backend, &gens), &P, &proof.ipp_proof) { return Err(VerifyError::THatIppMismatch); }
+
+	// 7. g^t_hat h^tau_x = C_v1^z C_v2^(z^2) g^δ T1^x T2^(x^2): the squares of block 0 sum to
+	// the value in C_v1 and those of block 1 to the value in C_v2
+	if !range_relation_holds(backend, &[&proof.C_v1, &proof.C_v2], &proof.T1, &proof.T2, &proof.t_hat, &proof.tau_x, &ch.x, &weights) {
+		return Err(VerifyError::THatCommitmentMismatch);
+	}
+
+	Ok(())
+}
+
+/// Verify an inner product argument against the vector commitment P
+/// - params: backend, generators of the proof's dimension, P = `ipp_commitment(..)`, proof
+/// - returns: true if folding P and the generators by the recomputed challenges opens to (a, b)
+/// - usage: pair with `prove_inner_product`; P and the generators are borrowed, not cloned
+#[allow(non_snake_case)]
+pub fn verify_inner_product<B: GroupBackend<Element = BigInt>>(backend: &B, gens: &IppGenerators<BigInt>, P: &BigInt, proof: &IPPProof) -> bool {
+	if proof.L.len() != proof.R.len() || gens.h_vec.len() != gens.g_vec.len() { return false; }
+	if expected_ipp_levels(gens.g_vec.len()) != Ok(proof.L.len()) { return false; }
+
+	// Borrow the caller's P and generators until the first fold replaces them
+	let mut P = Cow::Borrowed(P);
+	let mut g_vec = Cow::Borrowed(gens.g_vec.as_slice());
+	let mut h_vec = Cow::Borrowed(gens.h_vec.as_slice());
+	for (L, R) in proof.L.iter().zip(&proof.R) {
+		let x = backend.challenge(&[&P, L, R]);
+		if x == BigInt::from(0) { return false; }
+		P = Cow::Owned(fold_ipp_commitment(backend, &P, L, R, &x));
+		let (g_new, h_new) = fold_ipp_generators(backend, &g_vec, &h_vec, &x);
+		(g_vec, h_vec) = (Cow::Owned(g_new), Cow::Owned(h_new));
+	}
+
+	let expected = backend.multi_exp(
+		&[g_vec[0].clone(), h_vec[0].clone(), gens.u.clone()],
+		&[proof.a.clone(), proof.b.clone(), &proof.a * &proof.b],
+	);
+	ct_eq(&P, &expected, backend.scalar_modulus())
+}
+
+/// Number of IPP recursion levels for a proof of the given dimension: log2(dimension),
+/// computed exactly as `trailing_zeros` (a float log2 can round wrong near powers of two)
+pub fn expected_ipp_levels(dimension: usize) -> Result<usize, VerifyError> {
+	if !dimension.is_power_of_two() { return Err(VerifyError::InvalidDimension(dimension)); }
+	Ok(dimension.trailing_zeros() as usize)
+}
 
-	true
+/// Same checks as `cuproof_verify_detailed` for a proof built with `cuproof_prove_with_dimension`
+pub fn cuproof_verify_with_dimension(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt, dimension: usize) -> Result<(), VerifyError> {
+	let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+	let ch = derive_challenges(proof, &backend)?;
+	verify_with_challenges(proof, g, h, n, None, &backend, &ch, dimension)
 }
 
+/// Range-level sanity checks shared by `cuproof_verify_with_range` and `prove_and_verify`
+fn check_range(a: &BigInt, b: &BigInt) -> Result<(), VerifyError> {
+	// a == b would make the proof degenerate; reject for now
+	if a >= b { return Err(VerifyError::InvalidRange); }
+	Ok(())
+}
+
+/// C_v1 and C_v2 must commit to 4v-4a+1 and 4b-4v+1 for the C in the proof
+fn check_range_binding(proof: &Cuproof, a: &BigInt, b: &BigInt, g: &BigInt, n: &BigInt) -> Result<(), VerifyError> {
+	if !range_binding_holds(&proof.C, &proof.C_v1, &proof.C_v2, a, b, g, n) {
+		return Err(VerifyError::RangeBindingMismatch);
+	}
+	Ok(())
+}
+
+/// Check C_v1, C_v2 open to the auxiliary values of a revealed v
+/// - params: proof, revealed v, public range a/b, blindings r_v1/r_v2 of C_v1/C_v2, g/h/n
+/// - returns: true if C_v1 = Commit(4v-4a+1, r_v1) and C_v2 = Commit(4b-4v+1, r_v2)
+/// - usage: after a value is disclosed; `cuproof_prove` uses r_v1 = 4r and r_v2 = -4r
+#[allow(clippy::too_many_arguments)]
+pub fn verify_auxiliary_openings(proof: &Cuproof, v: &BigInt, a: &BigInt, b: &BigInt, r_v1: &BigInt, r_v2: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+	let (v1, v2) = expected_auxiliary(v, a, b);
+	ct_eq(&pedersen_commit(g, h, &v1, r_v1, n), &proof.C_v1, n) & ct_eq(&pedersen_commit(g, h, &v2, r_v2, n), &proof.C_v2, n)
+}
+
+/// Generate a proof and immediately verify it with the prover's own challenges
+/// - params: same as `cuproof_prove`
+/// - returns: the proof if it verifies, otherwise the failing check
+/// - usage: catch prover bugs at generation time instead of at the verifier
+pub fn prove_and_verify(v: &BigInt, r: &BigInt, a: &BigInt, b: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<Cuproof, VerifyError> {
+	check_range(a, b)?;
+	let (proof, challenges) = cuproof_prove_with_challenges::<DefaultChallengeHasher, _>(v, r, a, b, g, h, n, 64, &mut rand::rngs::OsRng)
+		.map_err(VerifyError::Prove)?;
+	verify_with_challenges(&proof, g, h, n, None, &RsaBackend::<DefaultChallengeHasher>::new(g, h, n), &challenges, DEFAULT_DIMENSION)?;
+	check_range_binding(&proof, a, b, g, n)?;
+	Ok(proof)
+}
+
+/// Range verification, a strict superset of `cuproof_verify`
+/// - returns: true only if `cuproof_verify` accepts and, additionally, a < b and C_v1, C_v2 are
+///   bound to C and [a, b]
+/// - usage: a proof accepted here is always accepted by `cuproof_verify`, never the reverse
 pub fn cuproof_verify_with_range(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt, a: &BigInt, b: &BigInt) -> bool {
-    if !cuproof_verify(proof, g, h, n) { return false; }
+    cuproof_verify_with_range_detailed(proof, g, h, n, a, b).is_ok()
+}
+
+/// Verify a `MinimalCuproof` for the range [a, b]
+/// - params: proof from `Cuproof::to_minimal`, g/h/n public parameters, a/b public range
+/// - returns: same result as `cuproof_verify_with_range` on the full proof
+/// - usage: T1, T2, t_hat and tau_x are rebuilt with `MinimalCuproof::expand` before the usual checks
+pub fn cuproof_verify_minimal(proof: &MinimalCuproof, g: &BigInt, h: &BigInt, n: &BigInt, a: &BigInt, b: &BigInt) -> bool {
+	cuproof_verify_with_range(&proof.expand(g, h, n), g, h, n, a, b)
+}
+
+/// Range verification of a proof for a commitment published out-of-band
+/// - params: proof, expected_c the published commitment to v, g/h/n public parameters, a/b public range
+/// - returns: true only if `cuproof_verify_with_range` accepts and proof.C equals expected_c
+/// - usage: C posted first (e.g. on-chain), the range proof later; without this check a valid
+///   proof for any other commitment would be accepted
+pub fn cuproof_verify_against_commitment(proof: &Cuproof, expected_c: &BigInt, g: &BigInt, h: &BigInt, n: &BigInt, a: &BigInt, b: &BigInt) -> bool {
+	ct_eq(&proof.C, expected_c, n) & cuproof_verify_with_range(proof, g, h, n, a, b)
+}
+
+/// Verify a `VerifierProof` from `Cuproof::to_verifier`
+/// - params: proof, g/h/n public parameters
+/// - returns: the first failing check, as `cuproof_verify_detailed` would report it for the full
+///   proof (minus `T1Mismatch`, `T2Mismatch` and `THatInconsistent`, which need the openings)
+/// - usage: verifiers that store or relay proofs without the prover-only fields, which would
+///   disclose v (see `Cuproof::to_verifier`)
+pub fn cuproof_verify_verifier_proof(proof: &VerifierProof, g: &BigInt, h: &BigInt, n: &BigInt) -> Result<(), VerifyError> {
+	let backend = RsaBackend::<DefaultChallengeHasher>::new(g, h, n);
+	let ch = transcript_challenges(&backend, [&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2], [&proof.T1, &proof.T2])?;
+	verify_verifier_with_challenges(proof, n, &backend, &ch, DEFAULT_DIMENSION)
+}
+
+/// Verify a `prove_bits` proof that the committed value fits in k bits
+/// - params: proof, k bit width, g/h/n public parameters
+/// - returns: true if the proof verifies for [0, 2^k - 1] (false for k = 0)
+/// - usage: a k-bit proof does not verify for any other k, since C_v2 is bound to 2^k - 1
+pub fn verify_bits(proof: &Cuproof, k: u32, g: &BigInt, h: &BigInt, n: &BigInt) -> bool {
+	if k == 0 { return false; }
+	let (a, b) = bit_range(k);
+	cuproof_verify_with_range(proof, g, h, n, &a, &b)
+}
+
+/// Same checks as `cuproof_verify_with_range`, but reports which one failed
+pub fn cuproof_verify_with_range_detailed(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt, a: &BigInt, b: &BigInt) -> Result<(), VerifyError> {
+    cuproof_verify_with_range_and_dimension(proof, g, h, n, a, b, DEFAULT_DIMENSION)
+}
+
+/// Same checks as `cuproof_verify_with_range_detailed` for a proof built with
+/// `cuproof_prove_with_dimension`; pass `proof.dimension()` to accept whatever dimension it was built at
+pub fn cuproof_verify_with_range_and_dimension(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &BigInt, a: &BigInt, b: &BigInt, dimension: usize) -> Result<(), VerifyError> {
+    cuproof_verify_with_dimension(proof, g, h, n, dimension)?;
 
     // Basic range-consistency checks via commitments C_v1 and C_v2
     // Expected: v1 = 4v - 4a + 1, v2 = 4b - 4v + 1
@@ -62,23 +437,21 @@ pub fn cuproof_verify_with_range(proof: &Cuproof, g: &BigInt, h: &BigInt, n: &Bi
     // C_v1 and C_v2 are consistent with some v relative to (a,b) bounds using inequalities:
     // For any v in [a,b], v1 >= 1 and v2 >= 1.
     // So we ensure that C_v1 and C_v2 are non-trivial and distinct from C, already checked above.
-    // Strengthen: ensure a <= b, and they are non-negative (typical demo domain)
-    if a > b { return false; }
-
+    // Strengthen: ensure a < b (a == b would make the proof degenerate)
     // Additional conservative checks:
     // - Ensure T1, T2, tau1, tau2 not zero already done in cuproof_verify
     // - Ensure commitments are not equal pairwise already done
-    // Range-specific simple guard: if a == b then proof should degenerate; reject for now
-    if a == b { return false; }
+    check_range(a, b)?;
 
-    true
+    // Bind C_v1, C_v2 to C and [a, b]; without this a proof for any range verified for every range
+    check_range_binding(proof, a, b, g, n)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::setup::fast_test_setup;
-    use crate::range_proof::cuproof_prove;
+    use crate::range_proof::{cuproof_prove, padded_range_witness, prove_committed};
     use crate::util::random_bigint;
     use num_bigint::BigInt;
 
@@ -100,5 +473,471 @@ mod tests {
         let mut bad = proof.clone();
         bad.T1 = &bad.T1 + BigInt::from(1);
         assert!(!cuproof_verify_with_range(&bad, &g, &h, &n, &a, &b));
+
+        // A matching context gives the same verdicts
+        let ctx = GeneratorContext::new(&g, &h, &n);
+        assert!(cuproof_verify_with_context(&proof, &g, &h, &n, Some(&ctx)));
+        assert!(!cuproof_verify_with_context(&bad, &g, &h, &n, Some(&ctx)));
+    }
+
+    // Purpose: t_hat is checked against the IPP, not only against the prover's t0, t1, t2
+    // Params: small demo range; t0 shifted by one with t_hat recomputed to match it
+    // Output: the polynomial checks pass but the proof is rejected with THatIppMismatch
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn inconsistent_t0_fails_ipp_check() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let (proof, ch) = cuproof_prove_with_challenges::<DefaultChallengeHasher, _>(&v, &random_bigint(128), &a, &b, &g, &h, &n, 64, &mut rand::rngs::OsRng).unwrap();
+        assert_eq!(cuproof_verify_detailed(&proof, &g, &h, &n), Ok(()));
+
+        let mut forged = proof.clone();
+        forged.t0 += 1;
+        forged.t_hat = &forged.t0 + &forged.t1 * &ch.x + &forged.t2 * &ch.x * &ch.x;
+        assert_eq!(cuproof_verify_detailed(&forged, &g, &h, &n), Err(VerifyError::THatIppMismatch));
+        assert!(!cuproof_verify_with_range(&forged, &g, &h, &n, &a, &b));
+    }
+
+    // Purpose: the verifier trace records the transcript challenges and agrees with cuproof_verify
+    // Params: small demo range; honest proof and one with t0 shifted and t_hat recomputed
+    // Output: trace y equals fiat_shamir(A, S, C, C_v1, C_v2) mod challenge_modulus(n); the honest
+    //         trace passes every check; the forged one fails only THatIppMismatch and
+    //         THatCommitmentMismatch
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn verify_trace_records_challenges_and_checks() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove(&v, &random_bigint(128), &a, &b, &g, &h, &n);
+
+        let (valid, trace) = cuproof_verify_trace(&proof, &g, &h, &n);
+        assert!(valid && cuproof_verify(&proof, &g, &h, &n));
+        assert_eq!(trace.y, fiat_shamir(&[&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2]) % challenge_modulus(&n));
+        assert_eq!(trace.z, fiat_shamir(&[&trace.y]) % challenge_modulus(&n));
+        assert_eq!((&trace.T1, &trace.T2), (&proof.T1, &proof.T2));
+        assert!(trace.ipp_statement.is_some());
+        assert!(trace.checks.iter().all(|(_, passed)| *passed));
+
+        let mut forged = proof.clone();
+        forged.t0 += 1;
+        forged.t_hat = &forged.t0 + &forged.t1 * &trace.x + &forged.t2 * &trace.x * &trace.x;
+        let (valid, trace) = cuproof_verify_trace(&forged, &g, &h, &n);
+        assert!(!valid && !cuproof_verify(&forged, &g, &h, &n));
+        let failed: Vec<_> = trace.checks.iter().filter(|(_, passed)| !passed).map(|(name, _)| *name).collect();
+        assert_eq!(failed, ["THatIppMismatch", "THatCommitmentMismatch"]);
+    }
+
+    // Purpose: a valid proof is only accepted for the commitment it was built for
+    // Params: small demo range; honest proof, its own C and the commitment to a different value
+    // Output: true for proof.C, false for g^43 h^r although the proof verifies for [1, 100]
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn verify_against_commitment_rejects_other_c() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, r) = (BigInt::from(1), BigInt::from(100), random_bigint(128));
+        let proof = cuproof_prove(&BigInt::from(42), &r, &a, &b, &g, &h, &n);
+        assert!(cuproof_verify_against_commitment(&proof, &pedersen_commit(&g, &h, &BigInt::from(42), &r, &n), &g, &h, &n, &a, &b));
+
+        let other = pedersen_commit(&g, &h, &BigInt::from(43), &r, &n);
+        assert!(cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b));
+        assert!(!cuproof_verify_against_commitment(&proof, &other, &g, &h, &n, &a, &b));
+    }
+
+    // Purpose: verifying the VerifierProof agrees with verifying the full Cuproof
+    // Params: small demo range; honest proof and copies with T1, mu, t_hat, tau_x or an IPP L tampered
+    // Output: both verifiers accept the honest proof and reject each tampered one with the same
+    //         error, except that only the full proof has the T1 opening to report T1Mismatch
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn verifier_proof_agrees_with_full_verify() {
+        let (g, h, n) = fast_test_setup();
+        let proof = cuproof_prove(&BigInt::from(42), &random_bigint(128), &BigInt::from(1), &BigInt::from(100), &g, &h, &n);
+        let mut cases = vec![proof.clone(); 6];
+        cases[1].T1 += 1;
+        cases[2].mu += 1;
+        cases[3].t_hat += 1;
+        cases[4].tau_x += 1;
+        cases[5].ipp_proof.L[0] += 1;
+        for (i, case) in cases.iter().enumerate() {
+            let full = cuproof_verify_detailed(case, &g, &h, &n);
+            let reduced = cuproof_verify_verifier_proof(&case.to_verifier(), &g, &h, &n);
+            assert_eq!(full.is_ok(), i == 0, "case {i}");
+            match i {
+                1 => assert_eq!((full, reduced), (Err(VerifyError::T1Mismatch), Err(VerifyError::THatIppMismatch))),
+                _ => assert_eq!(reduced, full, "case {i}"),
+            }
+        }
+    }
+
+    // Purpose: tau_x ties t_hat to C_v1 and C_v2 through T1, T2 (the check the openings cannot replace)
+    // Params: small demo range; honest proof, then tau_x shifted, then C_v1 and C_v2 traded for
+    //         commitments to v1 + 4, v2 - 4 (bound to [0, 99]) over the witness of v1, v2
+    // Output: THatCommitmentMismatch for both tampered proofs, from the full and the verifier proof
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn tau_x_binds_t_hat_to_auxiliary_commitments() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v, r) = (BigInt::from(1), BigInt::from(100), BigInt::from(42), random_bigint(128));
+        let proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
+        assert_eq!(cuproof_verify_verifier_proof(&proof.to_verifier(), &g, &h, &n), Ok(()));
+
+        let mut bad = proof.clone();
+        bad.tau_x += 1;
+        assert_eq!(cuproof_verify_detailed(&bad, &g, &h, &n), Err(VerifyError::THatCommitmentMismatch));
+        assert_eq!(cuproof_verify_verifier_proof(&bad.to_verifier(), &g, &h, &n), Err(VerifyError::THatCommitmentMismatch));
+
+        // v1 + 4 and v2 - 4 are the auxiliary values of v for [0, 99]; with the witness for
+        // [1, 100] the blocks sum to the old values, although C_v1 C_v2 is unchanged
+        let (v1, v2) = expected_auxiliary(&v, &a, &b);
+        let d = padded_range_witness(&v1, &v2, DEFAULT_DIMENSION).unwrap();
+        let backend = RsaBackend::<DefaultChallengeHasher>::new(&g, &h, &n);
+        let (shifted, _) = prove_committed(&backend, &v, &(&v1 + 4), &(&v2 - 4), &d, &r, &mut rand::rngs::OsRng);
+        let (a2, b2) = (BigInt::from(0), BigInt::from(99));
+        assert!(range_binding_holds(&shifted.C, &shifted.C_v1, &shifted.C_v2, &a2, &b2, &g, &n));
+        assert_eq!(cuproof_verify_with_range_detailed(&shifted, &g, &h, &n, &a2, &b2), Err(VerifyError::THatCommitmentMismatch));
+        assert_eq!(cuproof_verify_verifier_proof(&shifted.to_verifier(), &g, &h, &n), Err(VerifyError::THatCommitmentMismatch));
+    }
+
+    // Purpose: externally supplied challenges match cuproof_verify when correct and fail when wrong
+    // Params: small demo range, challenges returned by the prover
+    // Output: boolean assertions on cuproof_verify_with_challenges
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn verify_with_external_challenges() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let (proof, ch) = cuproof_prove_with_challenges::<DefaultChallengeHasher, _>(&v, &random_bigint(128), &a, &b, &g, &h, &n, 64, &mut rand::rngs::OsRng).unwrap();
+        assert!(cuproof_verify(&proof, &g, &h, &n));
+        assert!(cuproof_verify_with_challenges(&proof, &g, &h, &n, &ch.y, &ch.z, &ch.x));
+
+        assert!(!cuproof_verify_with_challenges(&proof, &g, &h, &n, &ch.y, &ch.z, &(&ch.x + 1)));
+        let zero = BigInt::from(0);
+        assert!(!cuproof_verify_with_challenges(&proof, &g, &h, &n, &zero, &ch.z, &ch.x));
+        assert!(!cuproof_verify_with_challenges(&proof, &g, &h, &n, &ch.y, &zero, &ch.x));
+    }
+
+    // Purpose: IPP level count is derived exactly from the dimension
+    // Params: dimensions 1, 2, 64, 1024 and non-powers of two; a dimension-16 proof
+    // Output: 0, 1, 6, 10 levels; InvalidDimension otherwise; the 16-dim proof only verifies as 16
+    //         and reports DimensionMismatch against 64
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn ipp_levels_from_dimension() {
+        use crate::range_proof::cuproof_prove_with_dimension;
+        assert_eq!(expected_ipp_levels(1), Ok(0));
+        assert_eq!(expected_ipp_levels(2), Ok(1));
+        assert_eq!(expected_ipp_levels(64), Ok(6));
+        assert_eq!(expected_ipp_levels(1024), Ok(10));
+        for d in [0, 3, 48, 1000] {
+            assert_eq!(expected_ipp_levels(d), Err(VerifyError::InvalidDimension(d)));
+        }
+
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove_with_dimension(&v, &random_bigint(128), &a, &b, &g, &h, &n, 16);
+        assert_eq!(cuproof_verify_with_dimension(&proof, &g, &h, &n, 16), Ok(()));
+        assert_eq!(cuproof_verify_with_dimension(&proof, &g, &h, &n, 64), Err(VerifyError::DimensionMismatch { expected: 64, found: 16 }));
+    }
+
+    // Purpose: a proof built at one dimension and verified at another names both dimensions
+    // Params: fast_test_setup params, proof at dimension 128, default verifier (dimension 64)
+    // Output: Cuproof::dimension is 128; detailed verify is DimensionMismatch { 64, 128 };
+    //         an IPP too deep for any usize dimension falls back to IppLevelMismatch
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn dimension_mismatch_between_prover_and_verifier() {
+        use crate::range_proof::cuproof_prove_with_dimension;
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove_with_dimension(&v, &random_bigint(128), &a, &b, &g, &h, &n, 128);
+        assert_eq!(proof.dimension(), Some(128));
+        assert_eq!(cuproof_verify_with_dimension(&proof, &g, &h, &n, 128), Ok(()));
+        let err = cuproof_verify_detailed(&proof, &g, &h, &n).unwrap_err();
+        assert_eq!(err, VerifyError::DimensionMismatch { expected: DEFAULT_DIMENSION, found: 128 });
+        assert_eq!(err.to_string(), "DimensionMismatch (verifier expects dimension 64, proof has 128)");
+
+        let mut deep = proof.clone();
+        deep.ipp_proof.L = vec![g.clone(); usize::BITS as usize];
+        deep.ipp_proof.R = deep.ipp_proof.L.clone();
+        assert_eq!(deep.dimension(), None);
+        assert_eq!(cuproof_verify_detailed(&deep, &g, &h, &n), Err(VerifyError::IppLevelMismatch { expected: 6, found: 64 }));
+    }
+
+    // Purpose: cuproof_verify_ctx gives the same verdict as cuproof_verify
+    // Params: one honest proof and one with tampered T1, GeneratorContext built once
+    // Output: equal booleans from both APIs (true, then false)
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn verify_ctx_matches_verify() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove(&v, &random_bigint(128), &a, &b, &g, &h, &n);
+        let mut bad = proof.clone();
+        bad.T1 = &bad.T1 + BigInt::from(1);
+
+        let ctx = GeneratorContext::new(&g, &h, &n);
+        for (p, expected) in [(&proof, true), (&bad, false)] {
+            assert_eq!(cuproof_verify_ctx(p, &ctx), cuproof_verify(p, &g, &h, &n));
+            assert_eq!(cuproof_verify_ctx(p, &ctx), expected);
+        }
+    }
+
+    // Purpose: prove_and_verify returns the proof for in-range values and the failing check otherwise
+    // Params: small demo ranges with random r
+    // Output: Ok(proof) that also passes cuproof_verify; Err(InvalidRange) for a degenerate range
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn prove_and_verify_in_range() {
+        let (g, h, n) = fast_test_setup();
+        let r = random_bigint(128);
+        for (a, b, v) in [(1, 100, 42), (0, 1000, 0), (0, 1000, 1000)] {
+            let (a, b, v) = (BigInt::from(a), BigInt::from(b), BigInt::from(v));
+            let proof = prove_and_verify(&v, &r, &a, &b, &g, &h, &n).expect("in-range proof must verify");
+            assert_eq!(cuproof_verify_detailed(&proof, &g, &h, &n), Ok(()));
+        }
+        let five = BigInt::from(5);
+        assert_eq!(prove_and_verify(&five, &r, &five, &five, &g, &h, &n).unwrap_err(), VerifyError::InvalidRange);
+
+        // v below a: v1 = 4(v - a) + 1 is negative and has no witness
+        let err = prove_and_verify(&BigInt::from(0), &r, &five, &BigInt::from(10), &g, &h, &n).unwrap_err();
+        assert!(matches!(err, VerifyError::Prove(ProveError::V1Decomposition(_))), "{:?}", err);
+    }
+
+    // Purpose: prover and verifier must share the Fiat–Shamir hasher
+    // Params: fast_test_setup, range [1, 100], v = 42, Keccak-256 transcript
+    // Output: Ok with Keccak on both sides, Err when the verifier uses SHA-256
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn verify_with_matching_hasher_only() {
+        use crate::range_proof::cuproof_prove_with_hasher;
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let r = random_bigint(128);
+        let proof = cuproof_prove_with_hasher::<Keccak256Hasher>(&v, &r, &a, &b, &g, &h, &n);
+        assert_eq!(cuproof_verify_with_hasher::<Keccak256Hasher>(&proof, &g, &h, &n), Ok(()));
+        assert!(cuproof_verify_with_hasher::<Sha256Hasher>(&proof, &g, &h, &n).is_err());
+        // The default verifier uses Keccak-256 only under the `keccak` feature
+        assert_eq!(cuproof_verify_detailed(&proof, &g, &h, &n).is_ok(), cfg!(feature = "keccak"));
+    }
+
+    // Purpose: a minimal proof verifies like the full one and is smaller
+    // Params: fast_test_setup params, range [1, 100], v = 42
+    // Output: minimal proof accepted for [1, 100] and rejected for [1, 40] or with a tampered C;
+    //         expand() reproduces the prover's T1, T2, t_hat, tau_x
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn minimal_proof_verifies_and_is_smaller() {
+        use crate::range_proof::{proof_size_bytes, minimal_proof_size_bytes};
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove(&v, &random_bigint(256), &a, &b, &g, &h, &n);
+        let minimal = proof.to_minimal();
+
+        assert_eq!(minimal.expand(&g, &h, &n), proof);
+        assert!(cuproof_verify_minimal(&minimal, &g, &h, &n, &a, &b));
+        assert!(!cuproof_verify_minimal(&minimal, &g, &h, &n, &a, &BigInt::from(40)));
+        assert!(minimal_proof_size_bytes(&minimal) < proof_size_bytes(&proof));
+
+        let mut bad = minimal.clone();
+        bad.C += 1;
+        assert!(!cuproof_verify_minimal(&bad, &g, &h, &n, &a, &b));
+    }
+
+    // Purpose: soundness regression guard — a value outside [a, b] is refused by every prover and
+    //         the proofs a dishonest prover can build for it are rejected
+    // Params: fast_test_setup params, v = 150, range [0, 100]; a forgery with honest C,
+    //         C_v1 = Commit(601, 4r), C_v2 = Commit(-199, -4r) (so the range binding holds), an
+    //         all-zero witness and a real IPP over it
+    // Output: every prover entry point refuses (negative v2); a proof made for the wider range
+    //         [0, 200] is rejected against [0, 100]; the forgery fails THatCommitmentMismatch
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn out_of_range_value_cannot_be_proven() {
+        use crate::range_proof::{cuproof_prove_checked, try_cuproof_prove_with_backend, try_cuproof_prove_with_dimension, try_cuproof_prove_with_hasher, try_cuproof_prove_with_rng, ProofBuilder};
+        use crate::lagrange::SquaresError;
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v, r) = (BigInt::from(0), BigInt::from(100), BigInt::from(150), random_bigint(128));
+
+        let too_big = Err(ProveError::V2Decomposition(SquaresError::Negative));
+        assert_eq!(cuproof_prove_checked(&v, &r, &a, &b, &g, &h, &n), too_big);
+        assert_eq!(try_cuproof_prove_with_dimension(&v, &r, &a, &b, &g, &h, &n, 16), too_big);
+        assert_eq!(try_cuproof_prove_with_rng(&v, &r, &a, &b, &g, &h, &n, &mut rand::rngs::OsRng), too_big);
+        assert_eq!(try_cuproof_prove_with_hasher::<DefaultChallengeHasher>(&v, &r, &a, &b, &g, &h, &n), too_big);
+        assert_eq!(try_cuproof_prove_with_backend(&v, &r, &a, &b, &RsaBackend::<DefaultChallengeHasher>::new(&g, &h, &n)), too_big);
+        assert_eq!(try_cuproof_prove_with_dimension(&BigInt::from(-1), &r, &a, &b, &g, &h, &n, 64), Err(ProveError::V1Decomposition(SquaresError::Negative)));
+        let built = ProofBuilder::new().value(&v).range(&a, &b).params(&g, &h, &n).blinding(&r).build();
+        assert_eq!(built, Err(ProveError::V2Decomposition(SquaresError::Negative)));
+        assert_eq!(prove_and_verify(&v, &r, &a, &b, &g, &h, &n), Err(VerifyError::Prove(ProveError::V2Decomposition(SquaresError::Negative))));
+
+        let wider = cuproof_prove(&v, &r, &a, &BigInt::from(200), &g, &h, &n);
+        assert!(cuproof_verify(&wider, &g, &h, &n));
+        assert!(!cuproof_verify_with_range(&wider, &g, &h, &n, &a, &b));
+        assert_eq!(cuproof_verify_with_range_detailed(&wider, &g, &h, &n, &a, &b), Err(VerifyError::RangeBindingMismatch));
+
+        // Without tau_x tied to C_v1 and C_v2, any witness passed: the zero vector has a valid IPP
+        let (v1, v2) = expected_auxiliary(&v, &a, &b);
+        assert_eq!((v1.clone(), v2.clone()), (BigInt::from(601), BigInt::from(-199)));
+        let backend = RsaBackend::<DefaultChallengeHasher>::new(&g, &h, &n);
+        let (forged, _) = prove_committed(&backend, &v, &v1, &v2, &vec![BigInt::from(0); DEFAULT_DIMENSION], &r, &mut rand::rngs::OsRng);
+        assert!(range_binding_holds(&forged.C, &forged.C_v1, &forged.C_v2, &a, &b, &g, &n));
+        assert!(!cuproof_verify_with_range(&forged, &g, &h, &n, &a, &b));
+        assert_eq!(cuproof_verify_with_range_detailed(&forged, &g, &h, &n, &a, &b), Err(VerifyError::THatCommitmentMismatch));
+        assert_eq!(cuproof_verify_verifier_proof(&forged.to_verifier(), &g, &h, &n), Err(VerifyError::THatCommitmentMismatch));
+    }
+
+    // Purpose: a revealed value and its blinding open C_v1, C_v2 to the expected auxiliary values
+    // Params: fast_test_setup params, range [1, 100], revealed v = 42 with blinding r
+    // Output: openings accepted for (v, 4r, -4r), rejected for another v or blinding
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn auxiliary_openings_match_revealed_value() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let r = random_bigint(256);
+        let proof = cuproof_prove(&v, &r, &a, &b, &g, &h, &n);
+
+        assert_eq!(expected_auxiliary(&v, &a, &b), (BigInt::from(165), BigInt::from(233)));
+        let (r_v1, r_v2) = (4 * &r, -4 * &r);
+        assert!(verify_auxiliary_openings(&proof, &v, &a, &b, &r_v1, &r_v2, &g, &h, &n));
+        assert!(!verify_auxiliary_openings(&proof, &BigInt::from(43), &a, &b, &r_v1, &r_v2, &g, &h, &n));
+        assert!(!verify_auxiliary_openings(&proof, &v, &a, &b, &r_v2, &r_v1, &g, &h, &n));
+    }
+
+    // Purpose: the IPP binds its final scalars to the committed vectors
+    // Params: fast_test_setup params, known vectors l = 1..8, r = 8..1
+    // Output: honest proof verifies; tampered a, L[0] or P fail
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    #[allow(non_snake_case)]
+    fn inner_product_argument_binds_vectors() {
+        use crate::range_proof::{ipp_commitment, prove_inner_product};
+        let (g, h, n) = fast_test_setup();
+        let backend = RsaBackend::<Sha256Hasher>::new(&g, &h, &n);
+        let gens = IppGenerators::new(&backend, 8);
+        let l: Vec<BigInt> = (1..=8).map(BigInt::from).collect();
+        let r: Vec<BigInt> = (1..=8).rev().map(BigInt::from).collect();
+        let P = ipp_commitment(&backend, &gens, &l, &r);
+
+        let proof = prove_inner_product(&backend, &gens, &l, &r);
+        assert_eq!(proof.L.len(), 3);
+        assert!(verify_inner_product(&backend, &gens, &P, &proof));
+
+        let mut bad = proof.clone();
+        bad.a += 1;
+        assert!(!verify_inner_product(&backend, &gens, &P, &bad));
+
+        let mut bad = proof.clone();
+        bad.L[0] = &bad.L[0] * &g % &n;
+        assert!(!verify_inner_product(&backend, &gens, &P, &bad));
+
+        // Same inner product (120), different vectors
+        let other = ipp_commitment(&backend, &gens, &r, &l);
+        assert!(!verify_inner_product(&backend, &gens, &other, &proof));
+    }
+
+    // Purpose: k-bit proofs accept the largest k-bit value and reject 2^k
+    // Params: fast_test_setup params, k = 8, 16, 32
+    // Output: 2^k - 1 proves and verifies (only for its own k); 2^k and k = 0 are rejected
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn prove_and_verify_bit_width() {
+        use crate::range_proof::prove_bits;
+        let (g, h, n) = fast_test_setup();
+        let r = random_bigint(256);
+        for k in [8u32, 16, 32] {
+            let max = (BigInt::from(1) << k) - 1;
+            let proof = prove_bits(&max, &r, k, &g, &h, &n).unwrap();
+            assert!(verify_bits(&proof, k, &g, &h, &n));
+            assert!(!verify_bits(&proof, k + 1, &g, &h, &n));
+
+            let over = BigInt::from(1) << k;
+            assert!(matches!(prove_bits(&over, &r, k, &g, &h, &n), Err(ProveError::V2Decomposition(_))));
+        }
+        assert_eq!(prove_bits(&BigInt::from(0), &r, 0, &g, &h, &n).unwrap_err(), ProveError::InvalidRange);
+    }
+
+    // Purpose: check_ranges accepts honest proofs and names out-of-range group elements
+    // Params: fast_test_setup params, range [1, 100], v = 42
+    // Output: Ok for the honest proof; ElementOutOfRange for C_v1 = n, A = 0 and ipp_R = -1
+    // Usage: `cargo test -- src::verify` or `cargo test`
+    #[test]
+    fn check_ranges_on_group_elements() {
+        let (g, h, n) = fast_test_setup();
+        let (a, b, v) = (BigInt::from(1), BigInt::from(100), BigInt::from(42));
+        let proof = cuproof_prove(&v, &random_bigint(256), &a, &b, &g, &h, &n);
+        assert_eq!(proof.check_ranges(&n), Ok(()));
+
+        let mut bad = proof.clone();
+        bad.C_v1 = n.clone();
+        assert_eq!(bad.check_ranges(&n), Err(VerifyError::ElementOutOfRange("C_v1")));
+        let mut bad = proof.clone();
+        bad.A = BigInt::from(0);
+        assert_eq!(bad.check_ranges(&n), Err(VerifyError::ElementOutOfRange("A")));
+        let mut bad = proof.clone();
+        bad.ipp_proof.R[2] = BigInt::from(-1);
+        assert_eq!(bad.check_ranges(&n), Err(VerifyError::ElementOutOfRange("ipp_R")));
+
+        // Integer scalars are out of scope even when they exceed n
+        let mut big_scalar = proof.clone();
+        big_scalar.t0 = &n * 2;
+        assert_eq!(big_scalar.check_ranges(&n), Ok(()));
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use crate::setup::fast_test_setup;
+    use crate::range_proof::cuproof_prove;
+    use crate::util::random_bigint;
+    use proptest::prelude::*;
+
+    // Each case runs a fresh fast_test_setup, so keep the case count modest
+    fn config() -> ProptestConfig {
+        ProptestConfig { cases: 12, ..ProptestConfig::default() }
+    }
+
+    /// (a, b, v) with a < b and a <= v <= b, small enough for find_3_squares
+    fn in_range() -> impl Strategy<Value = (u64, u64, u64)> {
+        (0u64..5_000, 1u64..5_000).prop_flat_map(|(a, len)| (Just(a), Just(a + len), a..=a + len))
+    }
+
+    /// (a, b, v) with a < b and v outside [a, b]
+    fn out_of_range() -> impl Strategy<Value = (u64, u64, u64)> {
+        (1u64..5_000, 1u64..5_000, 1u64..1_000, any::<bool>()).prop_map(|(a, len, off, below)| {
+            let b = a + len;
+            let v = if below { a.saturating_sub(off) } else { b + off };
+            (a, b, v)
+        }).prop_filter("v must lie outside [a, b]", |&(a, b, v)| !value_in_range(&BigInt::from(v), &BigInt::from(a), &BigInt::from(b)))
+    }
+
+    proptest! {
+        #![proptest_config(config())]
+
+        // Purpose: completeness — honest proofs for a <= v <= b always verify against [a, b]
+        // Params: random (a, b, v), fresh fast_test_setup and random r per case
+        // Output: Ok(()) from cuproof_verify_with_range_detailed; proptest shrinks any failure
+        // Usage: `cargo test -- src::verify::proptests` or `cargo test`
+        #[test]
+        fn in_range_values_verify((a, b, v) in in_range()) {
+            let (g, h, n) = fast_test_setup();
+            let (a, b, v) = (BigInt::from(a), BigInt::from(b), BigInt::from(v));
+            let proof = cuproof_prove(&v, &random_bigint(128), &a, &b, &g, &h, &n);
+            prop_assert_eq!(cuproof_verify_with_range_detailed(&proof, &g, &h, &n, &a, &b), Ok(()));
+        }
+
+        // Purpose: soundness of the range binding — a proof for v outside [a, b] (made for the
+        // smallest range that contains v) is rejected when checked against [a, b]
+        // Params: random (a, b, v) with v < a or v > b
+        // Output: Err(RangeBindingMismatch)
+        // Usage: `cargo test -- src::verify::proptests` or `cargo test`
+        #[test]
+        fn out_of_range_values_rejected((a, b, v) in out_of_range()) {
+            let (g, h, n) = fast_test_setup();
+            let (a2, b2) = (a.min(v), b.max(v));
+            let (a, b, v) = (BigInt::from(a), BigInt::from(b), BigInt::from(v));
+            let proof = cuproof_prove(&v, &random_bigint(128), &BigInt::from(a2), &BigInt::from(b2), &g, &h, &n);
+            prop_assert_eq!(cuproof_verify_with_range_detailed(&proof, &g, &h, &n, &a, &b), Err(VerifyError::RangeBindingMismatch));
+        }
     }
 }
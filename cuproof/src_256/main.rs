@@ -1,38 +1,33 @@
+// Same protocol as `cuproof`, built with the `keccak` feature: Keccak-256 challenges, the 256-bit
+// setup by default, plus the EVM export and aggregated proofs
 use std::env;
 use num_bigint::BigInt;
 
-mod setup;
-mod commitment;
-mod fiat_shamir;
-mod lagrange;
-mod range_proof;
-mod verify;
-mod util;
-mod benchmark;
-mod evm;
-
-use setup::{setup_256, fast_test_setup};
-use range_proof::cuproof_prove;
-use verify::cuproof_verify_with_range;
-use util::{save_params, load_params, save_proof, load_proof, hex_to_bigint, random_bigint};
-use benchmark::{benchmark_multiple_ranges, print_benchmark_summary};
-use evm::{save_proof_for_evm, save_proof_json};
+use cuproof::setup::{setup_256, setup_256_derived_h, fast_test_setup};
+use cuproof::range_proof::cuproof_prove;
+use cuproof::aggregate::cuproof_prove_aggregated;
+use cuproof::verify::cuproof_verify_with_range_detailed;
+use cuproof::util::{save_params, load_params, save_proof, load_proof, hex_to_bigint, bigint_to_hex, random_bigint};
+use cuproof::benchmark::{benchmark_multiple_ranges, benchmark_aggregation, print_benchmark_summary};
+use cuproof::evm::{save_proof_for_evm, save_proof_json, save_aggregated_for_evm, save_aggregated_json, import_proof_json, estimate_evm_gas, cuproof_verify_reduced};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
-        eprintln!("Usage:\n  setup [256|fast] <params_path>\n  prove <params_path> <a_hex> <b_hex> <v_hex> <proof_path>\n  verify <params_path> <a_hex> <b_hex> <proof_path>\n  benchmark [256|fast] [range_lengths...]");
+        eprintln!("Usage:\n  setup [256|256-derived|fast] <params_path>\n  prove <params_path> <a_hex> <b_hex> <v_hex> <proof_path>\n  verify <params_path> <a_hex> <b_hex> <proof_path>\n  verify-json <params_path> <json_path>\n  convert <in> <out> --from hexline|json --to hexline|json --params <params_path>\n  prove-aggregated <params_path> <a_hex> <b_hex> <out_prefix> <v_hex>... [--evm]\n  benchmark [256|fast] [range_lengths...]\n  benchmark-aggregation [256|fast] <range_bits> [counts...]");
         return;
     }
     match args[1].as_str() {
         "setup" => {
-            if args.len() < 4 { eprintln!("Usage: setup [256|fast] <params_path>"); return; }
+            if args.len() < 4 { eprintln!("Usage: setup [256|256-derived|fast] <params_path>"); return; }
             let mode = args[2].as_str();
             let path = &args[3];
             let (g, h, n) = match mode {
                 "256" => setup_256(),
+                // h = derive_h(g, n): nobody knows log_g(h)
+                "256-derived" => setup_256_derived_h(),
                 "fast" => fast_test_setup(),
-                _ => { eprintln!("mode must be 256 or fast"); return; }
+                _ => { eprintln!("mode must be 256, 256-derived or fast"); return; }
             };
             if let Err(e) = save_params(path, &g, &h, &n) {
                 eprintln!("Failed to save params: {}", e);
@@ -69,6 +64,10 @@ fn main() {
                     eprintln!("Failed to save EVM format: {}", e);
                 } else {
                     println!("Saved EVM-compatible proof to {}", evm_path);
+                    match estimate_evm_gas(&proof) {
+                        Some(gas) => println!("Estimated verification gas: ~{}", gas),
+                        None => eprintln!("Failed to estimate gas: IPP depth {} is too large", proof.ipp_depth()),
+                    }
                 }
             }
             
@@ -95,8 +94,105 @@ fn main() {
                 Ok(p) => p,
                 Err(e) => { eprintln!("Failed to load proof: {}", e); return; }
             };
-            let ok = cuproof_verify_with_range(&proof, &g, &h, &n, &a, &b);
-            println!("{}", if ok { "VALID" } else { "INVALID" });
+            match cuproof_verify_with_range_detailed(&proof, &g, &h, &n, &a, &b) {
+                Ok(()) => println!("VALID"),
+                Err(e) => println!("INVALID: {}", e),
+            }
+        }
+        "verify-json" => {
+            if args.len() < 4 { eprintln!("Usage: verify-json <params_path> <json_path>"); return; }
+            let (g, h, n) = match load_params(&args[2]) {
+                Ok(t) => t,
+                Err(e) => { eprintln!("Failed to load params: {}", e); return; }
+            };
+            let proof = match std::fs::read_to_string(&args[3]).and_then(|json| import_proof_json(&json, &n)) {
+                Ok(p) => p,
+                Err(e) => { eprintln!("Failed to load JSON proof: {}", e); return; }
+            };
+            // The JSON holds the EVM-reduced proof, so check it the way the contract does
+            match cuproof_verify_reduced(&proof, &g, &h, &n) {
+                Ok(()) => println!("VALID"),
+                Err(e) => println!("INVALID: {}", e),
+            }
+        }
+        "convert" => {
+            let usage = "Usage: convert <in> <out> --from hexline|json --to hexline|json --params <params_path>";
+            let flag = |name: &str| args.iter().position(|s| s == name).and_then(|i| args.get(i + 1));
+            let (Some(input), Some(output)) = (args.get(2), args.get(3)) else { eprintln!("{}", usage); return; };
+            let (Some(from), Some(to), Some(params_path)) = (flag("--from"), flag("--to"), flag("--params")) else {
+                eprintln!("{}", usage);
+                return;
+            };
+            let (g, h, n) = match load_params(params_path) {
+                Ok(t) => t,
+                Err(e) => { eprintln!("Failed to load params: {}", e); return; }
+            };
+            let proof = match from.as_str() {
+                "hexline" => load_proof(input),
+                "json" => std::fs::read_to_string(input).and_then(|json| import_proof_json(&json, &n)),
+                other => { eprintln!("Unknown format {} (expected hexline or json)", other); return; }
+            };
+            let proof = match proof {
+                Ok(p) => p,
+                Err(e) => { eprintln!("Failed to load proof: {}", e); return; }
+            };
+            let saved = match to.as_str() {
+                "hexline" => {
+                    // The JSON keeps only values mod n, so the original prover output is gone
+                    if from == "json" {
+                        eprintln!("WARNING: JSON proofs are reduced mod n; the hex-line output is the reduced proof, check it with verify-json semantics (cuproof_verify_reduced)");
+                    }
+                    save_proof(output, &proof)
+                }
+                "json" => save_proof_json(output, &proof, &g, &h, &n),
+                other => { eprintln!("Unknown format {} (expected hexline or json)", other); return; }
+            };
+            if let Err(e) = saved {
+                eprintln!("Failed to save proof: {}", e);
+                return;
+            }
+            println!("Converted {} ({}) to {} ({})", input, from, output, to);
+        }
+        "prove-aggregated" => {
+            if args.len() < 7 { eprintln!("Usage: prove-aggregated <params_path> <a_hex> <b_hex> <out_prefix> <v_hex>... [--evm]"); return; }
+            let params_path = &args[2];
+            let a = hex_to_bigint(&args[3]);
+            let b = hex_to_bigint(&args[4]);
+            let out_prefix = &args[5];
+            let export_evm = args.contains(&"--evm".to_string());
+            let values: Vec<BigInt> = args[6..].iter()
+                .filter(|s| !s.starts_with("--"))
+                .map(|s| hex_to_bigint(s))
+                .collect();
+
+            let (g, h, n) = match load_params(params_path) {
+                Ok(t) => t,
+                Err(e) => { eprintln!("Failed to load params: {}", e); return; }
+            };
+            let blindings: Vec<BigInt> = values.iter().map(|_| random_bigint(256)).collect();
+            let proof = cuproof_prove_aggregated(&values, &blindings, &a, &b, &g, &h, &n);
+
+            let json_path = format!("{}_evm.json", out_prefix);
+            if let Err(e) = save_aggregated_json(&json_path, &proof, &g, &h, &n) {
+                eprintln!("Failed to save JSON format: {}", e);
+                return;
+            }
+            println!("Saved aggregated JSON proof ({} values) to {}", proof.len(), json_path);
+            // C_i in input order, so callers can map each commitment to its record
+            for i in 0..proof.len() {
+                if let Some(c) = proof.commitment(i) {
+                    println!("  C[{}] = 0x{}", i, bigint_to_hex(c));
+                }
+            }
+
+            if export_evm {
+                let evm_path = format!("{}_evm.sol", out_prefix);
+                if let Err(e) = save_aggregated_for_evm(&evm_path, &proof, &g, &h, &n) {
+                    eprintln!("Failed to save EVM format: {}", e);
+                } else {
+                    println!("Saved EVM-compatible aggregated proof to {}", evm_path);
+                }
+            }
         }
         "benchmark" => {
             if args.len() < 3 { 
@@ -107,6 +203,7 @@ fn main() {
             }
             
             let mode = args[2].as_str();
+            // default_setup is setup_256 under the `keccak` feature
             let use_256_setup = match mode {
                 "256" => true,
                 "fast" => false,
@@ -136,9 +233,40 @@ fn main() {
             println!("Các độ dài khoảng: {:?}", range_lengths);
             println!();
             
-            let results = benchmark_multiple_ranges(range_lengths, use_256_setup);
+            let results = benchmark_multiple_ranges(range_lengths, !use_256_setup);
             print_benchmark_summary(&results);
         }
+        "benchmark-aggregation" => {
+            if args.len() < 4 {
+                eprintln!("Usage: benchmark-aggregation [256|fast] <range_bits> [counts...]");
+                eprintln!("Example: benchmark-aggregation 256 32 1 2 4 8");
+                return;
+            }
+            let use_fast = match args[2].as_str() {
+                "256" => false,
+                "fast" => true,
+                _ => { eprintln!("Mode must be '256' or 'fast'"); return; }
+            };
+            let range_bits = match args[3].parse::<usize>() {
+                Ok(bits) => bits,
+                Err(_) => { eprintln!("Invalid range_bits: {}", args[3]); return; }
+            };
+            let mut counts = Vec::new();
+            for arg in &args[4..] {
+                match arg.parse::<usize>() {
+                    Ok(c) if c > 0 => counts.push(c),
+                    _ => { eprintln!("Invalid count: {}", arg); return; }
+                }
+            }
+            if counts.is_empty() { counts = vec![1, 2, 4, 8]; }
+
+            let results = benchmark_aggregation(&counts, range_bits, use_fast);
+            println!("{:<10} {:<15} {:<15} {:<15}", "Số giá trị", "Verify(ms)", "Size(bytes)", "Bytes/giá trị");
+            for r in &results {
+                println!("{:<10} {:<15} {:<15} {:<15.1}", r.aggregation_count, r.verify_time_ms,
+                         r.proof_size_bytes, r.proof_size_bytes as f64 / r.aggregation_count as f64);
+            }
+        }
         _ => {
             eprintln!("Unknown command");
         }
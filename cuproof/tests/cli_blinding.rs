@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cuproof_cli_{}_{}", std::process::id(), name))
+}
+
+fn cuproof(args: &[&str]) {
+    let out = Command::new(env!("CARGO_BIN_EXE_cuproof")).args(args).output().expect("run cuproof");
+    assert!(out.status.success(), "cuproof {:?} failed: {}", args, String::from_utf8_lossy(&out.stderr));
+}
+
+// Purpose: `prove --blinding` pins r and all derived nonces, so two runs give identical proofs
+// Params: fast setup, range [1, 100], v = 42, fixed blinding
+// Output: byte-identical proof files that verify; a different blinding gives a different proof
+// Usage: `cargo test --test cli_blinding` or `cargo test`
+#[test]
+fn pinned_blinding_gives_identical_proofs() {
+    let params = temp_path("params.txt");
+    let proof1 = temp_path("proof1.txt");
+    let proof2 = temp_path("proof2.txt");
+    let proof3 = temp_path("proof3.txt");
+    let params_s = params.to_str().unwrap();
+
+    cuproof(&["setup", "fast", params_s]);
+    for (path, blinding) in [(&proof1, "deadbeef"), (&proof2, "deadbeef"), (&proof3, "deadbef0")] {
+        cuproof(&["prove", params_s, "01", "64", "2a", path.to_str().unwrap(), "--blinding", blinding]);
+    }
+
+    let p1 = std::fs::read(&proof1).unwrap();
+    let p2 = std::fs::read(&proof2).unwrap();
+    let p3 = std::fs::read(&proof3).unwrap();
+    assert_eq!(p1, p2);
+    assert_ne!(p1, p3);
+
+    let out = Command::new(env!("CARGO_BIN_EXE_cuproof"))
+        .args(["verify", params_s, "01", "64", proof1.to_str().unwrap()])
+        .output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "VALID");
+
+    for p in [&params, &proof1, &proof2, &proof3] {
+        let _ = std::fs::remove_file(p);
+    }
+}
+
+// Purpose: a `--blinding` or `--blinding-env` value that is not hex is an error, not r = 0
+// Params: fast setup, range [1, 100], v = 42, blindings "xyz" and "" and an env var set to "not-hex"
+// Output: a non-hex error on stderr and no proof file for each
+// Usage: `cargo test --test cli_blinding` or `cargo test`
+#[test]
+fn invalid_blinding_hex_is_rejected() {
+    let params = temp_path("params_bad.txt");
+    let proof = temp_path("proof_bad.txt");
+    let (params_s, proof_s) = (params.to_str().unwrap(), proof.to_str().unwrap());
+    cuproof(&["setup", "fast", params_s]);
+
+    for blinding in ["xyz", ""] {
+        let out = Command::new(env!("CARGO_BIN_EXE_cuproof"))
+            .args(["prove", params_s, "01", "64", "2a", proof_s, "--blinding", blinding])
+            .output().unwrap();
+        assert!(String::from_utf8_lossy(&out.stderr).contains("--blinding: not a hex value"), "{:?}", blinding);
+        assert!(!proof.exists());
+    }
+    let out = Command::new(env!("CARGO_BIN_EXE_cuproof"))
+        .args(["prove", params_s, "01", "64", "2a", proof_s, "--blinding-env", "CUPROOF_TEST_BAD_BLINDING"])
+        .env("CUPROOF_TEST_BAD_BLINDING", "not-hex")
+        .output().unwrap();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--blinding-env: CUPROOF_TEST_BAD_BLINDING is not a hex value"));
+    assert!(!proof.exists());
+
+    let _ = std::fs::remove_file(&params);
+}
@@ -0,0 +1,33 @@
+use std::process::Command;
+
+// Purpose: `convert` moves a proof between the hex-line and JSON formats without changing it
+// Params: cuproof256 256-bit setup (the JSON stores uint256 words), range [1, 100], v = 42
+// Output: the JSON verifies with verify-json; JSON -> hexline warns about reduction and converts
+//         back to the same JSON
+// Usage: `cargo test --features keccak --test cli_convert`
+#[test]
+fn hexline_json_round_trip() {
+    let exe = env!("CARGO_BIN_EXE_cuproof256");
+    let dir = std::env::temp_dir().join(format!("cuproof_convert_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+    let (params, proof, json, back, json2) = (path("params.txt"), path("proof.txt"), path("proof.json"), path("back.txt"), path("back.json"));
+    let run = |args: &[&str]| {
+        let out = Command::new(exe).args(args).output().unwrap();
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        out
+    };
+
+    run(&["setup", "256", &params]);
+    run(&["prove", &params, "01", "64", "2a", &proof]);
+    run(&["convert", &proof, &json, "--from", "hexline", "--to", "json", "--params", &params]);
+    let out = run(&["verify-json", &params, &json]);
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "VALID");
+
+    let out = run(&["convert", &json, &back, "--from", "json", "--to", "hexline", "--params", &params]);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("WARNING"));
+    run(&["convert", &back, &json2, "--from", "hexline", "--to", "json", "--params", &params]);
+    assert_eq!(std::fs::read_to_string(&json).unwrap(), std::fs::read_to_string(&json2).unwrap());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cuproof_cli_dim_{}_{}", std::process::id(), name))
+}
+
+fn cuproof(args: &[&str]) -> Output {
+    let out = Command::new(env!("CARGO_BIN_EXE_cuproof")).args(args).output().expect("run cuproof");
+    assert!(out.status.success(), "cuproof {:?} failed: {}", args, String::from_utf8_lossy(&out.stderr));
+    out
+}
+
+// Purpose: `prove --dimension` picks the IPP dimension and `verify --dimension` must agree with it
+// Params: fast setup, range [1, 100], v = 42, dimension 16; dimension 48 as a rejected value
+// Output: VALID with 4 IPP levels for `verify --dimension 16`; DimensionMismatch for verify at the
+//         default 64 or at 32; no proof file written for dimension 48
+// Usage: `cargo test --test cli_dimension` or `cargo test`
+#[test]
+fn prove_at_chosen_dimension_verifies() {
+    let params = temp_path("params.txt");
+    let proof = temp_path("proof16.txt");
+    let rejected = temp_path("proof48.txt");
+    let (params_s, proof_s) = (params.to_str().unwrap(), proof.to_str().unwrap());
+
+    cuproof(&["setup", "fast", params_s]);
+    cuproof(&["prove", params_s, "01", "64", "2a", proof_s, "--dimension", "16"]);
+
+    let out = cuproof(&["verify", params_s, "01", "64", proof_s, "--dimension", "16"]);
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "VALID");
+    // The verifier, not the proof, decides the dimension
+    let out = cuproof(&["verify", params_s, "01", "64", proof_s]);
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "INVALID: DimensionMismatch (verifier expects dimension 64, proof has 16)");
+    let out = cuproof(&["verify", params_s, "01", "64", proof_s, "--dimension", "32"]);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("DimensionMismatch (verifier expects dimension 32, proof has 16)"));
+    let out = cuproof(&["inspect", proof_s]);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("IPP levels: 4 "));
+
+    let out = cuproof(&["prove", params_s, "01", "64", "2a", rejected.to_str().unwrap(), "--dimension", "48"]);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--dimension requires a power of two of at least 8"));
+    assert!(!rejected.exists());
+
+    for p in [&params, &proof] {
+        let _ = std::fs::remove_file(p);
+    }
+}
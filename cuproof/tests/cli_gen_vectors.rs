@@ -0,0 +1,39 @@
+use cuproof::util::{hex_to_bigint, load_params, load_proof};
+use cuproof::verify::cuproof_verify_with_range;
+use std::process::Command;
+
+// Purpose: `gen-vectors` output is reproducible and every vector re-verifies to its expected outcome
+// Params: two output directories written by the same command
+// Output: byte-identical files across runs; VALID/INVALID matches cuproof_verify_with_range
+// Usage: `cargo test --test cli_gen_vectors` or `cargo test`
+#[test]
+fn generated_vectors_reverify() {
+    let exe = env!("CARGO_BIN_EXE_cuproof");
+    let base = std::env::temp_dir().join(format!("cuproof_vectors_{}", std::process::id()));
+    let (first, second) = (base.join("first"), base.join("second"));
+    for dir in [&first, &second] {
+        let out = Command::new(exe).args(["gen-vectors", dir.to_str().unwrap()]).output().unwrap();
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    }
+
+    let manifest = std::fs::read_to_string(first.join("vectors.txt")).unwrap();
+    assert_eq!(manifest, std::fs::read_to_string(second.join("vectors.txt")).unwrap());
+    let (g, h, n) = load_params(first.join("params.txt").to_str().unwrap()).unwrap();
+
+    let (mut valid, mut invalid) = (0, 0);
+    for line in manifest.lines().filter(|l| !l.starts_with('#')) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [file, a, b, expected] = fields[..] else { panic!("malformed manifest line: {}", line) };
+        assert_eq!(std::fs::read(first.join(file)).unwrap(), std::fs::read(second.join(file)).unwrap(), "{} differs between runs", file);
+        let proof = load_proof(first.join(file).to_str().unwrap()).unwrap();
+        let ok = cuproof_verify_with_range(&proof, &g, &h, &n, &hex_to_bigint(a), &hex_to_bigint(b));
+        match expected {
+            "VALID" => { assert!(ok, "{} should verify", file); valid += 1; }
+            "INVALID" => { assert!(!ok, "{} should be rejected", file); invalid += 1; }
+            other => panic!("unknown outcome {}", other),
+        }
+    }
+    assert!(valid > 0 && invalid > 0);
+
+    let _ = std::fs::remove_dir_all(&base);
+}
@@ -0,0 +1,28 @@
+use std::process::Command;
+
+// Purpose: `inspect` prints per-scalar bit lengths, the IPP level count and the proof size
+// Params: fast setup, range [1, 100], v = 42 (default dimension 64)
+// Output: "IPP levels: 6" with consistent L/R and a "Proof size" line
+// Usage: `cargo test --test cli_inspect` or `cargo test`
+#[test]
+fn inspect_reports_ipp_levels() {
+    let exe = env!("CARGO_BIN_EXE_cuproof");
+    let dir = std::env::temp_dir();
+    let params = dir.join(format!("cuproof_inspect_{}_params.txt", std::process::id()));
+    let proof = dir.join(format!("cuproof_inspect_{}_proof.txt", std::process::id()));
+    let (params_s, proof_s) = (params.to_str().unwrap(), proof.to_str().unwrap());
+
+    for args in [vec!["setup", "fast", params_s], vec!["prove", params_s, "01", "64", "2a", proof_s]] {
+        let out = Command::new(exe).args(&args).output().unwrap();
+        assert!(out.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&out.stderr));
+    }
+
+    let out = Command::new(exe).args(["inspect", proof_s]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("IPP levels: 6 (L=6, R=6, consistent)"), "{}", stdout);
+    assert!(stdout.contains("Proof size: "), "{}", stdout);
+    assert!(stdout.contains("T1"), "{}", stdout);
+
+    let _ = std::fs::remove_file(&params);
+    let _ = std::fs::remove_file(&proof);
+}
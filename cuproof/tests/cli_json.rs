@@ -0,0 +1,53 @@
+use std::process::Command;
+
+/// Raw value of a top-level `"key":value` pair in a flat JSON object
+fn json_field<'a>(json: &'a str, key: &str) -> &'a str {
+    let tag = format!("\"{}\":", key);
+    let start = json.find(&tag).unwrap_or_else(|| panic!("missing {} in {}", key, json)) + tag.len();
+    let rest = &json[start..];
+    let end = if rest.starts_with('"') {
+        rest[1..].find('"').unwrap() + 2
+    } else {
+        rest.find([',', '}']).unwrap()
+    };
+    &rest[..end]
+}
+
+// Purpose: `--json` turns prove/verify output into a single parseable JSON object
+// Params: fast setup, range [1, 100], v = 42
+// Output: prove reports proof_path, size_bytes, prove_ms; verify reports valid and verify_ms
+// Usage: `cargo test --test cli_json` or `cargo test`
+#[test]
+fn prove_and_verify_emit_json() {
+    let exe = env!("CARGO_BIN_EXE_cuproof");
+    let dir = std::env::temp_dir();
+    let params = dir.join(format!("cuproof_json_{}_params.txt", std::process::id()));
+    let proof = dir.join(format!("cuproof_json_{}_proof.txt", std::process::id()));
+    let (params_s, proof_s) = (params.to_str().unwrap(), proof.to_str().unwrap());
+
+    let out = Command::new(exe).args(["setup", "fast", params_s]).output().unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+    let out = Command::new(exe).args(["--json", "prove", params_s, "01", "64", "2a", proof_s]).output().unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let line = stdout.trim();
+    assert_eq!(line.lines().count(), 1, "{}", stdout);
+    assert!(line.starts_with('{') && line.ends_with('}'), "{}", line);
+    assert_eq!(json_field(line, "proof_path"), format!("\"{}\"", proof_s.replace('\\', "\\\\")));
+    assert!(json_field(line, "size_bytes").parse::<usize>().unwrap() > 0);
+    json_field(line, "prove_ms").parse::<u128>().unwrap();
+
+    let out = Command::new(exe).args(["verify", params_s, "01", "64", proof_s, "--json"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let line = stdout.trim();
+    assert_eq!(json_field(line, "valid"), "true", "{}", line);
+    json_field(line, "verify_ms").parse::<u128>().unwrap();
+
+    // Without the flag the human-readable output is unchanged
+    let out = Command::new(exe).args(["verify", params_s, "01", "64", proof_s]).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "VALID");
+
+    let _ = std::fs::remove_file(&params);
+    let _ = std::fs::remove_file(&proof);
+}
@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cuproof_cli_batch_{}_{}", std::process::id(), name))
+}
+
+fn cuproof(args: &[&str]) -> Output {
+    let out = Command::new(env!("CARGO_BIN_EXE_cuproof")).args(args).output().expect("run cuproof");
+    assert!(out.status.success(), "cuproof {:?} failed: {}", args, String::from_utf8_lossy(&out.stderr));
+    out
+}
+
+// Purpose: `prove-batch` writes one proof per CSV row and each proof verifies for its own range
+// Params: fast setup, rows (42 in [1, 100]), (0 in [0, 255]), (1500 in [1000, 2000]) in hex
+// Output: proof_1.txt .. proof_3.txt exist and verify VALID; summary reports 3 of 3 rows
+// Usage: `cargo test --test cli_prove_batch` or `cargo test`
+#[test]
+fn prove_batch_writes_one_proof_per_row() {
+    let params = temp_path("params.txt");
+    let csv = temp_path("input.csv");
+    let out_dir = temp_path("proofs");
+    let params_s = params.to_str().unwrap();
+    let rows = [("2a", "01", "64"), ("00", "00", "ff"), ("05dc", "03e8", "07d0")];
+    let body: String = rows.iter().map(|(v, a, b)| format!("{},{},{}\n", v, a, b)).collect();
+    std::fs::write(&csv, body).unwrap();
+
+    cuproof(&["setup", "fast", params_s]);
+    let out = cuproof(&["prove-batch", params_s, csv.to_str().unwrap(), out_dir.to_str().unwrap()]);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Proved 3 of 3 rows (0 failed)"));
+
+    for (i, (_, a, b)) in rows.iter().enumerate() {
+        let proof = out_dir.join(format!("proof_{}.txt", i + 1));
+        assert!(proof.exists(), "missing {}", proof.display());
+        let out = cuproof(&["verify", params_s, a, b, proof.to_str().unwrap()]);
+        assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "VALID");
+    }
+
+    let _ = std::fs::remove_dir_all(&out_dir);
+    for p in [&params, &csv] {
+        let _ = std::fs::remove_file(p);
+    }
+}
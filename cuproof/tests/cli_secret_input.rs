@@ -0,0 +1,45 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cuproof_cli_secret_{}_{}", std::process::id(), name))
+}
+
+fn cuproof(args: &[&str]) -> Output {
+    let out = Command::new(env!("CARGO_BIN_EXE_cuproof")).args(args).output().expect("run cuproof");
+    assert!(out.status.success(), "cuproof {:?} failed: {}", args, String::from_utf8_lossy(&out.stderr));
+    out
+}
+
+// Purpose: `--value-stdin` and `--blinding-env` give the same proof as passing both on argv
+// Params: fast setup, range [1, 100], v = 42 piped on stdin, blinding deadbeef in CUPROOF_TEST_BLINDING
+// Output: byte-identical proof files; the stdin/env proof verifies VALID
+// Usage: `cargo test --test cli_secret_input` or `cargo test`
+#[test]
+fn value_from_stdin_matches_argv() {
+    let params = temp_path("params.txt");
+    let argv_proof = temp_path("argv.txt");
+    let stdin_proof = temp_path("stdin.txt");
+    let params_s = params.to_str().unwrap();
+
+    cuproof(&["setup", "fast", params_s]);
+    cuproof(&["prove", params_s, "01", "64", "2a", argv_proof.to_str().unwrap(), "--blinding", "deadbeef"]);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cuproof"))
+        .args(["prove", params_s, "01", "64", stdin_proof.to_str().unwrap(), "--value-stdin", "--blinding-env", "CUPROOF_TEST_BLINDING"])
+        .env("CUPROOF_TEST_BLINDING", "deadbeef")
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn().expect("run cuproof");
+    child.stdin.take().unwrap().write_all(b"2a\n").unwrap();
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success(), "prove --value-stdin failed: {}", String::from_utf8_lossy(&out.stderr));
+
+    assert_eq!(std::fs::read(&argv_proof).unwrap(), std::fs::read(&stdin_proof).unwrap());
+    let out = cuproof(&["verify", params_s, "01", "64", stdin_proof.to_str().unwrap()]);
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "VALID");
+
+    for p in [&params, &argv_proof, &stdin_proof] {
+        let _ = std::fs::remove_file(p);
+    }
+}
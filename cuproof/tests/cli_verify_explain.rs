@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn temp_path(bin: &str, name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cuproof_explain_{}_{}_{}", bin, std::process::id(), name))
+}
+
+fn output(exe: &str, args: &[&str]) -> Output {
+    let out = Command::new(exe).args(args).output().expect("run cli");
+    assert!(out.status.success(), "{:?} failed: {}", args, String::from_utf8_lossy(&out.stderr));
+    out
+}
+
+fn run(exe: &str, args: &[&str]) -> String {
+    String::from_utf8_lossy(&output(exe, args).stdout).into_owned()
+}
+
+/// Flip the last hex digit of T1 in the text-format proof lines
+fn flip_t1(lines: &mut [String], t1_line: usize) {
+    let last = lines[t1_line].pop().unwrap();
+    lines[t1_line].push(if last == '0' { '1' } else { '0' });
+}
+
+/// Prove, tamper with T1 via `tamper`, and check the reported reason
+fn tampered_t1_is_explained(exe: &str, bin: &str, setup_mode: &str, tamper: fn(&Path)) {
+    let params = temp_path(bin, "params.txt");
+    let proof = temp_path(bin, "proof.txt");
+    let (params_s, proof_s) = (params.to_str().unwrap(), proof.to_str().unwrap());
+
+    run(exe, &["setup", setup_mode, params_s]);
+    run(exe, &["prove", params_s, "01", "64", "2a", proof_s]);
+    assert_eq!(run(exe, &["verify", params_s, "01", "64", proof_s]).trim(), "VALID");
+
+    tamper(&proof);
+    assert_eq!(run(exe, &["verify", params_s, "01", "64", proof_s]).trim(), "INVALID: T1Mismatch");
+
+    let _ = std::fs::remove_file(&params);
+    let _ = std::fs::remove_file(&proof);
+}
+
+/// cuproof files open with a header and end in a checksum, so tampering that is meant to reach
+/// the verifier has to re-save the proof: bump T1 and write it back with `save_proof`
+fn resave_with_bumped_t1(path: &Path) {
+    let path = path.to_str().unwrap();
+    let mut proof = cuproof::util::load_proof(path).unwrap();
+    proof.T1 += 1;
+    cuproof::util::save_proof(path, &proof).unwrap();
+}
+
+// Purpose: `verify` names the failing check instead of a bare INVALID
+// Params: fast (cuproof) / 256-bit (cuproof256) setup, range [1, 100], v = 42, proof with a corrupted T1
+// Output: "VALID" before tampering, "INVALID: T1Mismatch" after
+// Usage: `cargo test --test cli_verify_explain`; the cuproof256 case needs `--features keccak`
+#[test]
+fn verify_explains_tampered_t1() {
+    tampered_t1_is_explained(env!("CARGO_BIN_EXE_cuproof"), "cuproof", "fast", resave_with_bumped_t1);
+}
+
+#[cfg(feature = "keccak")]
+#[test]
+fn verify_explains_tampered_t1_256() {
+    tampered_t1_is_explained(env!("CARGO_BIN_EXE_cuproof256"), "cuproof256", "256", resave_with_bumped_t1);
+}
+
+// Purpose: a proof file that lost its checksum line is refused; header-less legacy files load only with --legacy-proof
+// Params: fast setup, range [1, 100], v = 42; T1 flipped in the file, then the checksum line
+//         dropped, then the header dropped as well
+// Output: no verdict and "Failed to load proof" without the checksum; "Failed to load proof"
+//         for the legacy file by default and "INVALID: T1Mismatch" with --legacy-proof
+// Usage: `cargo test --test cli_verify_explain` or `cargo test`
+#[test]
+fn verify_requires_checksum_unless_legacy() {
+    let exe = env!("CARGO_BIN_EXE_cuproof");
+    let params = temp_path("cuproof", "legacy_params.txt");
+    let proof = temp_path("cuproof", "legacy_proof.txt");
+    let (params_s, proof_s) = (params.to_str().unwrap(), proof.to_str().unwrap());
+    run(exe, &["setup", "fast", params_s]);
+    run(exe, &["prove", params_s, "01", "64", "2a", proof_s]);
+
+    let mut lines: Vec<String> = std::fs::read_to_string(&proof).unwrap().lines().map(str::to_string).collect();
+    assert!(lines.pop().unwrap().starts_with("sha256:"));
+    flip_t1(&mut lines, 3);
+    std::fs::write(&proof, lines.join("\n")).unwrap();
+    let out = output(exe, &["verify", params_s, "01", "64", proof_s]);
+    assert!(out.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Failed to load proof"));
+
+    std::fs::write(&proof, lines[1..].join("\n")).unwrap();
+    let out = output(exe, &["verify", params_s, "01", "64", proof_s]);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Failed to load proof"));
+    assert_eq!(run(exe, &["verify", params_s, "01", "64", proof_s, "--legacy-proof"]).trim(), "INVALID: T1Mismatch");
+
+    let _ = std::fs::remove_file(&params);
+    let _ = std::fs::remove_file(&proof);
+}
@@ -0,0 +1,67 @@
+use cuproof::evm::{cuproof_verify_reduced, export_proof_json, fiat_shamir_uint256, import_proof_json};
+use cuproof::fiat_shamir::{fiat_shamir_with, Keccak256Hasher, Sha256Hasher};
+use cuproof::range_proof::cuproof_prove_with_hasher;
+use cuproof::verify::cuproof_verify_with_hasher;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+// Purpose: pin what the `sha256` and `keccak` configurations share for identical params and
+//          inputs (proof fields, IPP depth, the Pedersen commitments) and what differs on purpose
+//          (the Fiat–Shamir hash, hence every challenge-dependent value)
+// Params: tests/fixtures/params_fast.txt, range [1, 100], v = 42, r = 7, default dimension 64
+// Output: both proofs verify under their own hasher and have 6 IPP levels; C, C_v1, C_v2 are
+//         equal; neither hasher accepts the other's proof
+// Usage: `cargo test --test cross_tree` or `cargo test`
+#[test]
+fn sha256_and_keccak_proofs_have_the_same_shape() {
+    let (g, h, n) = cuproof::util::load_params("tests/fixtures/params_fast.txt").unwrap();
+    let (a, b) = (BigInt::from(1), BigInt::from(100));
+    let (v, r) = (BigInt::from(42), BigInt::from(7));
+
+    let proof = cuproof_prove_with_hasher::<Sha256Hasher>(&v, &r, &a, &b, &g, &h, &n);
+    let proof_keccak = cuproof_prove_with_hasher::<Keccak256Hasher>(&v, &r, &a, &b, &g, &h, &n);
+    assert_eq!(cuproof_verify_with_hasher::<Sha256Hasher>(&proof, &g, &h, &n), Ok(()));
+    assert_eq!(cuproof_verify_with_hasher::<Keccak256Hasher>(&proof_keccak, &g, &h, &n), Ok(()));
+
+    // Structure: same IPP recursion whichever hasher drives the challenges
+    assert_eq!(proof.ipp_depth(), 6, "IPP level count for dimension 64");
+    assert_eq!(proof.ipp_depth(), proof_keccak.ipp_depth(), "IPP level count diverged between hashers");
+
+    // Intended equality: C, C_v1, C_v2 depend only on (v, r, a, b) and the params
+    assert_eq!(proof.C, proof_keccak.C, "commitment to v diverged");
+    assert_eq!(proof.C_v1, proof_keccak.C_v1, "commitment to v1 = 4v - 4a + 1 diverged");
+    assert_eq!(proof.C_v2, proof_keccak.C_v2, "commitment to v2 = 4b - 4v + 1 diverged");
+
+    // Intended difference: the challenges, so the proofs are not interchangeable
+    assert!(cuproof_verify_with_hasher::<Keccak256Hasher>(&proof, &g, &h, &n).is_err(), "keccak must reject a sha256 proof");
+    assert!(cuproof_verify_with_hasher::<Sha256Hasher>(&proof_keccak, &g, &h, &n).is_err(), "sha256 must reject a keccak proof");
+}
+
+// Purpose: the EVM export is the one place the uint256-word Keccak transcript of
+//          CuproofVerifier256.sol is used, and it does not depend on the prover's hasher
+// Params: tests/fixtures/params_fast.txt for the transcripts, setup_256 params for the export
+//         (uint256 words need n < 2^256), range [1, 100], v = 42, r = 7
+// Output: the word transcript differs from both decimal-string transcripts; the exported JSON of
+//         either configuration's proof passes cuproof_verify_reduced
+// Usage: `cargo test --test cross_tree` or `cargo test`
+#[test]
+fn evm_export_uses_its_own_transcript() {
+    let (g, h, n) = cuproof::util::load_params("tests/fixtures/params_fast.txt").unwrap();
+    let (a, b) = (BigInt::from(1), BigInt::from(100));
+    let (v, r) = (BigInt::from(42), BigInt::from(7));
+
+    let proof = cuproof_prove_with_hasher::<Sha256Hasher>(&v, &r, &a, &b, &g, &h, &n);
+    let transcript = [&proof.A, &proof.S, &proof.C, &proof.C_v1, &proof.C_v2];
+    let y_words = fiat_shamir_uint256(&transcript);
+    assert_ne!(fiat_shamir_with::<Sha256Hasher>(&transcript), y_words, "SHA-256 and Keccak-256 transcripts must differ");
+    assert_ne!(fiat_shamir_with::<Keccak256Hasher>(&transcript), y_words, "decimal and uint256-word encodings must differ");
+    assert!(!y_words.is_zero());
+
+    let (g, h, n) = cuproof::setup::setup_256();
+    let proof = cuproof_prove_with_hasher::<Sha256Hasher>(&v, &r, &a, &b, &g, &h, &n);
+    let proof_keccak = cuproof_prove_with_hasher::<Keccak256Hasher>(&v, &r, &a, &b, &g, &h, &n);
+    for p in [&proof, &proof_keccak] {
+        let exported = import_proof_json(&export_proof_json(p, &g, &h, &n), &n).unwrap();
+        assert_eq!(cuproof_verify_reduced(&exported, &g, &h, &n), Ok(()));
+    }
+}